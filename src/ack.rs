@@ -0,0 +1,290 @@
+//! Waiting until notified listeners are done with their notifications.
+
+use crate::notify::{self, NotificationPrivate};
+use crate::sync::atomic::{AtomicBool, AtomicUsize, Ordering};
+use crate::{Event, EventListener, IntoNotification};
+
+#[cfg(all(not(target_family = "wasm"), not(loom)))]
+use crate::Listener;
+
+use std::sync::Arc;
+
+use core::fmt;
+use core::future::Future;
+use core::pin::Pin;
+use core::task::{Context, Poll};
+
+/// The shared state between a notifier and the listeners it notified.
+pub(crate) struct Ack {
+    /// The number of notified listeners that are not done yet.
+    pending: AtomicUsize,
+
+    /// Whether one of the listeners has received its notification.
+    received: AtomicBool,
+
+    /// Notified once `pending` drops to zero or a listener receives its notification.
+    done: Event,
+}
+
+impl Ack {
+    fn new() -> Self {
+        Self {
+            pending: AtomicUsize::new(0),
+            received: AtomicBool::new(false),
+            done: Event::new(),
+        }
+    }
+
+    /// A listener was handed a notification.
+    pub(crate) fn acquire(&self) {
+        self.pending.fetch_add(1, Ordering::Relaxed);
+    }
+
+    /// A listener is done with its notification.
+    ///
+    /// `received` is `true` if the listener completed its wait with the notification.
+    pub(crate) fn release(&self, received: bool) {
+        if received {
+            self.received.store(true, Ordering::Release);
+        }
+
+        if self.pending.fetch_sub(1, Ordering::AcqRel) == 1 || received {
+            self.done.notify(usize::MAX);
+        }
+    }
+
+    /// Every notified listener is done.
+    fn is_done(&self) -> bool {
+        self.pending.load(Ordering::Acquire) == 0
+    }
+
+    /// A notified listener received its notification, or every notified listener is done.
+    fn is_met(&self) -> bool {
+        self.received.load(Ordering::Acquire) || self.is_done()
+    }
+}
+
+/// Waits on an [`Ack`] until a condition is met.
+struct Waiter {
+    /// The state shared with the notified listeners.
+    ack: Arc<Ack>,
+
+    /// Listening for the condition to be met.
+    listener: Option<EventListener>,
+
+    /// The number of notified listeners.
+    notified: usize,
+}
+
+impl Waiter {
+    fn new(ack: Arc<Ack>, notified: usize) -> Self {
+        Self {
+            ack,
+            listener: None,
+            notified,
+        }
+    }
+
+    #[cfg(all(not(target_family = "wasm"), not(loom)))]
+    fn wait(&mut self, cond: fn(&Ack) -> bool) {
+        while !cond(&self.ack) {
+            match self.listener.take() {
+                Some(listener) => listener.wait(),
+                None => self.listener = Some(self.ack.done.listen()),
+            }
+        }
+    }
+
+    fn poll(&mut self, cx: &mut Context<'_>, cond: fn(&Ack) -> bool) -> Poll<()> {
+        loop {
+            if cond(&self.ack) {
+                self.listener = None;
+                return Poll::Ready(());
+            }
+
+            match self.listener.as_mut() {
+                Some(listener) => {
+                    if Pin::new(listener).poll(cx).is_pending() {
+                        return Poll::Pending;
+                    }
+                    self.listener = None;
+                }
+                None => self.listener = Some(self.ack.done.listen()),
+            }
+        }
+    }
+
+    fn fmt(&self, name: &str, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct(name)
+            .field("notified", &self.notified)
+            .field("pending", &self.ack.pending.load(Ordering::Relaxed))
+            .finish()
+    }
+}
+
+/// A future that resolves once every listener notified by [`Event::notify_sync()`] is done with
+/// its notification.
+///
+/// A listener is done once it has been dropped, whether or not it received its notification.
+#[must_use = "an acknowledgement does nothing unless it is awaited or waited on"]
+pub struct Acknowledgement {
+    waiter: Waiter,
+}
+
+impl fmt::Debug for Acknowledgement {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        self.waiter.fmt("Acknowledgement", f)
+    }
+}
+
+impl Acknowledgement {
+    /// Returns the number of listeners that were notified.
+    #[inline]
+    pub fn notified(&self) -> usize {
+        self.waiter.notified
+    }
+
+    /// Blocks until every notified listener is done with its notification.
+    #[cfg(all(not(target_family = "wasm"), not(loom)))]
+    pub fn wait(mut self) {
+        self.waiter.wait(Ack::is_done);
+    }
+}
+
+impl Future for Acknowledgement {
+    type Output = ();
+
+    #[inline]
+    fn poll(mut self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Self::Output> {
+        self.waiter.poll(cx, Ack::is_done)
+    }
+}
+
+/// A future that resolves once one of the listeners notified by [`Event::notify_and_wait()`]
+/// has received its notification.
+///
+/// It resolves to `true` if a listener received its notification, and to `false` if every
+/// notified listener was dropped without receiving it.
+#[must_use = "a rendezvous does nothing unless it is awaited or waited on"]
+pub struct Rendezvous {
+    waiter: Waiter,
+}
+
+impl fmt::Debug for Rendezvous {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        self.waiter.fmt("Rendezvous", f)
+    }
+}
+
+impl Rendezvous {
+    /// Returns the number of listeners that were notified.
+    #[inline]
+    pub fn notified(&self) -> usize {
+        self.waiter.notified
+    }
+
+    /// Blocks until one of the notified listeners has received its notification.
+    ///
+    /// Returns `false` if every notified listener was dropped without receiving it.
+    #[cfg(all(not(target_family = "wasm"), not(loom)))]
+    pub fn wait(mut self) -> bool {
+        self.waiter.wait(Ack::is_met);
+        self.waiter.ack.received.load(Ordering::Acquire)
+    }
+}
+
+impl Future for Rendezvous {
+    type Output = bool;
+
+    #[inline]
+    fn poll(mut self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Self::Output> {
+        self.waiter
+            .poll(cx, Ack::is_met)
+            .map(|()| self.waiter.ack.received.load(Ordering::Acquire))
+    }
+}
+
+impl<T> Event<T> {
+    /// Notifies a number of active listeners, and returns a future that resolves once they are
+    /// done with their notifications.
+    ///
+    /// This works like [`Event::notify()`], except that the notifier can wait until every
+    /// listener it notified has been dropped, for example to know that the listeners have seen
+    /// the state that was published before the notification.
+    ///
+    /// Only the listeners notified by this call are tracked. If one of them is dropped without
+    /// receiving its notification, it is done, and the listener that the notification is
+    /// forwarded to is not tracked. Units that are stored because no listener accepted them are
+    /// not tracked either.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use event_listener::{Event, Listener};
+    ///
+    /// let event = Event::new();
+    /// let listener = event.listen();
+    ///
+    /// let ack = event.notify_sync(1);
+    /// assert_eq!(ack.notified(), 1);
+    ///
+    /// listener.wait();
+    /// ack.wait();
+    /// ```
+    pub fn notify_sync(&self, notify: impl IntoNotification<Tag = T>) -> Acknowledgement {
+        let notify = notify.into_notification();
+
+        // Make sure the notification comes after whatever triggered it.
+        notify.fence(notify::Internal::new());
+
+        let ack = Arc::new(Ack::new());
+        let inner = unsafe { &*self.inner() };
+        let notified = inner.notify_acked(notify, &ack);
+
+        Acknowledgement {
+            waiter: Waiter::new(ack, notified),
+        }
+    }
+
+    /// Notifies a number of active listeners, and returns a future that resolves once one of them
+    /// has received its notification.
+    ///
+    /// This allows handing something off to a listener without a second event to report back:
+    /// once the [`Rendezvous`] resolves to `true`, one of the notified listeners has completed
+    /// its wait. It resolves to `false` if every notified listener was dropped or
+    /// [discarded](EventListener::discard) its notification instead, and immediately if no
+    /// listener was notified.
+    ///
+    /// As with [`Event::notify_sync()`], only the listeners notified by this call are tracked.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use std::sync::Arc;
+    /// use std::thread;
+    /// use event_listener::{Event, Listener};
+    ///
+    /// let event = Arc::new(Event::new());
+    /// let listener = event.listen();
+    ///
+    /// let handle = thread::spawn(move || listener.wait());
+    ///
+    /// // Returns once the other thread has woken up.
+    /// assert!(event.notify_and_wait(1).wait());
+    /// handle.join().unwrap();
+    /// ```
+    pub fn notify_and_wait(&self, notify: impl IntoNotification<Tag = T>) -> Rendezvous {
+        let notify = notify.into_notification();
+
+        // Make sure the notification comes after whatever triggered it.
+        notify.fence(notify::Internal::new());
+
+        let ack = Arc::new(Ack::new());
+        let inner = unsafe { &*self.inner() };
+        let notified = inner.notify_acked(notify, &ack);
+
+        Rendezvous {
+            waiter: Waiter::new(ack, notified),
+        }
+    }
+}
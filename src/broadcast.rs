@@ -0,0 +1,466 @@
+//! A broadcast channel built on top of tagged events.
+//!
+//! Every message sent through the channel is delivered to every [`Receiver`]. Messages are kept in
+//! a buffer of fixed capacity, so that receivers that are not waiting at the time of sending can
+//! still catch up. A receiver that falls behind by more than the capacity misses the oldest
+//! messages and is told how many it missed with [`RecvError::Lagged`].
+//!
+//! Waiting receivers are notified with the message itself as the tag, so they usually do not
+//! need to look at the buffer at all.
+//!
+//! This module is only available with the `std` feature.
+//!
+//! # Examples
+//!
+//! ```
+//! use event_listener::broadcast;
+//!
+//! let (tx, mut rx1) = broadcast::channel(16);
+//! let mut rx2 = tx.subscribe();
+//!
+//! tx.send("hello").unwrap();
+//!
+//! # futures_lite::future::block_on(async {
+//! assert_eq!(rx1.recv().await, Ok("hello"));
+//! assert_eq!(rx2.recv().await, Ok("hello"));
+//! # });
+//! ```
+
+use crate::{Event, EventListener, IntoNotification};
+
+use std::collections::VecDeque;
+use std::error;
+use std::sync::{Arc, Mutex, MutexGuard};
+
+use core::fmt;
+use core::future::Future;
+use core::pin::Pin;
+use core::task::{Context, Poll};
+
+/// The tag of a notification: the sequence number and the message, or `None` once the channel is
+/// closed.
+type Message<T> = Option<(u64, T)>;
+
+/// Creates a broadcast channel that buffers up to `capacity` messages.
+///
+/// # Panics
+///
+/// Panics if `capacity` is zero.
+pub fn channel<T: Clone>(capacity: usize) -> (Sender<T>, Receiver<T>) {
+    assert!(capacity > 0, "broadcast channel capacity must be non-zero");
+
+    let shared = Arc::new(Shared {
+        state: Mutex::new(State {
+            buffer: VecDeque::with_capacity(capacity),
+            capacity,
+            next_seq: 0,
+            senders: 1,
+            receivers: 1,
+            closed: false,
+        }),
+        event: Event::with_tag(),
+    });
+
+    let sender = Sender {
+        shared: shared.clone(),
+    };
+    let receiver = Receiver { shared, next: 0 };
+    (sender, receiver)
+}
+
+/// The state shared between the senders and receivers.
+struct Shared<T> {
+    /// The buffered messages and the channel counters.
+    state: Mutex<State<T>>,
+
+    /// Notified when a message is sent or the channel is closed.
+    event: Event<Message<T>>,
+}
+
+struct State<T> {
+    /// The most recent messages, oldest first.
+    buffer: VecDeque<T>,
+
+    /// The maximum number of buffered messages.
+    capacity: usize,
+
+    /// The sequence number of the next message.
+    next_seq: u64,
+
+    /// The number of live senders.
+    senders: usize,
+
+    /// The number of live receivers.
+    receivers: usize,
+
+    /// Whether all senders have been dropped.
+    closed: bool,
+}
+
+impl<T> State<T> {
+    /// The sequence number of the oldest buffered message.
+    fn head_seq(&self) -> u64 {
+        self.next_seq - self.buffer.len() as u64
+    }
+}
+
+impl<T> Shared<T> {
+    fn lock(&self) -> MutexGuard<'_, State<T>> {
+        self.state.lock().unwrap_or_else(|e| e.into_inner())
+    }
+}
+
+/// The sending half of a broadcast channel.
+///
+/// Senders can be cloned. The channel is closed once all senders are dropped.
+pub struct Sender<T> {
+    shared: Arc<Shared<T>>,
+}
+
+impl<T> fmt::Debug for Sender<T> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("Sender").finish_non_exhaustive()
+    }
+}
+
+impl<T: Clone> Sender<T> {
+    /// Sends a message to every receiver.
+    ///
+    /// Returns the number of receivers that the message was sent to, or gives the message back
+    /// if there are no receivers.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use event_listener::broadcast;
+    ///
+    /// let (tx, rx) = broadcast::channel(1);
+    /// assert_eq!(tx.send(1), Ok(1));
+    ///
+    /// drop(rx);
+    /// assert_eq!(tx.send(2).unwrap_err().0, 2);
+    /// ```
+    pub fn send(&self, value: T) -> Result<usize, SendError<T>> {
+        let (seq, receivers) = {
+            let mut state = self.shared.lock();
+            if state.receivers == 0 {
+                return Err(SendError(value));
+            }
+
+            if state.buffer.len() == state.capacity {
+                state.buffer.pop_front();
+            }
+            state.buffer.push_back(value.clone());
+
+            let seq = state.next_seq;
+            state.next_seq += 1;
+            (seq, state.receivers)
+        };
+
+        // Wake up every waiting receiver with the message itself.
+        self.shared
+            .event
+            .notify(usize::MAX.additional().tag(Some((seq, value))));
+        Ok(receivers)
+    }
+
+    /// Creates a new receiver that gets every message sent after this call.
+    pub fn subscribe(&self) -> Receiver<T> {
+        let mut state = self.shared.lock();
+        state.receivers += 1;
+        Receiver {
+            shared: self.shared.clone(),
+            next: state.next_seq,
+        }
+    }
+
+    /// Returns the number of live receivers.
+    pub fn receiver_count(&self) -> usize {
+        self.shared.lock().receivers
+    }
+}
+
+impl<T> Clone for Sender<T> {
+    fn clone(&self) -> Self {
+        self.shared.lock().senders += 1;
+        Self {
+            shared: self.shared.clone(),
+        }
+    }
+}
+
+impl<T> Drop for Sender<T> {
+    fn drop(&mut self) {
+        let mut state = self.shared.lock();
+        state.senders -= 1;
+        if state.senders > 0 {
+            return;
+        }
+        state.closed = true;
+        drop(state);
+
+        // Nobody can send any more, so wake up every receiver that is still waiting.
+        self.shared
+            .event
+            .notify(usize::MAX.additional().tag_with(|| None));
+    }
+}
+
+/// The receiving half of a broadcast channel.
+///
+/// Every receiver gets its own copy of each message. Cloning a receiver creates a new receiver at
+/// the same position in the channel.
+pub struct Receiver<T> {
+    shared: Arc<Shared<T>>,
+
+    /// The sequence number of the next message to receive.
+    next: u64,
+}
+
+impl<T> fmt::Debug for Receiver<T> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("Receiver")
+            .field("next", &self.next)
+            .finish_non_exhaustive()
+    }
+}
+
+impl<T: Clone> Receiver<T> {
+    /// Receives the next message, without waiting.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use event_listener::broadcast::{self, TryRecvError};
+    ///
+    /// let (tx, mut rx) = broadcast::channel(2);
+    /// assert_eq!(rx.try_recv(), Err(TryRecvError::Empty));
+    ///
+    /// for i in 0..3 {
+    ///     tx.send(i).unwrap();
+    /// }
+    ///
+    /// // The first message was pushed out of the buffer.
+    /// assert_eq!(rx.try_recv(), Err(TryRecvError::Lagged(1)));
+    /// assert_eq!(rx.try_recv(), Ok(1));
+    /// assert_eq!(rx.try_recv(), Ok(2));
+    ///
+    /// drop(tx);
+    /// assert_eq!(rx.try_recv(), Err(TryRecvError::Closed));
+    /// ```
+    pub fn try_recv(&mut self) -> Result<T, TryRecvError> {
+        let state = self.shared.lock();
+
+        let head = state.head_seq();
+        if self.next < head {
+            let missed = head - self.next;
+            self.next = head;
+            return Err(TryRecvError::Lagged(missed));
+        }
+
+        match state.buffer.get((self.next - head) as usize) {
+            Some(value) => {
+                self.next += 1;
+                Ok(value.clone())
+            }
+            None if state.closed => Err(TryRecvError::Closed),
+            None => Err(TryRecvError::Empty),
+        }
+    }
+
+    /// Waits for the next message.
+    ///
+    /// The returned future is cancel-safe: dropping it never loses a message.
+    pub fn recv(&mut self) -> Recv<'_, T> {
+        Recv {
+            receiver: self,
+            listener: None,
+        }
+    }
+
+    /// Blocks until the next message is received.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use std::thread;
+    /// use event_listener::broadcast;
+    ///
+    /// let (tx, mut rx) = broadcast::channel(1);
+    /// thread::spawn(move || tx.send(7).unwrap());
+    ///
+    /// assert_eq!(rx.recv_blocking(), Ok(7));
+    /// ```
+    #[cfg(not(target_family = "wasm"))]
+    pub fn recv_blocking(&mut self) -> Result<T, RecvError> {
+        use crate::Listener;
+
+        loop {
+            if let Some(result) = self.try_recv_ready() {
+                return result;
+            }
+
+            // Check again after starting to listen, so that no message is missed.
+            let listener = self.shared.event.listen();
+            if let Some(result) = self.try_recv_ready() {
+                return result;
+            }
+
+            if let Some(value) = self.take_message(listener.wait()) {
+                return Ok(value);
+            }
+        }
+    }
+
+    /// Receives the next message, unless the channel is empty.
+    fn try_recv_ready(&mut self) -> Option<Result<T, RecvError>> {
+        match self.try_recv() {
+            Ok(value) => Some(Ok(value)),
+            Err(TryRecvError::Lagged(missed)) => Some(Err(RecvError::Lagged(missed))),
+            Err(TryRecvError::Closed) => Some(Err(RecvError::Closed)),
+            Err(TryRecvError::Empty) => None,
+        }
+    }
+
+    /// Accepts the message delivered with a notification, if it is the next one.
+    fn take_message(&mut self, message: Message<T>) -> Option<T> {
+        match message {
+            Some((seq, value)) if seq == self.next => {
+                self.next += 1;
+                Some(value)
+            }
+            _ => None,
+        }
+    }
+}
+
+impl<T> Clone for Receiver<T> {
+    fn clone(&self) -> Self {
+        self.shared.lock().receivers += 1;
+        Self {
+            shared: self.shared.clone(),
+            next: self.next,
+        }
+    }
+}
+
+impl<T> Drop for Receiver<T> {
+    fn drop(&mut self) {
+        self.shared.lock().receivers -= 1;
+    }
+}
+
+/// The future returned by [`Receiver::recv()`].
+#[must_use = "futures do nothing unless you `.await` or poll them"]
+pub struct Recv<'a, T> {
+    receiver: &'a mut Receiver<T>,
+    listener: Option<EventListener<Message<T>>>,
+}
+
+impl<T> fmt::Debug for Recv<'_, T> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("Recv").finish_non_exhaustive()
+    }
+}
+
+impl<T> Unpin for Recv<'_, T> {}
+
+impl<T: Clone> Future for Recv<'_, T> {
+    type Output = Result<T, RecvError>;
+
+    fn poll(mut self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Self::Output> {
+        let this = &mut *self;
+
+        loop {
+            if let Some(result) = this.receiver.try_recv_ready() {
+                this.listener = None;
+                return Poll::Ready(result);
+            }
+
+            match &mut this.listener {
+                // Check again after starting to listen, so that no message is missed.
+                None => this.listener = Some(this.receiver.shared.event.listen()),
+
+                Some(listener) => {
+                    let message = match Pin::new(listener).poll(cx) {
+                        Poll::Ready(message) => message,
+                        Poll::Pending => return Poll::Pending,
+                    };
+                    this.listener = None;
+
+                    if let Some(value) = this.receiver.take_message(message) {
+                        return Poll::Ready(Ok(value));
+                    }
+                }
+            }
+        }
+    }
+}
+
+/// The error returned by [`Sender::send()`] when there are no receivers.
+///
+/// It contains the message that could not be sent.
+#[derive(Clone, Copy, PartialEq, Eq)]
+pub struct SendError<T>(pub T);
+
+impl<T> fmt::Debug for SendError<T> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("SendError").finish_non_exhaustive()
+    }
+}
+
+impl<T> fmt::Display for SendError<T> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str("sending into a channel without receivers")
+    }
+}
+
+impl<T> error::Error for SendError<T> {}
+
+/// The error returned by [`Receiver::recv()`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum RecvError {
+    /// The receiver fell behind, and this many messages were dropped from the buffer before it
+    /// could receive them. The next call receives the oldest message that is still buffered.
+    Lagged(u64),
+
+    /// All senders were dropped and every buffered message has been received.
+    Closed,
+}
+
+impl fmt::Display for RecvError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::Lagged(missed) => write!(f, "receiver lagged behind by {} messages", missed),
+            Self::Closed => f.write_str("receiving from a closed channel"),
+        }
+    }
+}
+
+impl error::Error for RecvError {}
+
+/// The error returned by [`Receiver::try_recv()`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum TryRecvError {
+    /// There is no message to receive yet.
+    Empty,
+
+    /// The receiver fell behind, and this many messages were dropped from the buffer before it
+    /// could receive them. The next call receives the oldest message that is still buffered.
+    Lagged(u64),
+
+    /// All senders were dropped and every buffered message has been received.
+    Closed,
+}
+
+impl fmt::Display for TryRecvError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::Empty => f.write_str("receiving from an empty channel"),
+            Self::Lagged(missed) => write!(f, "receiver lagged behind by {} messages", missed),
+            Self::Closed => f.write_str("receiving from a closed channel"),
+        }
+    }
+}
+
+impl error::Error for TryRecvError {}
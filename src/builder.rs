@@ -0,0 +1,174 @@
+//! Configuring an event before it is used.
+
+#[cfg(feature = "priority-inheritance")]
+use crate::priority::Hook;
+use crate::sys::{Parent, Undelivered};
+use crate::Event;
+#[cfg(feature = "priority-inheritance")]
+use crate::PriorityInheritance;
+
+use std::sync::Arc;
+
+use core::fmt;
+
+/// A builder for an [`Event`] that does not use the default behavior.
+///
+/// This is created by [`Event::builder()`], or by [`EventBuilder::new()`] for events with a tag
+/// type. Every setting can also be changed on an existing event; the builder puts them in one
+/// place for events that are set up once. [`Event::new()`] stays the way to create an event with
+/// the default behavior.
+///
+/// # Examples
+///
+/// ```
+/// use event_listener::{Event, IntoNotification, Listener};
+///
+/// let event = Event::builder().counting(true).build();
+///
+/// event.notify(1.additional());
+/// event.listen().wait();
+/// ```
+pub struct EventBuilder<T = ()> {
+    /// Whether the event is latched, and how to produce stored tags, if it stores notifications.
+    store: Option<(bool, fn() -> T)>,
+
+    /// Forwards notifications to the parent event.
+    parent: Option<Arc<Parent>>,
+
+    /// Receives the tags that no listener received.
+    undelivered: Option<Arc<Undelivered<T>>>,
+
+    /// Boosts the producer while threads are blocked on the event.
+    #[cfg(feature = "priority-inheritance")]
+    priority: Option<Arc<Hook>>,
+}
+
+impl<T> fmt::Debug for EventBuilder<T> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let mut f = f.debug_struct("EventBuilder");
+        f.field("counting", &matches!(self.store, Some((false, _))))
+            .field("latched", &matches!(self.store, Some((true, _))))
+            .field("parent", &self.parent.is_some())
+            .field("undelivered", &self.undelivered.is_some());
+        #[cfg(feature = "priority-inheritance")]
+        f.field("priority_inheritance", &self.priority.is_some());
+        f.finish()
+    }
+}
+
+impl<T> Default for EventBuilder<T> {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl<T> EventBuilder<T> {
+    /// Creates a builder for an event with the default behavior.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use event_listener::EventBuilder;
+    ///
+    /// let event = EventBuilder::<usize>::new().build();
+    /// ```
+    #[inline]
+    pub fn new() -> Self {
+        Self {
+            store: None,
+            parent: None,
+            undelivered: None,
+            #[cfg(feature = "priority-inheritance")]
+            priority: None,
+        }
+    }
+
+    /// Links the event to a parent event, like [`Event::set_parent()`].
+    pub fn parent(self, parent: Arc<Event>) -> Self {
+        self.parent_with(parent, || ())
+    }
+
+    /// Links the event to a parent event that receives tags produced by `tag`, like
+    /// [`Event::set_parent_with()`].
+    pub fn parent_with<U, F>(mut self, parent: Arc<Event<U>>, tag: F) -> Self
+    where
+        U: Send + 'static,
+        F: Fn() -> U + Send + Sync + 'static,
+    {
+        self.parent = Some(crate::forward_to(parent, tag));
+        self
+    }
+
+    /// Registers a function that receives the tags that no listener received, like
+    /// [`Event::on_undelivered()`].
+    pub fn on_undelivered<F>(mut self, f: F) -> Self
+    where
+        F: Fn(T) + Send + Sync + 'static,
+    {
+        self.undelivered = Some(Arc::new(f));
+        self
+    }
+
+    /// Registers a hook that boosts the producer while threads are blocked on the event, like
+    /// [`Event::set_priority_inheritance()`].
+    #[cfg(feature = "priority-inheritance")]
+    pub fn priority_inheritance<P>(mut self, hook: P) -> Self
+    where
+        P: PriorityInheritance + 'static,
+    {
+        self.priority = Some(Arc::new(hook));
+        self
+    }
+
+    /// Creates the event.
+    pub fn build(self) -> Event<T> {
+        let event = Event::with_tag();
+        if self.is_default() {
+            return event;
+        }
+
+        let inner = unsafe { &*event.inner() };
+        if let Some((latched, tag)) = self.store {
+            inner.set_store(latched, tag);
+        }
+        inner.set_parent(self.parent);
+        inner.set_undelivered(self.undelivered);
+        #[cfg(feature = "priority-inheritance")]
+        inner.set_priority_inheritance(self.priority);
+        event
+    }
+
+    /// Returns `true` if the builder does not change the default behavior.
+    fn is_default(&self) -> bool {
+        let default = self.store.is_none() && self.parent.is_none() && self.undelivered.is_none();
+        #[cfg(feature = "priority-inheritance")]
+        let default = default && self.priority.is_none();
+        default
+    }
+}
+
+impl EventBuilder<()> {
+    /// Makes the event a [counting](Event::counting) event.
+    ///
+    /// This replaces a previous call to [`EventBuilder::latched()`].
+    pub fn counting(mut self, counting: bool) -> Self {
+        self.set_store(false, counting);
+        self
+    }
+
+    /// Makes the event a [latched](Event::latched) event.
+    ///
+    /// This replaces a previous call to [`EventBuilder::counting()`].
+    pub fn latched(mut self, latched: bool) -> Self {
+        self.set_store(true, latched);
+        self
+    }
+
+    fn set_store(&mut self, latched: bool, enabled: bool) {
+        if enabled {
+            self.store = Some((latched, || ()));
+        } else if matches!(self.store, Some((l, _)) if l == latched) {
+            self.store = None;
+        }
+    }
+}
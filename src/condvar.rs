@@ -0,0 +1,319 @@
+//! A condition variable built on top of [`Event`].
+//!
+//! A [`Condvar`] works with any lock that implements [`Lockable`], such as
+//! [`std::sync::Mutex`]. Waiting registers a listener while the lock is still held, and only then
+//! unlocks it, so a notification sent by a thread that changed the condition under the lock can
+//! never be missed.
+//!
+//! Waiting on a [`std::sync::Mutex`] ignores poisoning: the guard is returned either way.
+//!
+//! This module is only available with the `std` feature.
+//!
+//! # Examples
+//!
+//! ```
+//! use std::sync::{Arc, Mutex};
+//! use std::thread;
+//! use event_listener::condvar::Condvar;
+//!
+//! let pair = Arc::new((Mutex::new(false), Condvar::new()));
+//!
+//! thread::spawn({
+//!     let pair = pair.clone();
+//!     move || {
+//!         let (started, condvar) = &*pair;
+//!         *started.lock().unwrap() = true;
+//!         condvar.notify_one();
+//!     }
+//! });
+//!
+//! let (started, condvar) = &*pair;
+//! let guard = condvar.wait_while(started, started.lock().unwrap(), |started| !*started);
+//! assert!(*guard);
+//! ```
+
+use crate::{Event, EventListener};
+
+use std::sync::{Mutex, MutexGuard};
+
+use core::fmt;
+use core::future::{self, Future, Ready};
+use core::ops::{Deref, DerefMut};
+use core::pin::Pin;
+use core::task::{Context, Poll};
+
+/// A lock that a [`Condvar`] can unlock while waiting, and lock again afterwards.
+pub trait Lockable<'a> {
+    /// The guard that keeps the lock locked.
+    type Guard;
+
+    /// Blocks until the lock is locked.
+    fn lock(&'a self) -> Self::Guard;
+}
+
+/// A lock that can be locked without blocking the current thread.
+pub trait AsyncLockable<'a> {
+    /// The guard that keeps the lock locked.
+    type Guard;
+
+    /// The future that locks the lock.
+    type Lock: Future<Output = Self::Guard> + Unpin;
+
+    /// Returns a future that resolves once the lock is locked.
+    fn lock_async(&'a self) -> Self::Lock;
+}
+
+impl<'a, T: ?Sized + 'a> Lockable<'a> for Mutex<T> {
+    type Guard = MutexGuard<'a, T>;
+
+    fn lock(&'a self) -> Self::Guard {
+        Mutex::lock(self).unwrap_or_else(|e| e.into_inner())
+    }
+}
+
+/// A [`std::sync::Mutex`] is locked in place, which briefly blocks the task if another thread
+/// holds it. This is fine for short critical sections.
+impl<'a, T: ?Sized + 'a> AsyncLockable<'a> for Mutex<T> {
+    type Guard = MutexGuard<'a, T>;
+    type Lock = Ready<MutexGuard<'a, T>>;
+
+    fn lock_async(&'a self) -> Self::Lock {
+        future::ready(Lockable::lock(self))
+    }
+}
+
+/// A condition variable for both threads and async tasks.
+///
+/// See the [module documentation](self) for more information.
+pub struct Condvar {
+    event: Event,
+}
+
+impl fmt::Debug for Condvar {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("Condvar").finish_non_exhaustive()
+    }
+}
+
+impl Default for Condvar {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl Condvar {
+    /// Creates a new condition variable.
+    pub fn new() -> Self {
+        Self {
+            event: Event::new(),
+        }
+    }
+
+    /// Unlocks `guard`, blocks until this condition variable is notified, and locks `lock` again.
+    ///
+    /// Like any condition variable, this can wake up without the condition having changed, so it
+    /// is usually called in a loop. [`Condvar::wait_while()`] contains that loop.
+    #[cfg(not(target_family = "wasm"))]
+    pub fn wait<'a, L: Lockable<'a>>(&self, lock: &'a L, guard: L::Guard) -> L::Guard {
+        use crate::Listener;
+
+        let listener = self.event.listen();
+        drop(guard);
+        listener.wait();
+        lock.lock()
+    }
+
+    /// Blocks while `condition` returns `true`, and returns the guard once it returns `false`.
+    #[cfg(not(target_family = "wasm"))]
+    pub fn wait_while<'a, L, F>(
+        &self,
+        lock: &'a L,
+        mut guard: L::Guard,
+        mut condition: F,
+    ) -> L::Guard
+    where
+        L: Lockable<'a>,
+        L::Guard: DerefMut,
+        F: FnMut(&mut <L::Guard as Deref>::Target) -> bool,
+    {
+        while condition(&mut *guard) {
+            guard = self.wait(lock, guard);
+        }
+        guard
+    }
+
+    /// Unlocks `guard`, and returns a future that resolves once this condition variable is
+    /// notified and `lock` is locked again.
+    ///
+    /// The listener is registered before this method returns, so a notification sent after it
+    /// is not missed even if the future is polled later.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use std::sync::Mutex;
+    /// use event_listener::condvar::Condvar;
+    ///
+    /// let mutex = Mutex::new(0);
+    /// let condvar = Condvar::new();
+    ///
+    /// let wait = condvar.wait_async(&mutex, mutex.lock().unwrap());
+    /// *mutex.lock().unwrap() = 1;
+    /// condvar.notify_all();
+    ///
+    /// # futures_lite::future::block_on(async {
+    /// assert_eq!(*wait.await, 1);
+    /// # });
+    /// ```
+    pub fn wait_async<'a, L: AsyncLockable<'a>>(
+        &self,
+        lock: &'a L,
+        guard: L::Guard,
+    ) -> Wait<'a, L> {
+        let listener = self.event.listen();
+        drop(guard);
+
+        Wait {
+            lock,
+            listener: Some(listener),
+            relock: None,
+        }
+    }
+
+    /// Returns a future that waits while `condition` returns `true`, and resolves to the guard
+    /// once it returns `false`.
+    ///
+    /// The condition is checked right away. If it returns `true`, `guard` is unlocked before
+    /// this method returns, like with [`Condvar::wait_async()`].
+    pub fn wait_while_async<'a, 'c, L, F>(
+        &'c self,
+        lock: &'a L,
+        mut guard: L::Guard,
+        mut condition: F,
+    ) -> WaitWhile<'a, 'c, L, F>
+    where
+        L: AsyncLockable<'a>,
+        L::Guard: DerefMut,
+        F: FnMut(&mut <L::Guard as Deref>::Target) -> bool,
+    {
+        let (guard, wait) = if condition(&mut *guard) {
+            (None, Some(self.wait_async(lock, guard)))
+        } else {
+            (Some(guard), None)
+        };
+
+        WaitWhile {
+            condvar: self,
+            lock,
+            guard,
+            wait,
+            condition,
+        }
+    }
+
+    /// Wakes up one waiter.
+    ///
+    /// Every call wakes up another waiter, even if the previous one has not woken up yet.
+    pub fn notify_one(&self) {
+        self.event.notify_additional(1);
+    }
+
+    /// Wakes up all waiters.
+    pub fn notify_all(&self) {
+        self.event.notify(usize::MAX);
+    }
+}
+
+/// The future returned by [`Condvar::wait_async()`].
+#[must_use = "futures do nothing unless you `.await` or poll them"]
+pub struct Wait<'a, L: AsyncLockable<'a>> {
+    lock: &'a L,
+    listener: Option<EventListener>,
+    relock: Option<L::Lock>,
+}
+
+impl<'a, L: AsyncLockable<'a>> fmt::Debug for Wait<'a, L> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("Wait")
+            .field("notified", &self.listener.is_none())
+            .finish_non_exhaustive()
+    }
+}
+
+impl<'a, L: AsyncLockable<'a>> Future for Wait<'a, L> {
+    type Output = L::Guard;
+
+    fn poll(mut self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Self::Output> {
+        let this = &mut *self;
+
+        if let Some(listener) = &mut this.listener {
+            if Pin::new(listener).poll(cx).is_pending() {
+                return Poll::Pending;
+            }
+            this.listener = None;
+        }
+
+        let relock = this.relock.get_or_insert_with(|| this.lock.lock_async());
+        let guard = match Pin::new(relock).poll(cx) {
+            Poll::Ready(guard) => guard,
+            Poll::Pending => return Poll::Pending,
+        };
+        this.relock = None;
+        Poll::Ready(guard)
+    }
+}
+
+/// The future returned by [`Condvar::wait_while_async()`].
+#[must_use = "futures do nothing unless you `.await` or poll them"]
+pub struct WaitWhile<'a, 'c, L: AsyncLockable<'a>, F> {
+    condvar: &'c Condvar,
+    lock: &'a L,
+    guard: Option<L::Guard>,
+    wait: Option<Wait<'a, L>>,
+    condition: F,
+}
+
+impl<'a, L: AsyncLockable<'a>, F> fmt::Debug for WaitWhile<'a, '_, L, F> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("WaitWhile").finish_non_exhaustive()
+    }
+}
+
+// The condition is never pinned.
+impl<'a, L: AsyncLockable<'a>, F> Unpin for WaitWhile<'a, '_, L, F> {}
+
+impl<'a, L, F> Future for WaitWhile<'a, '_, L, F>
+where
+    L: AsyncLockable<'a>,
+    L::Guard: DerefMut,
+    F: FnMut(&mut <L::Guard as Deref>::Target) -> bool,
+{
+    type Output = L::Guard;
+
+    fn poll(mut self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Self::Output> {
+        let this = &mut *self;
+
+        loop {
+            let wait = match &mut this.wait {
+                Some(wait) => wait,
+                None => {
+                    return Poll::Ready(
+                        this.guard
+                            .take()
+                            .expect("`WaitWhile` polled after completion"),
+                    )
+                }
+            };
+
+            let mut guard = match Pin::new(wait).poll(cx) {
+                Poll::Ready(guard) => guard,
+                Poll::Pending => return Poll::Pending,
+            };
+            if !(this.condition)(&mut *guard) {
+                this.wait = None;
+                return Poll::Ready(guard);
+            }
+            this.wait = Some(this.condvar.wait_async(this.lock, guard));
+        }
+    }
+}
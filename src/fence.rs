@@ -0,0 +1,102 @@
+//! Memory fences that match the ones emitted by this crate.
+//!
+//! Before an [`Event`] is notified, and in a few other places, the crate emits a `SeqCst` fence
+//! with [`full_fence()`]. Code that keeps its own atomics next to an event, and synchronizes
+//! them with the event's listeners, can use the same fences to stay consistent with the
+//! crate's ordering, instead of reimplementing them:
+//!
+//! - [`full_fence()`] is a `SeqCst` fence. It orders every earlier load and store before every
+//!   later one, and it is what a [relaxed](crate::IntoNotification::relaxed) notification leaves
+//!   out.
+//! - [`acquire_fence()`] and [`release_fence()`] are the weaker `Acquire` and `Release` fences,
+//!   for code that only needs to synchronize through a load before, or a store after, the
+//!   fence.
+//!
+//! # Examples
+//!
+//! ```
+//! use std::sync::atomic::{AtomicBool, Ordering};
+//! use event_listener::{fence, Event, IntoNotification, Listener};
+//!
+//! let flag = AtomicBool::new(false);
+//! let event = Event::new();
+//! let listener = event.listen();
+//!
+//! // Emit the fence once, then notify without one.
+//! flag.store(true, Ordering::Relaxed);
+//! fence::full_fence();
+//! event.notify(1.relaxed());
+//!
+//! listener.wait();
+//! assert!(flag.load(Ordering::Relaxed));
+//! ```
+//!
+//! [`Event`]: crate::Event
+
+use crate::sync::atomic::{self, Ordering};
+
+/// Emits a `SeqCst` fence.
+///
+/// This is equivalent to `atomic::fence(Ordering::SeqCst)`, but in some cases faster: on x86,
+/// a `lock`-prefixed instruction on a local variable is used instead of `mfence`. Both act as a
+/// full barrier, but tools like ThreadSanitizer do not recognize the former. The
+/// `portable-fence` feature makes this always use [`fence`](core::sync::atomic::fence).
+#[inline]
+pub fn full_fence() {
+    #[cfg(all(
+        any(target_arch = "x86", target_arch = "x86_64"),
+        not(miri),
+        not(loom),
+        not(feature = "portable-fence")
+    ))]
+    {
+        use core::{arch::asm, cell::UnsafeCell};
+        // HACK(stjepang): On x86 architectures there are two different ways of executing
+        // a `SeqCst` fence.
+        //
+        // 1. `atomic::fence(SeqCst)`, which compiles into a `mfence` instruction.
+        // 2. A `lock <op>` instruction.
+        //
+        // Both instructions have the effect of a full barrier, but empirical benchmarks have shown
+        // that the second one is sometimes a bit faster.
+        let a = UnsafeCell::new(0_usize);
+        // It is common to use `lock or` here, but when using a local variable, `lock not`, which
+        // does not change the flag, should be slightly more efficient.
+        // Refs: https://www.felixcloutier.com/x86/not
+        unsafe {
+            #[cfg(target_pointer_width = "64")]
+            asm!("lock not qword ptr [{0}]", in(reg) a.get(), options(nostack, preserves_flags));
+            #[cfg(target_pointer_width = "32")]
+            asm!("lock not dword ptr [{0:e}]", in(reg) a.get(), options(nostack, preserves_flags));
+        }
+        return;
+    }
+    #[allow(unreachable_code)]
+    {
+        atomic::fence(Ordering::SeqCst);
+    }
+}
+
+/// Emits an `Acquire` fence.
+///
+/// The fence pairs with an atomic load before it: if that load reads a value written by a
+/// `Release` store or after a `Release` fence in another thread, every load and store after the
+/// fence happens after what that thread did before the store. Loads before the fence cannot be
+/// moved after any load or store that comes after it. This is
+/// `atomic::fence(Ordering::Acquire)`, and only restricts the compiler on x86.
+#[inline]
+pub fn acquire_fence() {
+    atomic::fence(Ordering::Acquire);
+}
+
+/// Emits a `Release` fence.
+///
+/// The fence pairs with an atomic store after it: if another thread reads that store with an
+/// `Acquire` load, or with a load followed by an `Acquire` fence, every load and store before
+/// the fence happens before what that thread does afterwards. Loads and stores before the fence
+/// cannot be moved after any store that comes after it. This is
+/// `atomic::fence(Ordering::Release)`, and only restricts the compiler on x86.
+#[inline]
+pub fn release_fence() {
+    atomic::fence(Ordering::Release);
+}
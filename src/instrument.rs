@@ -0,0 +1,228 @@
+//! Histograms of how long listeners wait, recorded per event.
+//!
+//! With the `instrument` feature, every event records two latencies for each listener that
+//! receives a notification:
+//!
+//! - **listen to wake**: from [`Event::listen()`](crate::Event::listen) until a notification is
+//!   handed to the listener. This is how long the listener waited for something to happen.
+//! - **notify to poll**: from that notification until the listener's task or thread picks it up
+//!   by polling or waiting on the listener. This is the wakeup latency of the scheduler.
+//!
+//! [`Event::snapshot()`](crate::Event::snapshot) returns a copy of both histograms. They count
+//! every notification since the event was created, so the difference between two snapshots
+//! covers the time in between.
+//!
+//! Listeners that are notified but dropped or discarded before they are polled only contribute
+//! to the first histogram. Recording takes the current time while the event is locked, so the
+//! feature is meant for diagnostics rather than for production hot paths. It is not supported on
+//! WebAssembly targets without a clock.
+//!
+//! # Examples
+//!
+//! ```
+//! use event_listener::{Event, Listener};
+//!
+//! let event = Event::new();
+//! let listener = event.listen();
+//! event.notify(1);
+//! listener.wait();
+//!
+//! let snapshot = event.snapshot();
+//! assert_eq!(snapshot.listen_to_wake().count(), 1);
+//! assert_eq!(snapshot.notify_to_poll().count(), 1);
+//! ```
+
+use std::time::{Duration, Instant};
+
+use core::cell::Cell;
+use core::fmt;
+
+/// The number of buckets in a [`Histogram`]. Bucket `i` covers durations below `2^(i + 1)`
+/// nanoseconds, which is enough for any [`Duration`] that fits into 64 bits of nanoseconds.
+const BUCKETS: usize = 64;
+
+/// A histogram of durations with buckets that grow in powers of two.
+///
+/// The bucket of a duration is determined by the position of the highest bit of its length in
+/// nanoseconds, so the quantiles are exact to within a factor of two.
+#[derive(Clone, PartialEq, Eq)]
+pub struct Histogram {
+    /// The number of durations in each bucket.
+    buckets: [u64; BUCKETS],
+
+    /// The number of recorded durations.
+    count: u64,
+
+    /// The sum of the recorded durations.
+    total: Duration,
+
+    /// The longest recorded duration.
+    max: Duration,
+}
+
+impl fmt::Debug for Histogram {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("Histogram")
+            .field("count", &self.count)
+            .field("mean", &self.mean())
+            .field("p50", &self.quantile(0.5))
+            .field("p99", &self.quantile(0.99))
+            .field("max", &self.max())
+            .finish()
+    }
+}
+
+impl Default for Histogram {
+    fn default() -> Self {
+        Self {
+            buckets: [0; BUCKETS],
+            count: 0,
+            total: Duration::ZERO,
+            max: Duration::ZERO,
+        }
+    }
+}
+
+impl Histogram {
+    /// Returns the number of recorded durations.
+    pub fn count(&self) -> u64 {
+        self.count
+    }
+
+    /// Returns the sum of the recorded durations.
+    pub fn total(&self) -> Duration {
+        self.total
+    }
+
+    /// Returns the mean of the recorded durations, or `None` if there are none.
+    pub fn mean(&self) -> Option<Duration> {
+        match self.count {
+            0 => None,
+            count => Some(Duration::from_nanos(
+                (self.total.as_nanos() / u128::from(count)) as u64,
+            )),
+        }
+    }
+
+    /// Returns the longest recorded duration, or `None` if there are none.
+    pub fn max(&self) -> Option<Duration> {
+        match self.count {
+            0 => None,
+            _ => Some(self.max),
+        }
+    }
+
+    /// Returns an upper bound for the `q`-quantile of the recorded durations, or `None` if there
+    /// are none.
+    ///
+    /// `q` is clamped to the range from `0.0` to `1.0`, so `quantile(0.99)` is the 99th
+    /// percentile. The result is the upper end of the bucket that holds the quantile, but never
+    /// more than [`Histogram::max()`].
+    pub fn quantile(&self, q: f64) -> Option<Duration> {
+        if self.count == 0 {
+            return None;
+        }
+
+        let rank = ((self.count as f64) * q.clamp(0.0, 1.0)).ceil().max(1.0) as u64;
+        let mut seen = 0;
+        for (i, &count) in self.buckets.iter().enumerate() {
+            seen += count;
+            if seen >= rank {
+                return Some(bucket_end(i).min(self.max));
+            }
+        }
+
+        Some(self.max)
+    }
+
+    /// Returns the non-empty buckets, in increasing order.
+    ///
+    /// Each bucket is returned as the duration that all of its entries are shorter than, and the
+    /// number of entries.
+    pub fn buckets(&self) -> impl Iterator<Item = (Duration, u64)> + '_ {
+        self.buckets
+            .iter()
+            .enumerate()
+            .filter(|(_, &count)| count > 0)
+            .map(|(i, &count)| (bucket_end(i), count))
+    }
+
+    /// Records a duration.
+    fn record(&mut self, duration: Duration) {
+        let nanos = u64::try_from(duration.as_nanos()).unwrap_or(u64::MAX);
+        let bucket = (63 - (nanos | 1).leading_zeros()) as usize;
+
+        self.buckets[bucket] += 1;
+        self.count += 1;
+        self.total = self.total.saturating_add(duration);
+        self.max = self.max.max(duration);
+    }
+}
+
+/// Returns the duration that all entries of bucket `i` are shorter than.
+fn bucket_end(i: usize) -> Duration {
+    Duration::from_nanos(2u64.saturating_pow(i as u32 + 1))
+}
+
+/// The latencies recorded by an event, returned by
+/// [`Event::snapshot()`](crate::Event::snapshot).
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct Snapshot {
+    listen_to_wake: Histogram,
+    notify_to_poll: Histogram,
+}
+
+impl Snapshot {
+    /// The time from creating a listener until it was notified.
+    pub fn listen_to_wake(&self) -> &Histogram {
+        &self.listen_to_wake
+    }
+
+    /// The time from notifying a listener until it was polled or waited on.
+    pub fn notify_to_poll(&self) -> &Histogram {
+        &self.notify_to_poll
+    }
+}
+
+/// The times at which a listener was created and notified.
+#[derive(Debug)]
+pub(crate) struct Timings {
+    listened: Instant,
+    notified: Cell<Option<Instant>>,
+}
+
+impl Timings {
+    pub(crate) fn new() -> Self {
+        Self {
+            listened: Instant::now(),
+            notified: Cell::new(None),
+        }
+    }
+}
+
+/// The histograms of an event. They are only accessed while the event is locked.
+#[derive(Debug, Default)]
+pub(crate) struct Stats(Snapshot);
+
+impl Stats {
+    /// Records that a listener was notified.
+    pub(crate) fn notified(&mut self, timings: &Timings) {
+        let now = Instant::now();
+        self.0
+            .listen_to_wake
+            .record(now.saturating_duration_since(timings.listened));
+        timings.notified.set(Some(now));
+    }
+
+    /// Records that a listener picked up its notification.
+    pub(crate) fn polled(&mut self, timings: &Timings) {
+        if let Some(notified) = timings.notified.take() {
+            self.0.notify_to_poll.record(notified.elapsed());
+        }
+    }
+
+    /// Returns a copy of the histograms.
+    pub(crate) fn snapshot(&self) -> Snapshot {
+        self.0.clone()
+    }
+}
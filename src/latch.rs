@@ -0,0 +1,184 @@
+//! A count-down latch built on top of [`Event`].
+//!
+//! A [`CountDownLatch`] starts closed with a count. Every [`CountDownLatch::count_down()`] lowers
+//! the count, and the latch opens when it reaches zero, or right away when
+//! [`CountDownLatch::open()`] is called. Once open, the latch stays open: every waiter is woken up,
+//! and every later wait completes immediately.
+//!
+//! This module is only available with the `std` feature.
+//!
+//! # Examples
+//!
+//! ```
+//! use std::sync::Arc;
+//! use std::thread;
+//! use event_listener::latch::CountDownLatch;
+//!
+//! // Wait for two services to start.
+//! let started = Arc::new(CountDownLatch::new(2));
+//!
+//! for _ in 0..2 {
+//!     let started = started.clone();
+//!     thread::spawn(move || {
+//!         // Start the service...
+//!         started.count_down();
+//!     });
+//! }
+//!
+//! started.wait_blocking();
+//! assert!(started.is_open());
+//! ```
+
+use crate::{Event, EventListener};
+
+use core::fmt;
+use core::future::Future;
+use core::pin::Pin;
+use core::sync::atomic::{AtomicUsize, Ordering};
+use core::task::{Context, Poll};
+
+/// A latch that opens once after a number of count downs.
+///
+/// See the [module documentation](self) for more information.
+pub struct CountDownLatch {
+    /// The number of count downs left before the latch opens.
+    count: AtomicUsize,
+
+    /// Notified when the latch opens.
+    event: Event,
+}
+
+impl fmt::Debug for CountDownLatch {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("CountDownLatch")
+            .field("count", &self.count())
+            .finish()
+    }
+}
+
+impl CountDownLatch {
+    /// Creates a latch that opens after `count` count downs.
+    ///
+    /// A latch created with a count of zero is open from the start.
+    #[cfg(not(loom))]
+    pub const fn new(count: usize) -> Self {
+        Self {
+            count: AtomicUsize::new(count),
+            event: Event::new(),
+        }
+    }
+
+    #[cfg(loom)]
+    pub fn new(count: usize) -> Self {
+        Self {
+            count: AtomicUsize::new(count),
+            event: Event::new(),
+        }
+    }
+
+    /// Lowers the count by one, and opens the latch if it reaches zero.
+    ///
+    /// Does nothing if the latch is already open.
+    pub fn count_down(&self) {
+        let result = self
+            .count
+            .fetch_update(Ordering::AcqRel, Ordering::Acquire, |count| {
+                count.checked_sub(1)
+            });
+
+        if result == Ok(1) {
+            self.event.notify(usize::MAX);
+        }
+    }
+
+    /// Opens the latch, no matter how many count downs are left.
+    pub fn open(&self) {
+        if self.count.swap(0, Ordering::AcqRel) != 0 {
+            self.event.notify(usize::MAX);
+        }
+    }
+
+    /// Returns `true` if the latch is open.
+    pub fn is_open(&self) -> bool {
+        self.count() == 0
+    }
+
+    /// Returns the number of count downs left before the latch opens.
+    pub fn count(&self) -> usize {
+        self.count.load(Ordering::Acquire)
+    }
+
+    /// Waits until the latch is open.
+    ///
+    /// If the latch is already open, the returned future resolves right away.
+    pub fn wait(&self) -> Wait<'_> {
+        Wait {
+            latch: self,
+            listener: None,
+        }
+    }
+
+    /// Blocks until the latch is open.
+    ///
+    /// This is the blocking version of [`CountDownLatch::wait()`].
+    #[cfg(not(target_family = "wasm"))]
+    pub fn wait_blocking(&self) {
+        use crate::Listener;
+
+        if self.is_open() {
+            return;
+        }
+
+        // Check again after starting to listen, so that the opening is not missed.
+        let listener = self.event.listen();
+        if !self.is_open() {
+            listener.wait();
+        }
+    }
+}
+
+/// The future returned by [`CountDownLatch::wait()`].
+#[must_use = "futures do nothing unless you `.await` or poll them"]
+pub struct Wait<'a> {
+    latch: &'a CountDownLatch,
+    listener: Option<EventListener>,
+}
+
+impl fmt::Debug for Wait<'_> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("Wait")
+            .field("latch", self.latch)
+            .finish_non_exhaustive()
+    }
+}
+
+impl Future for Wait<'_> {
+    type Output = ();
+
+    fn poll(mut self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<()> {
+        let this = &mut *self;
+
+        if this.latch.is_open() {
+            this.listener = None;
+            return Poll::Ready(());
+        }
+
+        let listener = match &mut this.listener {
+            Some(listener) => listener,
+            None => {
+                // Check again after starting to listen, so that the opening is not missed.
+                let listener = this.latch.event.listen();
+                if this.latch.is_open() {
+                    return Poll::Ready(());
+                }
+                this.listener.insert(listener)
+            }
+        };
+
+        if Pin::new(listener).poll(cx).is_pending() {
+            return Poll::Pending;
+        }
+        this.listener = None;
+        Poll::Ready(())
+    }
+}
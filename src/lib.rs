@@ -63,12 +63,41 @@
 //! # Features
 //!
 //! - The `std` feature (enabled by default) enables the use of the Rust standard library. Disable it for `no_std`
-//! support
+//!   support
 //!
 //! - The `portable-atomic` feature enables the use of the [`portable-atomic`] crate to provide
 //!   atomic operations on platforms that don't support them.
 //!
+//! - The `test-util` feature enables the `test_util` module, which provides a `MockEvent` for
+//!   testing code that notifies events, and a `CountingAllocator` for asserting that code does
+//!   not allocate. It implies `std`.
+//!
+//! - The `portable-fence` feature makes the crate always emit `SeqCst` fences with
+//!   [`fence`](core::sync::atomic::fence), also in [`fence::full_fence()`]. By default, a
+//!   `lock`-prefixed instruction is used on x86 instead, which is sometimes faster, but is not
+//!   understood by tools like ThreadSanitizer.
+//!
+//! - The `registry` feature enables `Event::named()`, which looks up events by name in a
+//!   process-wide registry. It implies `std`.
+//!
+//! - The `locks` feature enables the `locks` module, which provides fair async locks built on
+//!   events. It implies `std`.
+//!
+//! - The `tokio` feature makes listeners take part in the cooperative scheduling of the
+//!   [`tokio`] runtime. Every notification that a task receives uses up some of its budget, and
+//!   once the budget is gone, polling a listener makes the task yield. It implies `std`, and
+//!   needs tokio 1.47 or newer, which in turn needs Rust 1.70 or newer.
+//!
+//! - The `instrument` feature enables `Event::snapshot()`, which returns histograms of how long
+//!   listeners waited to be notified, and how long they took to pick up their notifications.
+//!   It implies `std`.
+//!
+//! - The `priority-inheritance` feature enables `Event::set_priority_inheritance()`, which lets
+//!   threads blocked on an event boost the thread that is expected to notify it. It implies
+//!   `std`.
+//!
 //! [`portable-atomic`]: https://crates.io/crates/portable-atomic
+//! [`tokio`]: https://crates.io/crates/tokio
 
 #![cfg_attr(not(feature = "std"), no_std)]
 #![allow(clippy::multiple_bound_locations)] // This is a WONTFIX issue with pin-project-lite
@@ -89,7 +118,42 @@ extern crate std as alloc;
 #[cfg_attr(not(feature = "std"), path = "no_std.rs")]
 mod sys;
 
+#[cfg(feature = "std")]
+mod ack;
+#[cfg(feature = "std")]
+pub mod broadcast;
+#[cfg(feature = "std")]
+mod builder;
+#[cfg(feature = "std")]
+pub mod condvar;
+pub mod fence;
+#[cfg(feature = "instrument")]
+pub mod instrument;
+#[cfg(feature = "std")]
+pub mod latch;
+#[cfg(feature = "locks")]
+pub mod locks;
+mod mailbox;
 mod notify;
+#[cfg(feature = "std")]
+pub mod once_cell;
+#[cfg(feature = "std")]
+pub mod oneshot;
+#[cfg(feature = "std")]
+mod poison;
+#[cfg(feature = "priority-inheritance")]
+mod priority;
+#[cfg(feature = "registry")]
+mod registry;
+mod set;
+#[cfg(feature = "std")]
+mod subscription;
+#[cfg(feature = "test-util")]
+pub mod test_util;
+#[cfg(all(feature = "std", not(target_family = "wasm"), not(loom)))]
+mod timer;
+#[cfg(feature = "std")]
+pub mod wait_group;
 
 #[cfg(not(feature = "std"))]
 use alloc::boxed::Box;
@@ -100,7 +164,7 @@ use core::future::Future;
 use core::mem::ManuallyDrop;
 use core::pin::Pin;
 use core::ptr;
-use core::task::{Context, Poll, Waker};
+use core::task::{Context, Poll, RawWaker, RawWakerVTable, Waker};
 
 #[cfg(all(feature = "std", not(target_family = "wasm")))]
 use {
@@ -114,8 +178,27 @@ use sync::Arc;
 #[cfg(not(loom))]
 use sync::WithMut;
 
+#[cfg(feature = "std")]
+pub use ack::{Acknowledgement, Rendezvous};
+#[cfg(feature = "std")]
+pub use builder::EventBuilder;
 use notify::NotificationPrivate;
-pub use notify::{IntoNotification, Notification};
+#[cfg(feature = "std")]
+pub use notify::NotifyReport;
+pub use notify::{Additional, IntoNotification, Notification, Notify, Relaxed};
+#[cfg(feature = "std")]
+pub use notify::{MapTag, NotifyContext, NotifyWith, Tag, TagIter, TagWith};
+#[cfg(feature = "std")]
+pub use poison::PoisonGuard;
+#[cfg(feature = "priority-inheritance")]
+pub use priority::PriorityInheritance;
+#[cfg(feature = "std")]
+pub use set::{EventGroup, GroupNotified};
+pub use set::{ListenerSet, NextNotified};
+#[cfg(feature = "std")]
+pub use subscription::{NextNotification, Subscription};
+#[cfg(all(feature = "std", not(target_family = "wasm"), not(loom)))]
+pub use timer::{CheckedTimeoutListener, ScheduledNotify, Ticker, TimeoutListener};
 
 /// Inner state of [`Event`].
 struct Inner<T> {
@@ -130,6 +213,9 @@ struct Inner<T> {
     /// more traditional `Vec` of listeners, with an atomic queue used as a backup for high
     /// contention.
     list: sys::List<T>,
+
+    /// Notifications from interrupt handlers, delivered by whoever holds the list.
+    mailbox: mailbox::Mailbox,
 }
 
 impl<T> Inner<T> {
@@ -137,6 +223,7 @@ impl<T> Inner<T> {
         Self {
             notified: AtomicUsize::new(usize::MAX),
             list: sys::List::new(),
+            mailbox: mailbox::Mailbox::new(),
         }
     }
 }
@@ -160,6 +247,17 @@ impl<T> Inner<T> {
 /// kind of notification was delivered.
 ///
 /// Listeners are registered and notified in the first-in first-out fashion, ensuring fairness.
+/// On `std` platforms, a notification is handed to specific listeners while the event is locked,
+/// so a listener that registers afterwards cannot take it away from an older one, no matter how
+/// many listeners are registered concurrently.
+///
+/// Cloning an `Event` returns another handle to the same event, like cloning an `Arc` would.
+/// Listeners created from any handle are notified by all of them.
+///
+/// An `Event` is a single pointer in size. Its state, including the list of listeners, is only
+/// allocated the first time the event is listened to, cloned or configured. Notifying an event
+/// that has never been listened to does nothing and does not allocate, so events are cheap to
+/// embed in structures that are created often but rarely waited on.
 pub struct Event<T = ()> {
     /// A pointer to heap-allocated inner state.
     ///
@@ -240,6 +338,9 @@ impl<T> Event<T> {
 
     /// Tell whether any listeners are currently notified.
     ///
+    /// For [counting](Event::counting) and [latched](Event::latched) events, this also tells
+    /// whether a stored notification would complete a new listener right away.
+    ///
     /// # Examples
     ///
     /// ```
@@ -306,6 +407,307 @@ impl<T> Event<T> {
         EventListener { listener }
     }
 
+    /// Returns a guard listening for a notification that borrows the event.
+    ///
+    /// An [`EventListener`] keeps the event's inner state alive on its own, which costs a
+    /// reference count update when it is created and dropped. The returned [`BorrowedListener`]
+    /// borrows the event instead, so it can only be used while the event is alive, for example
+    /// within a scope that owns the event. Unlike a [`listener!`] on the stack, it can be
+    /// returned and stored in other types.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use event_listener::{BorrowedListener, Event, Listener};
+    ///
+    /// struct Waiter<'a> {
+    ///     listener: BorrowedListener<'a>,
+    /// }
+    ///
+    /// let event = Event::new();
+    /// let waiter = Waiter {
+    ///     listener: event.listen_borrowed(),
+    /// };
+    ///
+    /// event.notify(1);
+    /// waiter.listener.wait();
+    /// ```
+    #[cold]
+    pub fn listen_borrowed(&self) -> BorrowedListener<'_, T> {
+        let inner = unsafe { &*self.inner() };
+
+        // Allocate the listener on the heap and insert it.
+        let mut listener = Box::pin(InnerListener {
+            event: inner,
+            listener: None,
+        });
+        listener.as_mut().listen();
+
+        BorrowedListener { listener }
+    }
+
+    /// Returns a guard listening for a notification of the given weight.
+    ///
+    /// A normal listener consumes one unit of a notification. A weighted listener consumes
+    /// `weight` units instead, so `notify(n)` wakes listeners in the usual first-in first-out
+    /// order until the combined weight of the woken listeners would exceed `n`. This is useful for
+    /// building fair semaphores, where a single waiter may want to acquire several permits at
+    /// once.
+    ///
+    /// If a notified weighted listener is dropped without receiving its notification, all of its
+    /// units are passed on to the next listeners in line.
+    ///
+    /// Weighted listeners need the `std` feature.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `weight` is zero.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use event_listener::Event;
+    ///
+    /// let event = Event::new();
+    ///
+    /// let listener1 = event.listen_weighted(2);
+    /// let listener2 = event.listen_weighted(3);
+    ///
+    /// // `listener1` needs two units, so it can be woken up...
+    /// assert_eq!(event.notify(4), 1);
+    ///
+    /// // ...but `listener2` needs three on top of that.
+    /// assert_eq!(event.notify(5), 1);
+    /// ```
+    #[cfg(feature = "std")]
+    #[cold]
+    pub fn listen_weighted(&self, weight: usize) -> EventListener<T>
+    where
+        T: Clone,
+    {
+        assert!(weight > 0, "listener weight must be non-zero");
+
+        self.listen_with(ListenOptions {
+            weight,
+            clone_tag: Some(T::clone),
+            ..ListenOptions::default()
+        })
+        .0
+    }
+
+    /// Returns a guard listening for a notification as part of a group.
+    ///
+    /// Once any listener has been registered with a group, notifications rotate between the
+    /// groups that have waiting listeners instead of always going to the oldest listener. Every
+    /// notified listener is the oldest waiting listener of the next group in line, where groups
+    /// are visited in ascending order of their keys. Listeners registered without a group form a
+    /// group of their own that comes before all others.
+    ///
+    /// This can be used to make sure that one tenant with many waiters cannot starve another
+    /// tenant that only has a few.
+    ///
+    /// Finding the next group in line scans the waiting listeners of the event for every
+    /// notified listener, so while grouped listeners exist, notifying `k` listeners costs time
+    /// proportional to `k` times the number of waiting listeners. This is meant for a moderate
+    /// number of listeners.
+    ///
+    /// Grouped listeners need the `std` feature.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use event_listener::{Event, IntoNotification, Listener};
+    ///
+    /// let event = Event::new();
+    ///
+    /// let a1 = event.listen_grouped(1);
+    /// let a2 = event.listen_grouped(1);
+    /// let b1 = event.listen_grouped(2);
+    ///
+    /// // The notifications alternate between the two groups.
+    /// event.notify(1.additional());
+    /// event.notify(1.additional());
+    ///
+    /// assert!(a1.discard());
+    /// assert!(!a2.discard());
+    /// assert!(b1.discard());
+    /// ```
+    #[cfg(feature = "std")]
+    #[cold]
+    pub fn listen_grouped(&self, group: usize) -> EventListener<T> {
+        self.listen_with(ListenOptions {
+            group: Some(group),
+            ..ListenOptions::default()
+        })
+        .0
+    }
+
+    /// Returns a guard listening for a notification whose tag matches a predicate.
+    ///
+    /// The listener is only considered for notifications whose tag is accepted by `filter`. A
+    /// notification that it rejects goes to the next listener in line that accepts it instead.
+    /// A tag that no listener accepts uses up one unit of the notification, and counts as
+    /// undelivered. Notifications made with [`tag_iter()`](IntoNotification::tag_iter) or
+    /// [`tag_with()`](IntoNotification::tag_with) then try their next tag, while other
+    /// notifications stop, since all of their tags are the same. Once the next tag has been
+    /// tried as many times in a row as there are filtered listeners, the notification stops as
+    /// well. The rejected tags of a
+    /// `tag_iter()` notification are returned by [`NotifyReport::into_undelivered()`]; other
+    /// rejected tags are handed to the [undelivered tag callback](Event::on_undelivered).
+    ///
+    /// Finding an accepting listener scans the waiting listeners of the event for every tag, so
+    /// notifying many listeners is slow while filtered listeners exist. With
+    /// [grouped listeners](Event::listen_grouped), this comes on top of finding the next group.
+    ///
+    /// The predicate is called with the event locked, so it must not access the event.
+    ///
+    /// Filtered listeners need the `std` feature.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use event_listener::{Event, IntoNotification, Listener};
+    ///
+    /// let event = Event::<u32>::with_tag();
+    ///
+    /// let odd = event.listen_filter(|n| n % 2 == 1);
+    /// let even = event.listen_filter(|n| n % 2 == 0);
+    ///
+    /// event.notify(1.additional().tag(4));
+    /// event.notify(1.additional().tag(7));
+    ///
+    /// assert_eq!(even.wait(), 4);
+    /// assert_eq!(odd.wait(), 7);
+    /// ```
+    #[cfg(feature = "std")]
+    #[cold]
+    pub fn listen_filter<F>(&self, filter: F) -> EventListener<T>
+    where
+        F: Fn(&T) -> bool + Send + Sync + 'static,
+    {
+        self.listen_with(ListenOptions {
+            filter: Some(Box::new(filter)),
+            ..ListenOptions::default()
+        })
+        .0
+    }
+
+    /// Returns a guard listening for a notification, which belongs to the given class.
+    ///
+    /// Regular notifications treat the listener like any other. In addition,
+    /// [`Event::notify_class()`] and [`Event::notify_one_or_all()`] only notify listeners of
+    /// one class, so that a single event can serve, for example, both the readers and the
+    /// writers of a lock.
+    ///
+    /// Listeners with a class need the `std` feature.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use event_listener::{Event, Listener, ListenerClass};
+    ///
+    /// let event = Event::new();
+    ///
+    /// let reader = event.listen_class(ListenerClass::A);
+    /// let writer = event.listen_class(ListenerClass::B);
+    ///
+    /// assert_eq!(event.notify_class(ListenerClass::B, 1), 1);
+    /// assert!(!reader.discard());
+    /// assert!(writer.discard());
+    /// ```
+    #[cfg(feature = "std")]
+    #[cold]
+    pub fn listen_class(&self, class: ListenerClass) -> EventListener<T> {
+        self.listen_with(ListenOptions {
+            class,
+            ..ListenOptions::default()
+        })
+        .0
+    }
+
+    /// Returns a guard listening for a notification that gives up at a deadline.
+    ///
+    /// The returned [`TimeoutListener`] resolves to `Some` with the notification's tag, or to
+    /// `None` if `timeout` passes before a notification is received. Once the deadline passes,
+    /// the listener is removed from the event in the background, so it no longer takes up a
+    /// notification, even if it is never polled again.
+    ///
+    /// Deadlines are tracked by a timer thread that is started on first use. Listeners with
+    /// deadlines need the `std` feature and are not available on WebAssembly.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use std::time::Duration;
+    /// use event_listener::Event;
+    ///
+    /// let event = Event::new();
+    /// let listener = event.listen_timeout(Duration::from_millis(10));
+    ///
+    /// // There is no notification, so this times out.
+    /// assert_eq!(listener.wait(), None);
+    /// ```
+    #[cfg(all(feature = "std", not(target_family = "wasm"), not(loom)))]
+    #[cold]
+    pub fn listen_timeout(&self, timeout: Duration) -> TimeoutListener<T>
+    where
+        T: Send,
+    {
+        let (listener, id) = self.listen_with(ListenOptions::default());
+        TimeoutListener::new(listener, id, Instant::now().checked_add(timeout))
+    }
+
+    /// Returns a listener that stays registered and buffers every notification it receives.
+    ///
+    /// Each call to [`Subscription::next()`] returns one buffered notification with its tag.
+    /// Notifications that arrive while nobody is waiting on the subscription are kept, so a
+    /// consumer that handles one notification after another does not miss any of them in
+    /// between, as it could when creating a new listener for each one.
+    ///
+    /// Subscriptions need the `std` feature.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use event_listener::Event;
+    ///
+    /// let event = Event::new();
+    /// let mut subscription = event.subscribe();
+    ///
+    /// event.notify(1);
+    /// event.notify(1);
+    /// assert_eq!(subscription.buffered(), 2);
+    ///
+    /// # futures_lite::future::block_on(async {
+    /// while subscription.buffered() > 0 {
+    ///     subscription.next().await.unwrap();
+    /// }
+    /// # });
+    /// ```
+    #[cfg(feature = "std")]
+    pub fn subscribe(&self) -> Subscription<T> {
+        let (listener, _) = self.listen_with(ListenOptions {
+            subscribed: true,
+            ..ListenOptions::default()
+        });
+        Subscription::new(listener)
+    }
+
+    /// Returns a heap-allocated listener registered with the given options, along with its ID.
+    #[cfg(feature = "std")]
+    fn listen_with(&self, options: ListenOptions<T>) -> (EventListener<T>, u64) {
+        let inner = ManuallyDrop::new(unsafe { Arc::from_raw(self.inner()) });
+
+        // Allocate the listener on the heap and insert it.
+        let mut listener = Box::pin(InnerListener {
+            event: Arc::clone(&inner),
+            listener: None,
+        });
+        let id = listener.as_mut().listen_with(options);
+
+        (EventListener { listener }, id)
+    }
+
     /// Notifies a number of active listeners.
     ///
     /// The number is allowed to be zero or exceed the current number of listeners.
@@ -318,6 +720,10 @@ impl<T> Event<T> {
     ///
     /// This function returns the number of [`EventListener`]s that were notified by this call.
     ///
+    /// On `std` platforms, the listeners to notify are chosen while the event is locked, so
+    /// `notify(1)` hands the notification, along with its tag, directly to the oldest waiting
+    /// listener. That listener receives it even if other listeners register before it wakes up.
+    ///
     /// # Caveats
     ///
     /// If the `std` feature is disabled, the notification will be delayed under high contention,
@@ -330,6 +736,20 @@ impl<T> Event<T> {
     /// implementation uses system locking primitives to ensure there is no unavoidable
     /// contention.
     ///
+    /// # Panics
+    ///
+    /// If producing a tag panics, for example in the tag's `Clone` implementation or in the
+    /// function passed to [`tag_with()`](IntoNotification::tag_with), the notification stops
+    /// there, as if it had run out of tags. The listeners that were notified before keep their
+    /// notifications, all other listeners keep waiting, and a [counting](Event::counting) event
+    /// stores the units that were not handed out. The panic is then passed on to the caller once
+    /// the event is unlocked again.
+    ///
+    /// A panic in the filter of a [filtered](Event::listen_filter) listener is not caught. It
+    /// unwinds out of the notification while the event is locked, and the rest of the
+    /// notification is lost. Without the `std` feature, tags are only ever `()` and are never
+    /// produced by user code.
+    ///
     /// # Examples
     ///
     /// Use the default notification strategy:
@@ -440,68 +860,56 @@ impl<T> Event<T> {
         // Make sure the notification comes after whatever triggered it.
         notify.fence(notify::Internal::new());
 
-        let inner = unsafe { &*self.inner() };
-        inner.notify(notify)
-    }
+        match self.try_inner() {
+            Some(inner) => inner.notify(notify),
 
-    /// Return a reference to the inner state if it has been initialized.
-    #[inline]
-    fn try_inner(&self) -> Option<&Inner<T>> {
-        let inner = self.inner.load(Ordering::Acquire);
-        unsafe { inner.as_ref() }
+            // Nobody has listened to the event yet, so there is nobody to notify.
+            None => 0,
+        }
     }
 
-    /// Returns a raw, initialized pointer to the inner state.
+    /// Notify a number of active listeners and report what could not be delivered.
     ///
-    /// This returns a raw pointer instead of reference because `from_raw`
-    /// requires raw/mut provenance: <https://github.com/rust-lang/rust/pull/67339>.
-    fn inner(&self) -> *const Inner<T> {
-        let mut inner = self.inner.load(Ordering::Acquire);
-
-        // If this is the first use, initialize the state.
-        if inner.is_null() {
-            // Allocate the state on the heap.
-            let new = Arc::new(Inner::<T>::new());
-
-            // Convert the state to a raw pointer.
-            let new = Arc::into_raw(new) as *mut Inner<T>;
-
-            // Replace the null pointer with the new state pointer.
-            inner = self
-                .inner
-                .compare_exchange(inner, new, Ordering::AcqRel, Ordering::Acquire)
-                .unwrap_or_else(|x| x);
+    /// This works like [`Event::notify()`], but returns a [`NotifyReport`] that tells how many
+    /// of the requested notifications went unused and hands back the notification. For
+    /// notifications made with [`IntoNotification::tag_iter()`], this gives access to the tags
+    /// that were not delivered.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use event_listener::{Event, IntoNotification};
+    ///
+    /// let event = Event::new();
+    /// let _listener = event.listen();
+    ///
+    /// let report = event.notify_report(3.additional());
+    /// assert_eq!(report.notified(), 1);
+    /// assert_eq!(report.undelivered(), 2);
+    /// ```
+    #[cfg(feature = "std")]
+    #[inline]
+    pub fn notify_report<N>(&self, notify: N) -> NotifyReport<N::Notify>
+    where
+        N: IntoNotification<Tag = T>,
+    {
+        let mut notify = notify.into_notification();
 
-            // Check if the old pointer value was indeed null.
-            if inner.is_null() {
-                // If yes, then use the new state pointer.
-                inner = new;
-            } else {
-                // If not, that means a concurrent operation has initialized the state.
-                // In that case, use the old pointer and deallocate the new one.
-                unsafe {
-                    drop(Arc::from_raw(new));
-                }
-            }
-        }
+        // Make sure the notification comes after whatever triggered it.
+        notify.fence(notify::Internal::new());
 
-        inner
+        let inner = unsafe { &*self.inner() };
+        let (notified, undelivered) = inner.notify_counted(&mut notify);
+        NotifyReport::new(notified, undelivered, notify)
     }
 
-    /// Get the number of listeners currently listening to this [`Event`].
+    /// Notifies a number of active listeners, unless that would block.
     ///
-    /// This call returns the number of [`EventListener`]s that are currently listening to
-    /// this event. It does this by acquiring the internal event lock and reading the listener
-    /// count. Therefore it is only available for `std`-enabled platforms.
-    ///
-    /// # Caveats
-    ///
-    /// This function returns just a snapshot of the number of listeners at this point in time.
-    /// Due to the nature of multi-threaded CPUs, it is possible that this number will be
-    /// inaccurate by the time that this function returns.
+    /// This works like [`Event::notify()`], but fails with [`WouldBlock`] instead of waiting
+    /// when another thread is currently working on the listener list, for example to notify or
+    /// register a listener. Nothing is notified in that case.
     ///
-    /// It is possible for the actual number to change at any point. Therefore, the number should
-    /// only ever be used as a hint.
+    /// A [parent](Event::set_parent) of the event is still notified as usual.
     ///
     /// # Examples
     ///
@@ -509,130 +917,212 @@ impl<T> Event<T> {
     /// use event_listener::Event;
     ///
     /// let event = Event::new();
+    /// let listener = event.listen();
     ///
-    /// assert_eq!(event.total_listeners(), 0);
-    ///
-    /// let listener1 = event.listen();
-    /// assert_eq!(event.total_listeners(), 1);
-    ///
-    /// let listener2 = event.listen();
-    /// assert_eq!(event.total_listeners(), 2);
-    ///
-    /// drop(listener1);
-    /// drop(listener2);
-    /// assert_eq!(event.total_listeners(), 0);
+    /// assert_eq!(event.try_notify(1), Ok(1));
     /// ```
     #[cfg(feature = "std")]
     #[inline]
-    pub fn total_listeners(&self) -> usize {
-        if let Some(inner) = self.try_inner() {
-            inner.list.total_listeners()
-        } else {
-            0
-        }
+    pub fn try_notify(&self, notify: impl IntoNotification<Tag = T>) -> Result<usize, WouldBlock> {
+        let notify = notify.into_notification();
+
+        // Make sure the notification comes after whatever triggered it.
+        notify.fence(notify::Internal::new());
+
+        let inner = unsafe { &*self.inner() };
+        inner.try_notify(notify).ok_or(WouldBlock)
     }
-}
 
-impl Event<()> {
-    /// Creates a new [`Event`].
+    /// Schedules a notification to be delivered at a deadline.
+    ///
+    /// At `deadline`, `notify` is delivered as if it were passed to [`Event::notify()`] at that
+    /// time. This replaces a timer per listener when every listener should give up at the same
+    /// time: the listeners wait on the event as usual, and whoever sets the deadline also
+    /// schedules the notification. The returned [`ScheduledNotify`] cancels it.
+    ///
+    /// The scheduled notification keeps the event's state alive until it is delivered or
+    /// cancelled. It is delivered by the same timer thread as the deadlines of
+    /// [`Event::listen_timeout()`], which is started on first use. Scheduled notifications need
+    /// the `std` feature and are not available on WebAssembly.
+    ///
+    /// The tags of the notification and the [undelivered tag callback](Event::on_undelivered)
+    /// run on the timer thread, so every timeout and scheduled notification in the process is
+    /// held up while they run. If one of them panics, the panic is caught on the timer thread
+    /// and the rest of the notification is dropped.
     ///
     /// # Examples
     ///
     /// ```
-    /// use event_listener::Event;
+    /// use std::time::{Duration, Instant};
+    /// use event_listener::{Event, Listener};
     ///
     /// let event = Event::new();
+    /// let listener = event.listen();
+    ///
+    /// // The listener is woken up by then, whether or not anyone else notifies the event.
+    /// event.notify_at(Instant::now() + Duration::from_millis(10), 1);
+    /// listener.wait();
     /// ```
-    #[inline]
-    #[cfg(not(loom))]
-    pub const fn new() -> Self {
-        Self {
-            inner: AtomicPtr::new(ptr::null_mut()),
-        }
-    }
-
-    #[inline]
-    #[cfg(loom)]
-    pub fn new() -> Self {
-        Self {
-            inner: AtomicPtr::new(ptr::null_mut()),
-        }
+    #[cfg(all(feature = "std", not(target_family = "wasm"), not(loom)))]
+    pub fn notify_at<N>(&self, deadline: Instant, notify: N) -> ScheduledNotify
+    where
+        T: Send + 'static,
+        N: IntoNotification<Tag = T> + Send + 'static,
+    {
+        self.schedule_notify(Some(deadline), notify)
     }
 
-    /// Notifies a number of active listeners without emitting a `SeqCst` fence.
+    /// Schedules a notification to be delivered after a delay.
     ///
-    /// The number is allowed to be zero or exceed the current number of listeners.
+    /// This works like [`Event::notify_at()`] with a deadline of `delay` from now. If the
+    /// deadline cannot be represented, the notification is never delivered.
     ///
-    /// In contrast to [`Event::notify_additional()`], this method only makes sure *at least* `n`
-    /// listeners among the active ones are notified.
+    /// # Examples
     ///
-    /// Unlike [`Event::notify()`], this method does not emit a `SeqCst` fence.
+    /// ```
+    /// use std::time::Duration;
+    /// use event_listener::{Event, Listener};
     ///
-    /// This method only works for untagged events. In other cases, it is recommended to instead
-    /// use [`Event::notify()`] like so:
+    /// let event = Event::new();
+    /// let listener = event.listen();
     ///
+    /// event.notify_after(Duration::from_millis(10), 1);
+    /// listener.wait();
     /// ```
-    /// use event_listener::{IntoNotification, Event};
-    /// let event = Event::new();
+    #[cfg(all(feature = "std", not(target_family = "wasm"), not(loom)))]
+    pub fn notify_after<N>(&self, delay: Duration, notify: N) -> ScheduledNotify
+    where
+        T: Send + 'static,
+        N: IntoNotification<Tag = T> + Send + 'static,
+    {
+        self.schedule_notify(Instant::now().checked_add(delay), notify)
+    }
+
+    /// Notifies the event periodically, until the returned [`Ticker`] is dropped.
     ///
-    /// // Old way:
-    /// event.notify_relaxed(1);
+    /// Every `period`, starting one period from now, `notify` is delivered as if it were passed
+    /// to [`Event::notify()`]. Pass `1` to wake one listener per tick, or `usize::MAX` to wake
+    /// all of them. If the timer falls behind, the ticks that were missed are skipped rather than
+    /// delivered all at once.
     ///
-    /// // New way:
-    /// event.notify(1.relaxed());
-    /// ```
+    /// Ticks are delivered by the same timer thread as [`Event::notify_at()`], and the ticker
+    /// keeps the event's state alive until it is dropped. A tick that is being delivered while
+    /// the ticker is dropped still completes.
+    ///
+    /// As with [`Event::notify_at()`], the tags and the
+    /// [undelivered tag callback](Event::on_undelivered) run on the timer thread and hold up every
+    /// timeout in the process while they run. A tick that panics is dropped, and the ticker keeps
+    /// ticking.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `period` is zero.
     ///
     /// # Examples
     ///
     /// ```
-    /// use event_listener::{Event, IntoNotification};
-    /// use std::sync::atomic::{self, Ordering};
+    /// use std::time::Duration;
+    /// use event_listener::{Event, Listener};
     ///
     /// let event = Event::new();
+    /// let ticker = event.tick_every(Duration::from_millis(10), usize::MAX);
     ///
-    /// // This notification gets lost because there are no listeners.
-    /// event.notify_relaxed(1);
-    ///
-    /// let listener1 = event.listen();
-    /// let listener2 = event.listen();
-    /// let listener3 = event.listen();
-    ///
-    /// // We should emit a fence manually when using relaxed notifications.
-    /// atomic::fence(Ordering::SeqCst);
+    /// for _ in 0..3 {
+    ///     event.listen().wait();
+    /// }
     ///
-    /// // Notifies two listeners.
-    /// //
-    /// // Listener queueing is fair, which means `listener1` and `listener2`
-    /// // get notified here since they start listening before `listener3`.
-    /// event.notify_relaxed(2);
+    /// // Stop ticking.
+    /// drop(ticker);
     /// ```
+    #[cfg(all(feature = "std", not(target_family = "wasm"), not(loom)))]
+    pub fn tick_every<N>(&self, period: Duration, notify: N) -> Ticker
+    where
+        T: Send + 'static,
+        N: IntoNotification<Tag = T> + Clone + Send + 'static,
+    {
+        let event = self.clone();
+        Ticker::new(
+            period,
+            Box::new(move || {
+                event.notify(notify.clone());
+            }),
+        )
+    }
+
+    /// Schedules a notification on the timer thread.
+    #[cfg(all(feature = "std", not(target_family = "wasm"), not(loom)))]
+    fn schedule_notify<N>(&self, deadline: Option<Instant>, notify: N) -> ScheduledNotify
+    where
+        T: Send + 'static,
+        N: IntoNotification<Tag = T> + Send + 'static,
+    {
+        let event = self.clone();
+        ScheduledNotify::new(
+            deadline,
+            Box::new(move || {
+                event.notify(notify);
+            }),
+        )
+    }
+
+    /// Return a reference to the inner state if it has been initialized.
     #[inline]
-    pub fn notify_relaxed(&self, n: usize) -> usize {
-        self.notify(n.relaxed())
+    fn try_inner(&self) -> Option<&Inner<T>> {
+        let inner = self.inner.load(Ordering::Acquire);
+        unsafe { inner.as_ref() }
     }
 
-    /// Notifies a number of active and still unnotified listeners.
-    ///
-    /// The number is allowed to be zero or exceed the current number of listeners.
-    ///
-    /// In contrast to [`Event::notify()`], this method will notify `n` *additional* listeners that
-    /// were previously unnotified.
+    /// Returns a raw, initialized pointer to the inner state.
     ///
-    /// This method emits a `SeqCst` fence before notifying listeners.
+    /// This returns a raw pointer instead of reference because `from_raw`
+    /// requires raw/mut provenance: <https://github.com/rust-lang/rust/pull/67339>.
+    fn inner(&self) -> *const Inner<T> {
+        let mut inner = self.inner.load(Ordering::Acquire);
+
+        // If this is the first use, initialize the state.
+        if inner.is_null() {
+            // Allocate the state on the heap.
+            let new = Arc::new(Inner::<T>::new());
+
+            // Convert the state to a raw pointer.
+            let new = Arc::into_raw(new) as *mut Inner<T>;
+
+            // Replace the null pointer with the new state pointer.
+            inner = self
+                .inner
+                .compare_exchange(inner, new, Ordering::AcqRel, Ordering::Acquire)
+                .unwrap_or_else(|x| x);
+
+            // Check if the old pointer value was indeed null.
+            if inner.is_null() {
+                // If yes, then use the new state pointer.
+                inner = new;
+            } else {
+                // If not, that means a concurrent operation has initialized the state.
+                // In that case, use the old pointer and deallocate the new one.
+                unsafe {
+                    drop(Arc::from_raw(new));
+                }
+            }
+        }
+
+        inner
+    }
+
+    /// Get the number of listeners currently listening to this [`Event`].
     ///
-    /// This method only works for untagged events. In other cases, it is recommended to instead
-    /// use [`Event::notify()`] like so:
+    /// This call returns the number of [`EventListener`]s that are currently listening to
+    /// this event. It does this by acquiring the internal event lock and reading the listener
+    /// count. Therefore it is only available for `std`-enabled platforms.
     ///
-    /// ```
-    /// use event_listener::{IntoNotification, Event};
-    /// let event = Event::new();
+    /// # Caveats
     ///
-    /// // Old way:
-    /// event.notify_additional(1);
+    /// This function returns just a snapshot of the number of listeners at this point in time.
+    /// Due to the nature of multi-threaded CPUs, it is possible that this number will be
+    /// inaccurate by the time that this function returns.
     ///
-    /// // New way:
-    /// event.notify(1.additional());
-    /// ```
+    /// It is possible for the actual number to change at any point. Therefore, the number should
+    /// only ever be used as a hint.
     ///
     /// # Examples
     ///
@@ -641,82 +1131,887 @@ impl Event<()> {
     ///
     /// let event = Event::new();
     ///
-    /// // This notification gets lost because there are no listeners.
-    /// event.notify_additional(1);
+    /// assert_eq!(event.total_listeners(), 0);
     ///
     /// let listener1 = event.listen();
+    /// assert_eq!(event.total_listeners(), 1);
+    ///
     /// let listener2 = event.listen();
-    /// let listener3 = event.listen();
+    /// assert_eq!(event.total_listeners(), 2);
     ///
-    /// // Notifies two listeners.
-    /// //
-    /// // Listener queueing is fair, which means `listener1` and `listener2`
-    /// // get notified here since they start listening before `listener3`.
-    /// event.notify_additional(1);
-    /// event.notify_additional(1);
+    /// drop(listener1);
+    /// drop(listener2);
+    /// assert_eq!(event.total_listeners(), 0);
     /// ```
+    #[cfg(feature = "std")]
     #[inline]
-    pub fn notify_additional(&self, n: usize) -> usize {
-        self.notify(n.additional())
+    pub fn total_listeners(&self) -> usize {
+        if let Some(inner) = self.try_inner() {
+            inner.list.total_listeners()
+        } else {
+            0
+        }
     }
 
-    /// Notifies a number of active and still unnotified listeners without emitting a `SeqCst`
-    /// fence.
-    ///
-    /// The number is allowed to be zero or exceed the current number of listeners.
+    /// Returns the number of stored notifications of a [counting](Event::counting) or
+    /// [latched](Event::latched) event.
     ///
-    /// In contrast to [`Event::notify()`], this method will notify `n` *additional* listeners that
-    /// were previously unnotified.
+    /// For a counting event, this is the number of units that future listeners can take. For a
+    /// latched event, this is `1` while the event is open and `0` otherwise. Other events never
+    /// store notifications, so this is always `0` for them.
     ///
-    /// Unlike [`Event::notify_additional()`], this method does not emit a `SeqCst` fence.
+    /// Like [`Event::total_listeners()`], this is only a snapshot.
     ///
-    /// This method only works for untagged events. In other cases, it is recommended to instead
-    /// use [`Event::notify()`] like so:
+    /// # Examples
     ///
     /// ```
-    /// use event_listener::{IntoNotification, Event};
-    /// let event = Event::new();
+    /// use event_listener::{Event, IntoNotification, Listener};
     ///
-    /// // Old way:
-    /// event.notify_additional_relaxed(1);
+    /// let event = Event::counting();
+    /// event.notify(3.additional());
+    /// assert_eq!(event.pending_notifications(), 3);
     ///
-    /// // New way:
-    /// event.notify(1.additional().relaxed());
+    /// event.listen().wait();
+    /// assert_eq!(event.pending_notifications(), 2);
     /// ```
+    #[cfg(feature = "std")]
+    #[inline]
+    pub fn pending_notifications(&self) -> usize {
+        self.try_inner().map_or(0, |inner| inner.stored())
+    }
+
+    /// Drops the stored notifications of a [counting](Event::counting) or
+    /// [latched](Event::latched) event.
     ///
-    /// # Examples
+    /// This closes a latched event again. Listeners that were already notified keep their
+    /// notification. For other events, this does nothing.
+    #[cfg(feature = "std")]
+    #[inline]
+    pub fn reset(&self) {
+        if let Some(inner) = self.try_inner() {
+            inner.reset();
+        }
+    }
+
+    /// Links this event to a parent event.
     ///
-    /// ```
-    /// use event_listener::Event;
-    /// use std::sync::atomic::{self, Ordering};
+    /// From now on, every call to [`Event::notify()`] or one of its variants on this event also
+    /// notifies `parent`, with the same number of listeners and the same
+    /// [additional](IntoNotification::additional) setting. The parent is notified after this
+    /// event, and also when this event has no listeners at all, so changes propagate up a tree
+    /// of events.
     ///
-    /// let event = Event::new();
+    /// Notifications that are passed on from a dropped listener are not forwarded again. An
+    /// event has at most one parent; linking it again replaces the previous parent. The links
+    /// must not form a cycle.
     ///
-    /// // This notification gets lost because there are no listeners.
-    /// event.notify(1);
+    /// To notify a parent with tags, use [`Event::set_parent_with()`].
     ///
-    /// let listener1 = event.listen();
-    /// let listener2 = event.listen();
-    /// let listener3 = event.listen();
+    /// Parent links need the `std` feature.
     ///
-    /// // We should emit a fence manually when using relaxed notifications.
-    /// atomic::fence(Ordering::SeqCst);
+    /// # Examples
     ///
-    /// // Notifies two listeners.
-    /// //
-    /// // Listener queueing is fair, which means `listener1` and `listener2`
-    /// // get notified here since they start listening before `listener3`.
-    /// event.notify_additional_relaxed(1);
-    /// event.notify_additional_relaxed(1);
     /// ```
-    #[inline]
-    pub fn notify_additional_relaxed(&self, n: usize) -> usize {
-        self.notify(n.additional().relaxed())
+    /// use std::sync::Arc;
+    /// use event_listener::{Event, Listener};
+    ///
+    /// let root = Arc::new(Event::new());
+    /// let child = Event::new();
+    /// child.set_parent(root.clone());
+    ///
+    /// let listener = root.listen();
+    /// child.notify(1);
+    /// listener.wait();
+    /// ```
+    #[cfg(feature = "std")]
+    pub fn set_parent(&self, parent: std::sync::Arc<Event>) {
+        self.set_parent_with(parent, || ());
     }
-}
 
-impl<T> Drop for Event<T> {
-    #[inline]
+    /// Links this event to a parent event that receives tags produced by `tag`.
+    ///
+    /// This works like [`Event::set_parent()`], but the parent's listeners receive tags produced
+    /// by `tag` for every notification that is forwarded.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use std::sync::Arc;
+    /// use event_listener::{Event, Listener};
+    ///
+    /// let root = Arc::new(Event::<&str>::with_tag());
+    /// let child = Event::new();
+    /// child.set_parent_with(root.clone(), || "child changed");
+    ///
+    /// let listener = root.listen();
+    /// child.notify(1);
+    /// assert_eq!(listener.wait(), "child changed");
+    /// ```
+    #[cfg(feature = "std")]
+    pub fn set_parent_with<U, F>(&self, parent: std::sync::Arc<Event<U>>, tag: F)
+    where
+        U: Send + 'static,
+        F: Fn() -> U + Send + Sync + 'static,
+    {
+        let inner = unsafe { &*self.inner() };
+        inner.set_parent(Some(forward_to(parent, tag)));
+    }
+
+    /// Removes the link to the parent event, if any.
+    #[cfg(feature = "std")]
+    pub fn clear_parent(&self) {
+        if let Some(inner) = self.try_inner() {
+            inner.set_parent(None);
+        }
+    }
+
+    /// Registers a function that receives the tags that no listener received.
+    ///
+    /// Without it, such tags are dropped. This matters when the tags carry resources, such as
+    /// buffers, that should be reclaimed or logged. A tag is handed to `f` when:
+    ///
+    /// - a notified listener is dropped, and no other listener takes over its notification,
+    /// - a notified listener is [discarded](Listener::discard),
+    /// - no [filtered](Event::listen_filter) listener accepts it, unless it was produced by
+    ///   [`tag_iter()`](IntoNotification::tag_iter), which returns it in the [`NotifyReport`],
+    /// - it was produced for a listener that could not be notified after all,
+    /// - a notification made with [`tag()`](IntoNotification::tag) reaches no listener, in which
+    ///   case `f` receives a clone of its tag.
+    ///
+    /// Other tags that are never produced, because there were fewer listeners than the
+    /// notification asked for, are not handed to `f`: [`tag_with()`](IntoNotification::tag_with)
+    /// does not call its function for them, and [`tag_iter()`](IntoNotification::tag_iter)
+    /// leaves them in the iterator.
+    ///
+    /// `f` is called without the event locked, on the thread that dropped the tag. Registering a
+    /// function again replaces the previous one.
+    ///
+    /// Undelivered tag callbacks need the `std` feature.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use std::sync::{Arc, Mutex};
+    /// use event_listener::{Event, IntoNotification, Listener};
+    ///
+    /// let event = Event::<Vec<u8>>::with_tag();
+    /// let reclaimed = Arc::new(Mutex::new(Vec::new()));
+    /// event.on_undelivered({
+    ///     let reclaimed = reclaimed.clone();
+    ///     move |buffer| reclaimed.lock().unwrap().push(buffer)
+    /// });
+    ///
+    /// let listener = event.listen();
+    /// event.notify(1.tag(vec![1, 2, 3]));
+    /// drop(listener);
+    ///
+    /// assert_eq!(*reclaimed.lock().unwrap(), [vec![1, 2, 3]]);
+    /// ```
+    #[cfg(feature = "std")]
+    pub fn on_undelivered<F>(&self, f: F)
+    where
+        F: Fn(T) + Send + Sync + 'static,
+    {
+        let inner = unsafe { &*self.inner() };
+        inner.set_undelivered(Some(std::sync::Arc::new(f)));
+    }
+
+    /// Removes the function registered with [`Event::on_undelivered()`], if any.
+    #[cfg(feature = "std")]
+    pub fn clear_undelivered(&self) {
+        if let Some(inner) = self.try_inner() {
+            inner.set_undelivered(None);
+        }
+    }
+
+    /// Returns the histograms of how long the listeners of this event waited.
+    ///
+    /// See the [`instrument`] module for what is recorded. An event that was never listened to
+    /// returns empty histograms.
+    #[cfg(feature = "instrument")]
+    pub fn snapshot(&self) -> instrument::Snapshot {
+        self.try_inner()
+            .map_or_else(Default::default, |inner| inner.snapshot())
+    }
+
+    /// Registers a hook that boosts the producer of the event while threads are blocked on it.
+    ///
+    /// See [`PriorityInheritance`] for when the hook is called. Registering a hook again
+    /// replaces the previous one.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use std::sync::atomic::{AtomicUsize, Ordering};
+    /// use std::sync::Arc;
+    /// use std::thread;
+    /// use std::time::Duration;
+    /// use event_listener::{Event, Listener, PriorityInheritance};
+    ///
+    /// // Stands in for the producer's scheduling priority.
+    /// struct Producer {
+    ///     boosts: AtomicUsize,
+    /// }
+    ///
+    /// impl PriorityInheritance for Producer {
+    ///     fn boost(&self) {
+    ///         self.boosts.fetch_add(1, Ordering::SeqCst);
+    ///     }
+    ///
+    ///     fn restore(&self) {
+    ///         self.boosts.fetch_sub(1, Ordering::SeqCst);
+    ///     }
+    /// }
+    ///
+    /// let event = Event::new();
+    /// event.set_priority_inheritance(Producer {
+    ///     boosts: AtomicUsize::new(0),
+    /// });
+    ///
+    /// let listener = event.listen();
+    /// assert_eq!(listener.wait_timeout(Duration::from_millis(10)), None);
+    /// ```
+    #[cfg(feature = "priority-inheritance")]
+    pub fn set_priority_inheritance<P>(&self, hook: P)
+    where
+        P: PriorityInheritance + 'static,
+    {
+        let inner = unsafe { &*self.inner() };
+        inner.set_priority_inheritance(Some(std::sync::Arc::new(hook)));
+    }
+
+    /// Removes the hook registered with [`Event::set_priority_inheritance()`], if any.
+    #[cfg(feature = "priority-inheritance")]
+    pub fn clear_priority_inheritance(&self) {
+        if let Some(inner) = self.try_inner() {
+            inner.set_priority_inheritance(None);
+        }
+    }
+
+    /// Notifies all active listeners with a final tag, and closes the event.
+    ///
+    /// Both happen with the event locked, so a listener that registers concurrently either
+    /// receives the tag or sees the closed event; it cannot end up waiting forever. Every
+    /// listener created afterwards completes with [`WaitError::Closed`], as does a
+    /// [filtered](Event::listen_filter) listener that rejects the tag. Like for a
+    /// [poisoned](Event::poison_guard) event, a plain listener panics with the error, while a
+    /// [`CheckedListener`] returns it.
+    ///
+    /// Returns the number of listeners that were notified. A closed event stays closed.
+    ///
+    /// Closing an event needs the `std` feature.
+    ///
+    /// # Panics
+    ///
+    /// If cloning the tag panics, the remaining listeners are not notified, but they are still
+    /// interrupted with [`WaitError::Closed`]. The panic is then passed on to the caller once the
+    /// event is unlocked again.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use event_listener::{Event, Listener, WaitError};
+    ///
+    /// let event = Event::<&str>::with_tag();
+    /// let listener = event.listen();
+    ///
+    /// assert_eq!(event.notify_all_and_close("shutting down"), 1);
+    /// assert_eq!(listener.wait(), "shutting down");
+    ///
+    /// assert!(event.is_closed());
+    /// assert_eq!(event.listen().checked().wait(), Err(WaitError::Closed));
+    /// ```
+    #[cfg(feature = "std")]
+    pub fn notify_all_and_close(&self, tag: T) -> usize
+    where
+        T: Clone,
+    {
+        // Make sure the notification comes after whatever triggered it.
+        fence::full_fence();
+
+        let inner = unsafe { &*self.inner() };
+        inner.close(|| tag.clone())
+    }
+
+    /// Returns `true` if the event was closed with [`Event::notify_all_and_close()`].
+    #[cfg(feature = "std")]
+    pub fn is_closed(&self) -> bool {
+        self.try_inner().map_or(false, |inner| inner.is_closed())
+    }
+
+    /// Removes every listener that has not been notified yet.
+    ///
+    /// The removed listeners complete with [`WaitError::Drained`], which wakes them up if they
+    /// are waiting. Like for a [poisoned](Event::poison_guard) event, a plain listener panics
+    /// with the error, while a [`CheckedListener`] returns it. Listeners that were notified
+    /// already keep their notification, and the event itself is unchanged: it is not closed, and
+    /// new listeners wait as usual. This resets a subsystem without replacing its event.
+    ///
+    /// Returns the number of listeners that were removed.
+    ///
+    /// Draining an event needs the `std` feature.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use event_listener::{Event, Listener, WaitError};
+    ///
+    /// let event = Event::new();
+    /// let notified = event.listen();
+    /// event.notify(1);
+    /// let waiting = event.listen().checked();
+    ///
+    /// assert_eq!(event.drain(), 1);
+    /// assert_eq!(waiting.wait(), Err(WaitError::Drained));
+    /// notified.wait();
+    ///
+    /// // The event is still usable.
+    /// let listener = event.listen();
+    /// event.notify(1);
+    /// listener.wait();
+    /// ```
+    #[cfg(feature = "std")]
+    pub fn drain(&self) -> usize {
+        self.try_inner().map_or(0, |inner| inner.drain())
+    }
+
+    /// Removes the listener with the given ID from the event, without owning it.
+    ///
+    /// The listener completes with [`WaitError::Cancelled`], which wakes it up if it is waiting.
+    /// Like for a [poisoned](Event::poison_guard) event, a plain listener panics with the error,
+    /// while a [`CheckedListener`] returns it. Listeners that have already been notified keep
+    /// their notification.
+    ///
+    /// Returns `true` if the listener was removed.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use event_listener::{Event, Listener, WaitError};
+    ///
+    /// let event = Event::new();
+    /// let listener = event.listen();
+    /// let id = listener.id().unwrap();
+    ///
+    /// assert!(event.unlisten(id));
+    /// assert_eq!(listener.checked().wait(), Err(WaitError::Cancelled));
+    /// ```
+    #[cfg(feature = "std")]
+    pub fn unlisten(&self, id: ListenerId) -> bool {
+        self.try_inner().map_or(false, |inner| cancel(inner, id))
+    }
+}
+
+impl Event<()> {
+    /// Creates a new [`Event`].
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use event_listener::Event;
+    ///
+    /// let event = Event::new();
+    /// ```
+    #[inline]
+    #[cfg(not(loom))]
+    pub const fn new() -> Self {
+        Self {
+            inner: AtomicPtr::new(ptr::null_mut()),
+        }
+    }
+
+    #[inline]
+    #[cfg(loom)]
+    pub fn new() -> Self {
+        Self {
+            inner: AtomicPtr::new(ptr::null_mut()),
+        }
+    }
+
+    /// Creates an [`EventBuilder`] for an event that does not use the default behavior.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use event_listener::{Event, Listener};
+    ///
+    /// let event = Event::builder().latched(true).build();
+    ///
+    /// event.notify(1);
+    /// event.listen().wait();
+    /// ```
+    #[cfg(feature = "std")]
+    #[inline]
+    pub fn builder() -> EventBuilder {
+        EventBuilder::new()
+    }
+
+    /// Creates a new counting [`Event`].
+    ///
+    /// A counting event keeps notifications that no listener received, like a semaphore keeps
+    /// permits. A listener that is created while notifications are stored takes one of them and
+    /// completes right away, unless an older listener is still waiting, for example because it
+    /// is [weighted](Event::listen_weighted) and needs more units. Stored units are then handed
+    /// out in order by the next notification. Notifications that are not [additional] only top
+    /// up the stored count, just like they only top up the number of notified listeners.
+    ///
+    /// Counting events need the `std` feature.
+    ///
+    /// [additional]: IntoNotification::additional
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use event_listener::{Event, IntoNotification, Listener};
+    ///
+    /// let event = Event::counting();
+    ///
+    /// // Nobody is listening, so the notifications are stored.
+    /// event.notify(2.additional());
+    /// assert!(event.is_notified());
+    ///
+    /// event.listen().wait();
+    /// event.listen().wait();
+    /// assert!(!event.is_notified());
+    /// ```
+    #[cfg(feature = "std")]
+    pub fn counting() -> Self {
+        Self::with_store(false)
+    }
+
+    /// Creates a new latched [`Event`].
+    ///
+    /// The first notification of a latched event wakes up every listener, and opens the event:
+    /// all listeners created afterwards complete right away, until [`Event::reset()`] is called.
+    ///
+    /// Latched events need the `std` feature.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use event_listener::{Event, Listener};
+    ///
+    /// let event = Event::latched();
+    /// let listener = event.listen();
+    ///
+    /// event.notify(1);
+    /// listener.wait();
+    ///
+    /// // The event stays open.
+    /// event.listen().wait();
+    /// assert!(event.is_notified());
+    ///
+    /// event.reset();
+    /// assert!(!event.is_notified());
+    /// ```
+    #[cfg(feature = "std")]
+    pub fn latched() -> Self {
+        Self::with_store(true)
+    }
+
+    /// Creates an event that stores notifications for future listeners.
+    #[cfg(feature = "std")]
+    fn with_store(latched: bool) -> Self {
+        let event = Self::new();
+        let inner = unsafe { &*event.inner() };
+        inner.set_store(latched, || ());
+        event
+    }
+
+    /// Notifies a number of active listeners without emitting a `SeqCst` fence.
+    ///
+    /// The number is allowed to be zero or exceed the current number of listeners.
+    ///
+    /// In contrast to [`Event::notify_additional()`], this method only makes sure *at least* `n`
+    /// listeners among the active ones are notified.
+    ///
+    /// Unlike [`Event::notify()`], this method does not emit a `SeqCst` fence.
+    ///
+    /// This method only works for untagged events. In other cases, it is recommended to instead
+    /// use [`Event::notify()`] like so:
+    ///
+    /// ```
+    /// use event_listener::{IntoNotification, Event};
+    /// let event = Event::new();
+    ///
+    /// // Old way:
+    /// event.notify_relaxed(1);
+    ///
+    /// // New way:
+    /// event.notify(1.relaxed());
+    /// ```
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use event_listener::{Event, IntoNotification};
+    /// use std::sync::atomic::{self, Ordering};
+    ///
+    /// let event = Event::new();
+    ///
+    /// // This notification gets lost because there are no listeners.
+    /// event.notify_relaxed(1);
+    ///
+    /// let listener1 = event.listen();
+    /// let listener2 = event.listen();
+    /// let listener3 = event.listen();
+    ///
+    /// // We should emit a fence manually when using relaxed notifications.
+    /// atomic::fence(Ordering::SeqCst);
+    ///
+    /// // Notifies two listeners.
+    /// //
+    /// // Listener queueing is fair, which means `listener1` and `listener2`
+    /// // get notified here since they start listening before `listener3`.
+    /// event.notify_relaxed(2);
+    /// ```
+    #[inline]
+    pub fn notify_relaxed(&self, n: usize) -> usize {
+        self.notify(n.relaxed())
+    }
+
+    /// Notifies a number of active and still unnotified listeners.
+    ///
+    /// The number is allowed to be zero or exceed the current number of listeners.
+    ///
+    /// In contrast to [`Event::notify()`], this method will notify `n` *additional* listeners that
+    /// were previously unnotified.
+    ///
+    /// This method emits a `SeqCst` fence before notifying listeners.
+    ///
+    /// This method only works for untagged events. In other cases, it is recommended to instead
+    /// use [`Event::notify()`] like so:
+    ///
+    /// ```
+    /// use event_listener::{IntoNotification, Event};
+    /// let event = Event::new();
+    ///
+    /// // Old way:
+    /// event.notify_additional(1);
+    ///
+    /// // New way:
+    /// event.notify(1.additional());
+    /// ```
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use event_listener::Event;
+    ///
+    /// let event = Event::new();
+    ///
+    /// // This notification gets lost because there are no listeners.
+    /// event.notify_additional(1);
+    ///
+    /// let listener1 = event.listen();
+    /// let listener2 = event.listen();
+    /// let listener3 = event.listen();
+    ///
+    /// // Notifies two listeners.
+    /// //
+    /// // Listener queueing is fair, which means `listener1` and `listener2`
+    /// // get notified here since they start listening before `listener3`.
+    /// event.notify_additional(1);
+    /// event.notify_additional(1);
+    /// ```
+    #[inline]
+    pub fn notify_additional(&self, n: usize) -> usize {
+        self.notify(n.additional())
+    }
+
+    /// Notifies a number of active and still unnotified listeners without emitting a `SeqCst`
+    /// fence.
+    ///
+    /// The number is allowed to be zero or exceed the current number of listeners.
+    ///
+    /// In contrast to [`Event::notify()`], this method will notify `n` *additional* listeners that
+    /// were previously unnotified.
+    ///
+    /// Unlike [`Event::notify_additional()`], this method does not emit a `SeqCst` fence.
+    ///
+    /// This method only works for untagged events. In other cases, it is recommended to instead
+    /// use [`Event::notify()`] like so:
+    ///
+    /// ```
+    /// use event_listener::{IntoNotification, Event};
+    /// let event = Event::new();
+    ///
+    /// // Old way:
+    /// event.notify_additional_relaxed(1);
+    ///
+    /// // New way:
+    /// event.notify(1.additional().relaxed());
+    /// ```
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use event_listener::Event;
+    /// use std::sync::atomic::{self, Ordering};
+    ///
+    /// let event = Event::new();
+    ///
+    /// // This notification gets lost because there are no listeners.
+    /// event.notify(1);
+    ///
+    /// let listener1 = event.listen();
+    /// let listener2 = event.listen();
+    /// let listener3 = event.listen();
+    ///
+    /// // We should emit a fence manually when using relaxed notifications.
+    /// atomic::fence(Ordering::SeqCst);
+    ///
+    /// // Notifies two listeners.
+    /// //
+    /// // Listener queueing is fair, which means `listener1` and `listener2`
+    /// // get notified here since they start listening before `listener3`.
+    /// event.notify_additional_relaxed(1);
+    /// event.notify_additional_relaxed(1);
+    /// ```
+    #[inline]
+    pub fn notify_additional_relaxed(&self, n: usize) -> usize {
+        self.notify(n.additional().relaxed())
+    }
+
+    /// Notifies all active listeners.
+    ///
+    /// This is equivalent to `event.notify(usize::MAX)`, but avoids locking the listener list
+    /// when there is nobody left to notify, and does not count the listeners' weights. For a
+    /// [counting](Event::counting) event, nothing is stored for future listeners.
+    ///
+    /// Returns the number of listeners that were notified.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use event_listener::Event;
+    ///
+    /// let event = Event::new();
+    ///
+    /// let listener1 = event.listen();
+    /// let listener2 = event.listen();
+    ///
+    /// assert_eq!(event.notify_all(), 2);
+    /// assert_eq!(event.notify_all(), 0);
+    /// ```
+    #[inline]
+    pub fn notify_all(&self) -> usize {
+        self.notify_all_with(false)
+    }
+
+    /// Notifies all unnotified listeners with an additional notification.
+    ///
+    /// This is equivalent to `event.notify(usize::MAX.additional())`. The difference to
+    /// [`Event::notify_all()`] is what happens when a notified listener is dropped without
+    /// receiving its notification: an additional notification is always passed on to another
+    /// listener.
+    ///
+    /// Returns the number of listeners that were notified.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use event_listener::Event;
+    ///
+    /// let event = Event::new();
+    /// let listener = event.listen();
+    ///
+    /// assert_eq!(event.notify_all_additional(), 1);
+    /// ```
+    #[inline]
+    pub fn notify_all_additional(&self) -> usize {
+        self.notify_all_with(true)
+    }
+
+    /// Notifies a number of unnotified listeners of one class.
+    ///
+    /// Up to `n` of the oldest unnotified listeners registered with
+    /// [`Event::listen_class()`] are notified, so pass `usize::MAX` to notify all of them. Like
+    /// [`Event::notify_all()`], this does not count the listeners' weights or visit their
+    /// groups. The notifications are additional, so a listener that is dropped without
+    /// receiving its notification passes it on to the next listener in line, regardless of
+    /// its class.
+    ///
+    /// Returns the number of listeners that were notified.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use event_listener::{Event, ListenerClass};
+    ///
+    /// let event = Event::new();
+    ///
+    /// let reader1 = event.listen_class(ListenerClass::A);
+    /// let reader2 = event.listen_class(ListenerClass::A);
+    /// let writer = event.listen_class(ListenerClass::B);
+    ///
+    /// assert_eq!(event.notify_class(ListenerClass::A, usize::MAX), 2);
+    /// assert_eq!(event.notify_class(ListenerClass::A, usize::MAX), 0);
+    /// ```
+    #[cfg(feature = "std")]
+    pub fn notify_class(&self, class: ListenerClass, n: usize) -> usize {
+        // Make sure the notification comes after whatever triggered it.
+        fence::full_fence();
+
+        match self.try_inner() {
+            Some(inner) => inner.notify_class(class, n, None, || ()),
+            None => 0,
+        }
+    }
+
+    /// Notifies one unnotified listener of class `one`, or every unnotified listener of class
+    /// `all` if there is none.
+    ///
+    /// Both steps happen with the event locked, so no listener can be registered in between.
+    /// This is what an unlocked read-write lock needs: it hands the lock to one waiting writer,
+    /// or to all waiting readers if no writer is waiting.
+    ///
+    /// The notifications are additional, as with [`Event::notify_class()`]. Returns the number
+    /// of listeners that were notified.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use event_listener::{Event, Listener, ListenerClass};
+    ///
+    /// let event = Event::new();
+    ///
+    /// let reader1 = event.listen_class(ListenerClass::A);
+    /// let reader2 = event.listen_class(ListenerClass::A);
+    ///
+    /// // No writer is waiting, so all readers are notified.
+    /// assert_eq!(event.notify_one_or_all(ListenerClass::B, ListenerClass::A), 2);
+    ///
+    /// let writer1 = event.listen_class(ListenerClass::B);
+    /// let writer2 = event.listen_class(ListenerClass::B);
+    /// let reader3 = event.listen_class(ListenerClass::A);
+    ///
+    /// // Only one writer is notified.
+    /// assert_eq!(event.notify_one_or_all(ListenerClass::B, ListenerClass::A), 1);
+    /// assert!(writer1.discard());
+    /// assert!(!writer2.discard());
+    /// assert!(!reader3.discard());
+    /// ```
+    #[cfg(feature = "std")]
+    pub fn notify_one_or_all(&self, one: ListenerClass, all: ListenerClass) -> usize {
+        // Make sure the notification comes after whatever triggered it.
+        fence::full_fence();
+
+        match self.try_inner() {
+            Some(inner) => inner.notify_class(one, 1, Some(all), || ()),
+            None => 0,
+        }
+    }
+
+    /// Returns an iterator that notifies the waiting listeners in waves of `k`.
+    ///
+    /// Every call to [`next()`](Iterator::next) notifies `k` additional listeners and returns
+    /// how many were notified, until there is nobody left to notify. The caller decides what
+    /// happens between two waves, for example yielding, sleeping or awaiting a future, which
+    /// spreads out a thundering herd of thousands of tasks waiting for the same event.
+    ///
+    /// Listeners that start waiting while the waves are in progress are notified by later
+    /// waves as well.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use event_listener::Event;
+    ///
+    /// let event = Event::new();
+    /// let listeners = (0..5).map(|_| event.listen()).collect::<Vec<_>>();
+    ///
+    /// let mut waves = event.notify_waves(2);
+    /// assert_eq!(waves.next(), Some(2));
+    /// assert_eq!(waves.next(), Some(2));
+    /// assert_eq!(waves.next(), Some(1));
+    /// assert_eq!(waves.next(), None);
+    /// ```
+    #[inline]
+    pub fn notify_waves(&self, k: usize) -> NotifyWaves<'_> {
+        NotifyWaves { event: self, k }
+    }
+
+    /// Notifies listeners from an interrupt handler, or any other context that must not wait for
+    /// the event to be unlocked.
+    ///
+    /// This never blocks. If the event is unlocked, the listeners are notified right away, from
+    /// the calling context. Otherwise, the notification is left in a mailbox, and the thread or
+    /// task that holds the event delivers it before unlocking. Notifications waiting
+    /// in the mailbox are combined: plain counts are merged by taking the largest one, and
+    /// [additional](IntoNotification::additional) counts are added up.
+    ///
+    /// Without the `std` feature, this also never allocates. Listeners that other threads
+    /// register or remove at the same time are then not processed right away, but by the next
+    /// operation on the event. With the `std` feature, notifying the listeners right away may
+    /// allocate, to collect the tasks to wake when there are several of them.
+    ///
+    /// Unlike [`Event::notify()`], this does not report how many listeners were notified.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use event_listener::{Event, IntoNotification, Listener};
+    ///
+    /// static DATA_READY: Event = Event::new();
+    ///
+    /// // Called by the interrupt controller.
+    /// fn on_interrupt() {
+    ///     DATA_READY.notify_from_isr(1.additional());
+    /// }
+    ///
+    /// let listener = DATA_READY.listen();
+    /// on_interrupt();
+    /// assert!(listener.discard());
+    /// ```
+    pub fn notify_from_isr(&self, notify: impl IntoNotification<Tag = ()>) {
+        let notify = notify.into_notification();
+
+        // Make sure the notification comes after whatever triggered it.
+        notify.fence(notify::Internal::new());
+
+        if let Some(inner) = self.try_inner() {
+            inner.mailbox.push(
+                notify.count(notify::Internal::new()),
+                notify.is_additional(notify::Internal::new()),
+            );
+
+            // Deliver the notification ourselves, unless someone else holds the event.
+            inner.deliver_mailbox();
+        }
+    }
+
+    /// Notify all unnotified listeners.
+    #[inline]
+    fn notify_all_with(&self, additional: bool) -> usize {
+        // Make sure the notification comes after whatever triggered it.
+        fence::full_fence();
+
+        match self.try_inner() {
+            // If all listeners are notified already, there is nothing to do.
+            Some(inner) if inner.notified.load(Ordering::Acquire) != usize::MAX => {
+                inner.notify_all(additional, || ())
+            }
+            _ => 0,
+        }
+    }
+}
+
+impl<T> Clone for Event<T> {
+    #[inline]
+    fn clone(&self) -> Self {
+        // Share the inner state, initializing it if necessary.
+        let inner = ManuallyDrop::new(unsafe { Arc::from_raw(self.inner()) });
+        let inner = Arc::into_raw(Arc::clone(&inner)) as *mut Inner<T>;
+
+        Self {
+            inner: AtomicPtr::new(inner),
+        }
+    }
+}
+
+impl<T> Drop for Event<T> {
+    #[inline]
     fn drop(&mut self) {
         self.inner.with_mut(|&mut inner| {
             // If the state pointer has been initialized, drop it.
@@ -729,6 +2024,41 @@ impl<T> Drop for Event<T> {
     }
 }
 
+/// An iterator that notifies the listeners of an [`Event`] in waves.
+///
+/// This is created by [`Event::notify_waves()`].
+#[derive(Debug)]
+#[must_use = "iterators are lazy and do nothing unless consumed"]
+pub struct NotifyWaves<'a> {
+    event: &'a Event,
+    k: usize,
+}
+
+impl Iterator for NotifyWaves<'_> {
+    type Item = usize;
+
+    #[inline]
+    fn next(&mut self) -> Option<usize> {
+        match self.event.notify(self.k.additional()) {
+            0 => None,
+            count => Some(count),
+        }
+    }
+}
+
+/// Returns a waker that does nothing when woken.
+fn noop_waker() -> Waker {
+    const VTABLE: RawWakerVTable = RawWakerVTable::new(
+        |_| RawWaker::new(ptr::null(), &VTABLE),
+        |_| (),
+        |_| (),
+        |_| (),
+    );
+
+    // SAFETY: The vtable ignores the data pointer.
+    unsafe { Waker::from_raw(RawWaker::new(ptr::null(), &VTABLE)) }
+}
+
 /// A handle that is listening to an [`Event`].
 ///
 /// This trait represents a type waiting for a notification from an [`Event`]. See the
@@ -753,48 +2083,135 @@ pub trait Listener<T = ()>: Future<Output = T> + __sealed::Sealed {
     #[cfg(all(feature = "std", not(target_family = "wasm")))]
     fn wait(self) -> T;
 
-    /// Blocks until a notification is received or a timeout is reached.
-    ///
-    /// Returns `true` if a notification was received.
+    /// Blocks until a notification is received or a timeout is reached.
+    ///
+    /// Returns `true` if a notification was received.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use std::time::Duration;
+    /// use event_listener::{Event, Listener};
+    ///
+    /// let event = Event::new();
+    /// let mut listener = event.listen();
+    ///
+    /// // There are no notification so this times out.
+    /// assert!(listener.wait_timeout(Duration::from_secs(1)).is_none());
+    /// ```
+    #[cfg(all(feature = "std", not(target_family = "wasm")))]
+    fn wait_timeout(self, timeout: Duration) -> Option<T>;
+
+    /// Blocks until a notification is received or a deadline is reached.
+    ///
+    /// Returns `true` if a notification was received.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use std::time::{Duration, Instant};
+    /// use event_listener::{Event, Listener};
+    ///
+    /// let event = Event::new();
+    /// let mut listener = event.listen();
+    ///
+    /// // There are no notification so this times out.
+    /// assert!(listener.wait_deadline(Instant::now() + Duration::from_secs(1)).is_none());
+    /// ```
+    #[cfg(all(feature = "std", not(target_family = "wasm")))]
+    fn wait_deadline(self, deadline: Instant) -> Option<T>;
+
+    /// Blocks until a notification is received by spinning instead of parking the thread.
+    ///
+    /// This polls the listener in a loop with a [`spin_loop()`](core::hint::spin_loop) hint
+    /// in between, so it also works without the `std` feature, for example in the main loop of
+    /// firmware that has no operating system to park a thread. It keeps the CPU busy while
+    /// waiting, so prefer [`Listener::wait()`] where it is available.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use event_listener::{Event, Listener};
+    ///
+    /// let event = Event::new();
+    /// let listener = event.listen();
+    ///
+    /// event.notify(1);
+    /// listener.spin_wait();
+    /// ```
+    fn spin_wait(self) -> T
+    where
+        Self: Sized,
+    {
+        let mut listener = self;
+
+        // SAFETY: `listener` is shadowed, so it is never moved again.
+        let mut listener = unsafe { Pin::new_unchecked(&mut listener) };
+
+        let waker = noop_waker();
+        let mut cx = Context::from_waker(&waker);
+        loop {
+            if let Poll::Ready(tag) = listener.as_mut().poll(&mut cx) {
+                return tag;
+            }
+            core::hint::spin_loop();
+        }
+    }
+
+    /// Drops this listener and discards its notification (if any) without notifying another
+    /// active listener.
+    ///
+    /// Returns `true` if a notification was discarded.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use event_listener::{Event, Listener};
+    ///
+    /// let event = Event::new();
+    /// let mut listener1 = event.listen();
+    /// let mut listener2 = event.listen();
+    ///
+    /// event.notify(1);
+    ///
+    /// assert!(listener1.discard());
+    /// assert!(!listener2.discard());
+    /// ```
+    fn discard(self) -> bool;
+
+    /// Returns `true` if this listener listens to the given `Event`.
     ///
     /// # Examples
     ///
     /// ```
-    /// use std::time::Duration;
     /// use event_listener::{Event, Listener};
     ///
     /// let event = Event::new();
-    /// let mut listener = event.listen();
+    /// let listener = event.listen();
     ///
-    /// // There are no notification so this times out.
-    /// assert!(listener.wait_timeout(Duration::from_secs(1)).is_none());
+    /// assert!(listener.listens_to(&event));
     /// ```
-    #[cfg(all(feature = "std", not(target_family = "wasm")))]
-    fn wait_timeout(self, timeout: Duration) -> Option<T>;
+    fn listens_to(&self, event: &Event<T>) -> bool;
 
-    /// Blocks until a notification is received or a deadline is reached.
-    ///
-    /// Returns `true` if a notification was received.
+    /// Returns `true` if both listeners listen to the same `Event`.
     ///
     /// # Examples
     ///
     /// ```
-    /// use std::time::{Duration, Instant};
     /// use event_listener::{Event, Listener};
     ///
     /// let event = Event::new();
-    /// let mut listener = event.listen();
+    /// let listener1 = event.listen();
+    /// let listener2 = event.listen();
     ///
-    /// // There are no notification so this times out.
-    /// assert!(listener.wait_deadline(Instant::now() + Duration::from_secs(1)).is_none());
+    /// assert!(listener1.same_event(&listener2));
     /// ```
-    #[cfg(all(feature = "std", not(target_family = "wasm")))]
-    fn wait_deadline(self, deadline: Instant) -> Option<T>;
+    fn same_event(&self, other: &Self) -> bool;
 
-    /// Drops this listener and discards its notification (if any) without notifying another
-    /// active listener.
+    /// Returns `true` if this listener is still registered with its `Event`.
     ///
-    /// Returns `true` if a notification was discarded.
+    /// A listener stops listening once it has completed, or once it has been removed from the
+    /// event without a notification, for example because the event was poisoned.
     ///
     /// # Examples
     ///
@@ -802,17 +2219,14 @@ pub trait Listener<T = ()>: Future<Output = T> + __sealed::Sealed {
     /// use event_listener::{Event, Listener};
     ///
     /// let event = Event::new();
-    /// let mut listener1 = event.listen();
-    /// let mut listener2 = event.listen();
-    ///
-    /// event.notify(1);
+    /// let listener = event.listen();
     ///
-    /// assert!(listener1.discard());
-    /// assert!(!listener2.discard());
+    /// assert!(listener.is_listening());
     /// ```
-    fn discard(self) -> bool;
+    fn is_listening(&self) -> bool;
 
-    /// Returns `true` if this listener listens to the given `Event`.
+    /// Returns `true` if this listener has been notified, but has not received the notification
+    /// yet.
     ///
     /// # Examples
     ///
@@ -821,12 +2235,18 @@ pub trait Listener<T = ()>: Future<Output = T> + __sealed::Sealed {
     ///
     /// let event = Event::new();
     /// let listener = event.listen();
+    /// assert!(!listener.is_notified());
     ///
-    /// assert!(listener.listens_to(&event));
+    /// event.notify(1);
+    /// assert!(listener.is_notified());
     /// ```
-    fn listens_to(&self, event: &Event<T>) -> bool;
+    #[cfg(feature = "std")]
+    fn is_notified(&self) -> bool;
 
-    /// Returns `true` if both listeners listen to the same `Event`.
+    /// Returns the ID of this listener, which can be used to remove it with
+    /// [`Event::unlisten()`].
+    ///
+    /// Returns `None` if the listener has already completed.
     ///
     /// # Examples
     ///
@@ -837,9 +2257,10 @@ pub trait Listener<T = ()>: Future<Output = T> + __sealed::Sealed {
     /// let listener1 = event.listen();
     /// let listener2 = event.listen();
     ///
-    /// assert!(listener1.same_event(&listener2));
+    /// assert_ne!(listener1.id(), listener2.id());
     /// ```
-    fn same_event(&self, other: &Self) -> bool;
+    #[cfg(feature = "std")]
+    fn id(&self) -> Option<ListenerId>;
 }
 
 /// Implement the `Listener` trait using the underlying `InnerListener`.
@@ -848,93 +2269,483 @@ macro_rules! forward_impl_to_listener {
         impl<$gen> crate::Listener<$gen> for $ty {
             #[cfg(all(feature = "std", not(target_family = "wasm")))]
             fn wait(mut self) -> $gen {
-                self.listener_mut().wait_internal(None).unwrap()
+                crate::expect_uninterrupted(self.listener_mut().wait_internal(None)).unwrap()
             }
 
             #[cfg(all(feature = "std", not(target_family = "wasm")))]
             fn wait_timeout(mut self, timeout: std::time::Duration) -> Option<$gen> {
-                self.listener_mut()
-                    .wait_internal(std::time::Instant::now().checked_add(timeout))
+                crate::expect_uninterrupted(
+                    self.listener_mut()
+                        .wait_internal(std::time::Instant::now().checked_add(timeout)),
+                )
             }
 
             #[cfg(all(feature = "std", not(target_family = "wasm")))]
             fn wait_deadline(mut self, deadline: std::time::Instant) -> Option<$gen> {
-                self.listener_mut().wait_internal(Some(deadline))
+                crate::expect_uninterrupted(self.listener_mut().wait_internal(Some(deadline)))
+            }
+
+            fn discard(mut self) -> bool {
+                self.listener_mut().discard()
+            }
+
+            #[inline]
+            fn listens_to(&self, event: &Event<$gen>) -> bool {
+                core::ptr::eq::<Inner<$gen>>(
+                    &*self.listener().event,
+                    event.inner.load(core::sync::atomic::Ordering::Acquire),
+                )
+            }
+
+            #[inline]
+            fn same_event(&self, other: &$ty) -> bool {
+                core::ptr::eq::<Inner<$gen>>(&*self.listener().event, &*other.listener().event)
+            }
+
+            #[inline]
+            fn is_listening(&self) -> bool {
+                self.listener().is_listening()
+            }
+
+            #[cfg(feature = "std")]
+            #[inline]
+            fn is_notified(&self) -> bool {
+                self.listener().is_notified()
+            }
+
+            #[cfg(feature = "std")]
+            #[inline]
+            fn id(&self) -> Option<crate::ListenerId> {
+                self.listener().id()
+            }
+        }
+
+        impl<$gen> $ty {
+            /// Polls for a notification without pinning the listener first.
+            ///
+            /// This does the same as polling the listener as a [`Future`], for hand-written futures
+            /// that drive the listener from their own `poll` method.
+            ///
+            /// # Examples
+            ///
+            /// ```
+            /// use std::task::Context;
+            /// use event_listener::Event;
+            ///
+            /// let event = Event::new();
+            /// let mut listener = event.listen();
+            ///
+            /// let waker = waker_fn::waker_fn(|| ());
+            /// let mut cx = Context::from_waker(&waker);
+            ///
+            /// assert!(listener.poll_wait(&mut cx).is_pending());
+            /// event.notify(1);
+            /// assert!(listener.poll_wait(&mut cx).is_ready());
+            /// ```
+            #[inline]
+            pub fn poll_wait(&mut self, cx: &mut Context<'_>) -> Poll<$gen> {
+                self.listener_mut().poll_internal(cx)
+            }
+        }
+
+        impl<$gen> Future for $ty {
+            type Output = $gen;
+
+            #[inline]
+            fn poll(mut self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<$gen> {
+                self.listener_mut().poll_internal(cx)
+            }
+        }
+    };
+}
+
+/// A guard waiting for a notification from an [`Event`].
+///
+/// There are two ways for a listener to wait for a notification:
+///
+/// 1. In an asynchronous manner using `.await`.
+/// 2. In a blocking manner by calling [`EventListener::wait()`] on it.
+///
+/// If a notified listener is dropped without receiving a notification, dropping will notify
+/// another active listener. Whether one *additional* listener will be notified depends on what
+/// kind of notification was delivered.
+///
+/// See the [`Listener`] trait for the functionality exposed by this type.
+///
+/// This structure allocates the listener on the heap.
+pub struct EventListener<T = ()> {
+    listener: Pin<Box<InnerListener<T, Arc<Inner<T>>>>>,
+}
+
+unsafe impl<T: Send> Send for EventListener<T> {}
+unsafe impl<T: Send> Sync for EventListener<T> {}
+
+impl<T> core::panic::UnwindSafe for EventListener<T> {}
+impl<T> core::panic::RefUnwindSafe for EventListener<T> {}
+impl<T> Unpin for EventListener<T> {}
+
+impl<T> fmt::Debug for EventListener<T> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        self.listener().fmt_debug("EventListener", f)
+    }
+}
+
+impl<T> EventListener<T> {
+    #[inline]
+    fn listener(&self) -> &InnerListener<T, Arc<Inner<T>>> {
+        &self.listener
+    }
+
+    #[inline]
+    fn listener_mut(&mut self) -> Pin<&mut InnerListener<T, Arc<Inner<T>>>> {
+        self.listener.as_mut()
+    }
+
+    /// Transforms the tag of the notification with a function.
+    ///
+    /// The returned [`MapListener`] still behaves like this listener: dropping it before it
+    /// completes passes a received notification on to another listener.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use event_listener::{Event, IntoNotification};
+    ///
+    /// let event = Event::<u32>::with_tag();
+    /// let listener = event.listen().map(|n| format!("job {}", n));
+    ///
+    /// event.notify(1.tag(7));
+    /// # futures_lite::future::block_on(async {
+    /// assert_eq!(listener.await, "job 7");
+    /// # });
+    /// ```
+    #[inline]
+    pub fn map<U, F>(self, map: F) -> MapListener<T, F>
+    where
+        F: FnOnce(T) -> U,
+    {
+        MapListener {
+            listener: self,
+            map: Some(map),
+        }
+    }
+}
+
+/// A listener that transforms the tag of its notification.
+///
+/// This is created by [`EventListener::map()`].
+pub struct MapListener<T, F> {
+    listener: EventListener<T>,
+    map: Option<F>,
+}
+
+impl<T, F> fmt::Debug for MapListener<T, F> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("MapListener").finish_non_exhaustive()
+    }
+}
+
+// The function is never pinned.
+impl<T, F> Unpin for MapListener<T, F> {}
+
+impl<T, U, F> MapListener<T, F>
+where
+    F: FnOnce(T) -> U,
+{
+    /// Blocks until a notification is received.
+    ///
+    /// See [`Listener::wait()`].
+    #[cfg(all(feature = "std", not(target_family = "wasm")))]
+    pub fn wait(self) -> U {
+        let map = self.map.expect("`MapListener` polled after completion");
+        map(self.listener.wait())
+    }
+
+    /// Blocks until a notification is received or a timeout is reached.
+    ///
+    /// See [`Listener::wait_timeout()`].
+    #[cfg(all(feature = "std", not(target_family = "wasm")))]
+    pub fn wait_timeout(self, timeout: Duration) -> Option<U> {
+        let map = self.map.expect("`MapListener` polled after completion");
+        self.listener.wait_timeout(timeout).map(map)
+    }
+}
+
+impl<T, F> MapListener<T, F> {
+    /// Returns `true` if the listener is still registered with its event.
+    ///
+    /// See [`Listener::is_listening()`].
+    #[inline]
+    pub fn is_listening(&self) -> bool {
+        self.listener.is_listening()
+    }
+
+    /// Drops this listener and discards its notification (if any) without notifying another
+    /// active listener.
+    ///
+    /// See [`Listener::discard()`].
+    #[inline]
+    pub fn discard(self) -> bool {
+        self.listener.discard()
+    }
+}
+
+impl<T, U, F> Future for MapListener<T, F>
+where
+    F: FnOnce(T) -> U,
+{
+    type Output = U;
+
+    fn poll(mut self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Self::Output> {
+        match Pin::new(&mut self.listener).poll(cx) {
+            Poll::Ready(tag) => {
+                let map = self
+                    .map
+                    .take()
+                    .expect("`MapListener` polled after completion");
+                Poll::Ready(map(tag))
             }
+            Poll::Pending => Poll::Pending,
+        }
+    }
+}
+
+forward_impl_to_listener! { T => EventListener<T> }
+
+/// A guard waiting for a notification from an [`Event`] that it borrows.
+///
+/// This is created by [`Event::listen_borrowed()`]. It behaves like an [`EventListener`], but
+/// does not keep the event alive.
+///
+/// See the [`Listener`] trait for the functionality exposed by this type.
+pub struct BorrowedListener<'a, T = ()> {
+    listener: Pin<Box<InnerListener<T, &'a Inner<T>>>>,
+}
+
+unsafe impl<T: Send> Send for BorrowedListener<'_, T> {}
+unsafe impl<T: Send> Sync for BorrowedListener<'_, T> {}
+
+impl<T> core::panic::UnwindSafe for BorrowedListener<'_, T> {}
+impl<T> core::panic::RefUnwindSafe for BorrowedListener<'_, T> {}
+impl<T> Unpin for BorrowedListener<'_, T> {}
+
+impl<T> fmt::Debug for BorrowedListener<'_, T> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        self.listener().fmt_debug("BorrowedListener", f)
+    }
+}
+
+impl<'a, T> BorrowedListener<'a, T> {
+    #[inline]
+    fn listener(&self) -> &InnerListener<T, &'a Inner<T>> {
+        &self.listener
+    }
+
+    #[inline]
+    fn listener_mut(&mut self) -> Pin<&mut InnerListener<T, &'a Inner<T>>> {
+        self.listener.as_mut()
+    }
+}
+
+forward_impl_to_listener! { T => BorrowedListener<'_, T> }
+
+#[cfg(feature = "std")]
+impl<T> EventListener<T> {
+    /// Wraps this listener so that it reports why it completed without a notification.
+    ///
+    /// A plain listener panics if it is interrupted, for example because the event was
+    /// [poisoned](Event::poison_guard). The returned [`CheckedListener`] resolves to a
+    /// [`WaitError`] instead.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use event_listener::Event;
+    ///
+    /// let event = Event::new();
+    /// let listener = event.listen().checked();
+    ///
+    /// event.notify(1);
+    /// assert_eq!(listener.wait(), Ok(()));
+    /// ```
+    #[inline]
+    pub fn checked(self) -> CheckedListener<T> {
+        CheckedListener { listener: self }
+    }
+
+    /// Returns a handle that cancels this listener from another thread.
+    ///
+    /// The handle is taken before the listener is moved into a blocking [`wait()`]. Calling
+    /// [`WaitCanceller::cancel()`] then works like [`Event::unlisten()`]: the waiting thread is
+    /// woken up, and a [checked](EventListener::checked) wait completes with
+    /// [`WaitError::Cancelled`].
+    ///
+    /// Returns `None` if the listener is not registered with its event.
+    ///
+    /// [`wait()`]: Listener::wait
+    ///
+    /// # Panics
+    ///
+    /// A plain [`wait()`] or `.await` of this listener panics once it is cancelled. Wait on
+    /// [`EventListener::checked()`] to get the error instead.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use std::thread;
+    /// use event_listener::{Event, WaitError};
+    ///
+    /// let event = Event::new();
+    /// let listener = event.listen();
+    /// let canceller = listener.canceller().unwrap();
+    ///
+    /// let handle = thread::spawn(move || listener.checked().wait());
+    ///
+    /// canceller.cancel();
+    /// assert_eq!(handle.join().unwrap(), Err(WaitError::Cancelled));
+    /// ```
+    pub fn canceller(&self) -> Option<WaitCanceller<T>> {
+        let id = self.listener().id()?;
+        Some(WaitCanceller {
+            event: self.listener.event.clone(),
+            id,
+        })
+    }
+
+    /// Drops this listener, unless that would block on the listener list of the event.
+    ///
+    /// Dropping a listener locks the listener list, so a destructor can wait on a thread that is
+    /// notifying the event. This gives the listener back instead if someone else holds the lock,
+    /// so that it can be dropped later, for example after the next notification.
+    ///
+    /// A received notification is passed on to another listener, as if the listener had been
+    /// dropped.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use event_listener::Event;
+    ///
+    /// let event = Event::new();
+    /// let listener = event.listen();
+    ///
+    /// assert!(listener.try_drop().is_ok());
+    /// assert_eq!(event.total_listeners(), 0);
+    /// ```
+    pub fn try_drop(mut self) -> Result<(), Self> {
+        if self.listener_mut().try_remove() {
+            Ok(())
+        } else {
+            Err(self)
+        }
+    }
+}
 
-            fn discard(mut self) -> bool {
-                self.listener_mut().discard()
-            }
+/// A handle that cancels a listener from another thread.
+///
+/// This is created by [`EventListener::canceller()`]. It keeps the event alive, but not the
+/// listener: cancelling a listener that has completed or been dropped does nothing.
+#[cfg(feature = "std")]
+pub struct WaitCanceller<T = ()> {
+    event: Arc<Inner<T>>,
+    id: ListenerId,
+}
 
-            #[inline]
-            fn listens_to(&self, event: &Event<$gen>) -> bool {
-                core::ptr::eq::<Inner<$gen>>(
-                    &*self.listener().event,
-                    event.inner.load(core::sync::atomic::Ordering::Acquire),
-                )
-            }
+#[cfg(feature = "std")]
+unsafe impl<T: Send> Send for WaitCanceller<T> {}
+#[cfg(feature = "std")]
+unsafe impl<T: Send> Sync for WaitCanceller<T> {}
 
-            #[inline]
-            fn same_event(&self, other: &$ty) -> bool {
-                core::ptr::eq::<Inner<$gen>>(&*self.listener().event, &*other.listener().event)
-            }
+#[cfg(feature = "std")]
+impl<T> Clone for WaitCanceller<T> {
+    fn clone(&self) -> Self {
+        Self {
+            event: self.event.clone(),
+            id: self.id,
         }
+    }
+}
 
-        impl<$gen> Future for $ty {
-            type Output = $gen;
+#[cfg(feature = "std")]
+impl<T> fmt::Debug for WaitCanceller<T> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("WaitCanceller")
+            .field("id", &self.id)
+            .finish_non_exhaustive()
+    }
+}
 
-            #[inline]
-            fn poll(mut self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<$gen> {
-                self.listener_mut().poll_internal(cx)
-            }
-        }
-    };
+#[cfg(feature = "std")]
+impl<T> WaitCanceller<T> {
+    /// Cancels the listener, waking it up if it is waiting.
+    ///
+    /// A listener that has already been notified keeps its notification. Returns `true` if the
+    /// listener was cancelled.
+    pub fn cancel(&self) -> bool {
+        cancel(&self.event, self.id)
+    }
+
+    /// Returns the ID of the listener this handle cancels.
+    #[inline]
+    pub fn id(&self) -> ListenerId {
+        self.id
+    }
 }
 
-/// A guard waiting for a notification from an [`Event`].
-///
-/// There are two ways for a listener to wait for a notification:
+/// A listener that reports why it completed without a notification.
 ///
-/// 1. In an asynchronous manner using `.await`.
-/// 2. In a blocking manner by calling [`EventListener::wait()`] on it.
-///
-/// If a notified listener is dropped without receiving a notification, dropping will notify
-/// another active listener. Whether one *additional* listener will be notified depends on what
-/// kind of notification was delivered.
-///
-/// See the [`Listener`] trait for the functionality exposed by this type.
-///
-/// This structure allocates the listener on the heap.
-pub struct EventListener<T = ()> {
-    listener: Pin<Box<InnerListener<T, Arc<Inner<T>>>>>,
+/// This is created by [`EventListener::checked()`]. It resolves to `Ok` with the tag of the
+/// notification, or to a [`WaitError`] if the listener was interrupted.
+#[cfg(feature = "std")]
+pub struct CheckedListener<T = ()> {
+    listener: EventListener<T>,
 }
 
-unsafe impl<T: Send> Send for EventListener<T> {}
-unsafe impl<T: Send> Sync for EventListener<T> {}
-
-impl<T> core::panic::UnwindSafe for EventListener<T> {}
-impl<T> core::panic::RefUnwindSafe for EventListener<T> {}
-impl<T> Unpin for EventListener<T> {}
-
-impl<T> fmt::Debug for EventListener<T> {
+#[cfg(feature = "std")]
+impl<T> fmt::Debug for CheckedListener<T> {
     fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
-        f.debug_struct("EventListener").finish_non_exhaustive()
+        f.debug_struct("CheckedListener").finish_non_exhaustive()
     }
 }
 
-impl<T> EventListener<T> {
+#[cfg(feature = "std")]
+impl<T> Unpin for CheckedListener<T> {}
+
+#[cfg(feature = "std")]
+impl<T> CheckedListener<T> {
+    /// Blocks until a notification is received or the listener is interrupted.
+    #[cfg(not(target_family = "wasm"))]
+    pub fn wait(mut self) -> Result<T, WaitError> {
+        self.listener
+            .listener_mut()
+            .wait_internal(None)
+            .map(|tag| tag.unwrap())
+    }
+
+    /// Polls for a notification or an interruption without pinning the listener first.
+    ///
+    /// This does the same as polling the listener as a [`Future`].
     #[inline]
-    fn listener(&self) -> &InnerListener<T, Arc<Inner<T>>> {
-        &self.listener
+    pub fn poll_wait(&mut self, cx: &mut Context<'_>) -> Poll<Result<T, WaitError>> {
+        self.listener.listener_mut().poll_checked(cx)
     }
 
+    /// Returns the wrapped listener.
     #[inline]
-    fn listener_mut(&mut self) -> Pin<&mut InnerListener<T, Arc<Inner<T>>>> {
-        self.listener.as_mut()
+    pub fn into_inner(self) -> EventListener<T> {
+        self.listener
     }
 }
 
-forward_impl_to_listener! { T => EventListener<T> }
+#[cfg(feature = "std")]
+impl<T> Future for CheckedListener<T> {
+    type Output = Result<T, WaitError>;
+
+    #[inline]
+    fn poll(mut self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Self::Output> {
+        self.poll_wait(cx)
+    }
+}
 
 /// Create a stack-based event listener for an [`Event`].
 ///
@@ -1078,9 +2889,24 @@ impl<T, B: Borrow<Inner<T>> + Unpin> InnerListener<T, B> {
         (*this.event).borrow().insert(this.listener);
     }
 
+    /// Insert this listener into the linked list with the given options.
+    ///
+    /// Returns the ID of the listener.
+    #[cfg(feature = "std")]
+    #[inline]
+    fn listen_with(self: Pin<&mut Self>, options: ListenOptions<T>) -> u64 {
+        let this = self.project();
+        (*this.event).borrow().insert_with(this.listener, options)
+    }
+
     /// Wait until the provided deadline.
+    ///
+    /// Returns `Ok(None)` if the deadline passed, or an error if the listener was interrupted.
     #[cfg(all(feature = "std", not(target_family = "wasm")))]
-    fn wait_internal(mut self: Pin<&mut Self>, deadline: Option<Instant>) -> Option<T> {
+    fn wait_internal(
+        mut self: Pin<&mut Self>,
+        deadline: Option<Instant>,
+    ) -> Result<Option<T>, WaitError> {
         fn parker_and_task() -> (Parker, Task) {
             let parker = Parker::new();
             let unparker = parker.unparker();
@@ -1114,16 +2940,20 @@ impl<T, B: Borrow<Inner<T>> + Unpin> InnerListener<T, B> {
         deadline: Option<Instant>,
         parker: &Parker,
         unparker: TaskRef<'_>,
-    ) -> Option<T> {
+    ) -> Result<Option<T>, WaitError> {
         let mut this = self.project();
         let inner = (*this.event).borrow();
 
         // Set the listener's state to `Task`.
-        if let Some(tag) = inner.register(this.listener.as_mut(), unparker).notified() {
+        if let Some(tag) = inner.register(this.listener.as_mut(), unparker).checked()? {
             // We were already notified, so we don't need to park.
-            return Some(tag);
+            return Ok(Some(tag));
         }
 
+        // Boost the producer until we stop waiting, however that happens.
+        #[cfg(feature = "priority-inheritance")]
+        let _boost = inner.priority_inheritance().map(priority::Boost::new);
+
         // Wait until a notification is received or the timeout is reached.
         loop {
             match deadline {
@@ -1140,22 +2970,92 @@ impl<T, B: Borrow<Inner<T>> + Unpin> InnerListener<T, B> {
                     let now = Instant::now();
                     if now >= deadline {
                         // Remove our entry and check if we were notified.
-                        return inner
-                            .remove(this.listener.as_mut(), false)
-                            .expect("We never removed ourself from the list")
-                            .notified();
+                        return Ok(inner
+                            .take(this.listener.as_mut())
+                            .expect("We never removed ourself from the list"));
                     }
                     parker.park_deadline(deadline);
                 }
             }
 
             // See if we were notified.
-            if let Some(tag) = inner.register(this.listener.as_mut(), unparker).notified() {
-                return Some(tag);
+            if let Some(tag) = inner.register(this.listener.as_mut(), unparker).checked()? {
+                return Ok(Some(tag));
             }
         }
     }
 
+    /// Tell whether this listener is still registered with its event.
+    fn is_listening(&self) -> bool {
+        #[cfg(feature = "std")]
+        {
+            self.event
+                .borrow()
+                .with_state(self.listener.as_ref(), |state| {
+                    !matches!(state, State::Interrupted(_))
+                })
+                .unwrap_or(false)
+        }
+
+        #[cfg(not(feature = "std"))]
+        {
+            self.listener.is_some()
+        }
+    }
+
+    /// Tell whether this listener has been notified.
+    #[cfg(feature = "std")]
+    fn is_notified(&self) -> bool {
+        self.event
+            .borrow()
+            .with_state(self.listener.as_ref(), State::is_notified)
+            .unwrap_or(false)
+    }
+
+    /// Get the ID of this listener.
+    #[cfg(feature = "std")]
+    fn id(&self) -> Option<ListenerId> {
+        self.event
+            .borrow()
+            .id(self.listener.as_ref())
+            .map(ListenerId)
+    }
+
+    /// Format this listener for `Debug` output, with the given type name.
+    fn fmt_debug(&self, name: &str, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let mut s = f.debug_struct(name);
+        s.field("event", &(self.event.borrow() as *const Inner<T>));
+
+        #[cfg(feature = "std")]
+        {
+            let state = match self.listener.as_ref() {
+                None => Some(ListenerState::Completed),
+                listener => self
+                    .event
+                    .borrow()
+                    .try_with_state(listener, ListenerState::new::<T>),
+            };
+            match state {
+                Some(state) => s.field("state", &state),
+                None => s.field("state", &format_args!("<locked>")),
+            };
+        }
+
+        #[cfg(not(feature = "std"))]
+        s.field("listening", &self.listener.is_some());
+
+        s.finish()
+    }
+
+    /// Remove this listener from the list, unless that would block.
+    ///
+    /// Returns `false` if the listener is still in the list.
+    #[cfg(feature = "std")]
+    fn try_remove(self: Pin<&mut Self>) -> bool {
+        let this = self.project();
+        (*this.event).borrow().try_remove(this.listener, true)
+    }
+
     /// Drops this listener and discards its notification (if any) without notifying another
     /// active listener.
     fn discard(self: Pin<&mut Self>) -> bool {
@@ -1171,24 +3071,185 @@ impl<T, B: Borrow<Inner<T>> + Unpin> InnerListener<T, B> {
         let this = self.project();
         let inner = (*this.event).borrow();
 
-        // Try to register the listener.
-        match inner
-            .register(this.listener, TaskRef::Waker(cx.waker()))
-            .notified()
-        {
-            Some(tag) => {
-                // We were already notified, so we don't need to park.
-                Poll::Ready(tag)
+        cooperate(cx, |cx| {
+            // Try to register the listener.
+            match inner
+                .register(this.listener, TaskRef::Waker(cx.waker()))
+                .notified()
+            {
+                Some(tag) => {
+                    // We were already notified, so we don't need to park.
+                    Poll::Ready(tag)
+                }
+
+                None => {
+                    // We're now waiting for a notification.
+                    Poll::Pending
+                }
             }
+        })
+    }
+
+    /// Poll this subscription for its next buffered notification.
+    #[cfg(feature = "std")]
+    fn poll_subscription(self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Result<T, WaitError>> {
+        let this = self.project();
+        let inner = (*this.event).borrow();
+
+        cooperate(cx, |cx| {
+            inner.poll_subscription(this.listener, TaskRef::Waker(cx.waker()))
+        })
+    }
+
+    /// Get the number of buffered notifications of this subscription.
+    #[cfg(feature = "std")]
+    fn buffered(&self) -> usize {
+        self.event.borrow().buffered(self.listener.as_ref())
+    }
+
+    /// Poll this listener for a notification, reporting if it was interrupted.
+    #[cfg(feature = "std")]
+    fn poll_checked(self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Result<T, WaitError>> {
+        let this = self.project();
+        let inner = (*this.event).borrow();
 
-            None => {
-                // We're now waiting for a notification.
-                Poll::Pending
+        cooperate(cx, |cx| {
+            match inner
+                .register(this.listener, TaskRef::Waker(cx.waker()))
+                .checked()
+            {
+                Ok(Some(tag)) => Poll::Ready(Ok(tag)),
+                Ok(None) => Poll::Pending,
+                Err(reason) => Poll::Ready(Err(reason)),
             }
+        })
+    }
+}
+
+/// Polls a listener within the cooperative budget of the current tokio task.
+///
+/// Once the budget is used up, the listener is not polled and the task is asked to yield, even if
+/// a notification is waiting. Receiving a notification uses up a unit of the budget.
+#[cfg(feature = "tokio")]
+#[inline]
+fn cooperate<R>(cx: &mut Context<'_>, poll: impl FnOnce(&mut Context<'_>) -> Poll<R>) -> Poll<R> {
+    let coop = match tokio::task::coop::poll_proceed(cx) {
+        Poll::Ready(coop) => coop,
+        Poll::Pending => return Poll::Pending,
+    };
+
+    let result = poll(cx);
+    if result.is_ready() {
+        coop.made_progress();
+    }
+    result
+}
+
+/// Polls a listener.
+#[cfg(not(feature = "tokio"))]
+#[inline]
+fn cooperate<R>(cx: &mut Context<'_>, poll: impl FnOnce(&mut Context<'_>) -> Poll<R>) -> Poll<R> {
+    poll(cx)
+}
+
+/// The state of a listener, as shown in its `Debug` output.
+#[cfg(feature = "std")]
+enum ListenerState {
+    /// The listener is registered, but has not been polled yet.
+    Created,
+
+    /// A task is waiting for a notification.
+    Waiting,
+
+    /// The listener has been notified, but has not received the notification yet.
+    Notified {
+        /// Whether this is an "additional" notification.
+        additional: bool,
+
+        /// The type of the tag.
+        tag: &'static str,
+    },
+
+    /// The listener was detached from the event without being notified.
+    Interrupted(WaitError),
+
+    /// The listener has received its notification, or has been removed from the event.
+    Completed,
+}
+
+#[cfg(feature = "std")]
+impl ListenerState {
+    fn new<T>(state: &State<T>) -> Self {
+        match state {
+            State::Created => Self::Created,
+            State::Task(_) => Self::Waiting,
+            State::Notified { additional, .. } => Self::Notified {
+                additional: *additional,
+                tag: core::any::type_name::<T>(),
+            },
+            State::NotifiedTaken => Self::Completed,
+            State::Interrupted(reason) => Self::Interrupted(*reason),
+        }
+    }
+}
+
+#[cfg(feature = "std")]
+impl fmt::Debug for ListenerState {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::Created => f.write_str("Created"),
+            Self::Waiting => f.write_str("Waiting"),
+            Self::Notified { additional, tag } => f
+                .debug_struct("Notified")
+                .field("additional", additional)
+                .field("tag", tag)
+                .finish(),
+            Self::Interrupted(reason) => f.debug_tuple("Interrupted").field(reason).finish(),
+            Self::Completed => f.write_str("Completed"),
+        }
+    }
+}
+
+/// Parameters that a listener is registered with.
+#[cfg(feature = "std")]
+struct ListenOptions<T> {
+    /// The number of notification units this listener consumes.
+    weight: usize,
+
+    /// Clones the tag when a notification is forwarded to more than one listener.
+    clone_tag: Option<fn(&T) -> T>,
+
+    /// The group that this listener belongs to, if any.
+    group: Option<usize>,
+
+    /// Decides which tags this listener accepts.
+    filter: Option<Filter<T>>,
+
+    /// The class of this listener.
+    class: ListenerClass,
+
+    /// Whether this listener is a subscription, which buffers its notifications.
+    subscribed: bool,
+}
+
+#[cfg(feature = "std")]
+impl<T> Default for ListenOptions<T> {
+    fn default() -> Self {
+        Self {
+            weight: 1,
+            clone_tag: None,
+            group: None,
+            filter: None,
+            class: ListenerClass::A,
+            subscribed: false,
         }
     }
 }
 
+/// A predicate on the tags that a listener accepts.
+#[cfg(feature = "std")]
+type Filter<T> = Box<dyn Fn(&T) -> bool + Send + Sync>;
+
 /// The state of a listener.
 #[derive(PartialEq)]
 enum State<T> {
@@ -1211,6 +3272,10 @@ enum State<T> {
 
     /// Empty hole used to replace a notified listener.
     NotifiedTaken,
+
+    /// The listener was detached from the list without being notified.
+    #[cfg(feature = "std")]
+    Interrupted(WaitError),
 }
 
 impl<T> fmt::Debug for State<T> {
@@ -1223,6 +3288,8 @@ impl<T> fmt::Debug for State<T> {
                 .finish(),
             Self::Task(_) => f.write_str("Task(_)"),
             Self::NotifiedTaken => f.write_str("NotifiedTaken"),
+            #[cfg(feature = "std")]
+            Self::Interrupted(reason) => f.debug_tuple("Interrupted").field(reason).finish(),
         }
     }
 }
@@ -1254,6 +3321,10 @@ enum RegisterResult<T> {
 
     /// The listener was never inserted into the list.
     NeverInserted,
+
+    /// The listener was detached from the list without being notified.
+    #[cfg(feature = "std")]
+    Interrupted(WaitError),
 }
 
 impl<T> RegisterResult<T> {
@@ -1265,10 +3336,141 @@ impl<T> RegisterResult<T> {
             Self::Notified(tag) => Some(tag),
             Self::Registered => None,
             Self::NeverInserted => panic!("{}", NEVER_INSERTED_PANIC),
+            #[cfg(feature = "std")]
+            Self::Interrupted(reason) => panic!("{}", reason),
+        }
+    }
+
+    /// Whether or not the listener was notified, or why it was interrupted.
+    ///
+    /// Panics if the listener was never inserted into the list.
+    #[cfg(feature = "std")]
+    fn checked(self) -> Result<Option<T>, WaitError> {
+        match self {
+            Self::Interrupted(reason) => Err(reason),
+            other => Ok(other.notified()),
+        }
+    }
+}
+
+/// The reason why a listener completed without receiving a notification.
+///
+/// This is returned by [`CheckedListener`], which is created with [`EventListener::checked()`],
+/// and by `CheckedTimeoutListener`, which is created with `TimeoutListener::checked()`. Other
+/// listeners panic with this error instead.
+#[cfg(feature = "std")]
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+#[non_exhaustive]
+pub enum WaitError {
+    /// The deadline of the listener passed.
+    #[cfg_attr(any(target_family = "wasm", loom), allow(dead_code))]
+    TimedOut,
+
+    /// A notifier panicked while holding a [`PoisonGuard`] for the event.
+    Poisoned,
+
+    /// The listener was removed with [`Event::unlisten()`] or [`WaitCanceller::cancel()`].
+    Cancelled,
+
+    /// The event was closed with [`Event::notify_all_and_close()`].
+    Closed,
+
+    /// The listener was removed by [`Event::drain()`].
+    Drained,
+}
+
+#[cfg(feature = "std")]
+impl fmt::Display for WaitError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::TimedOut => f.write_str("the deadline of the listener passed"),
+            Self::Poisoned => f.write_str("a notifier panicked while holding a poison guard"),
+            Self::Cancelled => f.write_str("the listener was removed from the event"),
+            Self::Closed => f.write_str("the event was closed"),
+            Self::Drained => f.write_str("the listeners of the event were drained"),
         }
     }
 }
 
+#[cfg(feature = "std")]
+impl std::error::Error for WaitError {}
+
+/// Identifies a listener of an [`Event`].
+///
+/// This is returned by [`Listener::id()`] and can be passed to [`Event::unlisten()`]. IDs are
+/// only unique among the listeners of one event. They increase in registration order, so
+/// comparing the IDs of two listeners tells which one is older.
+#[cfg(feature = "std")]
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub struct ListenerId(u64);
+
+/// One of the two classes that the listeners of an [`Event`] can belong to.
+///
+/// Listeners registered with [`Event::listen_class()`] can be targeted by class, for example
+/// to tell readers and writers of a lock apart. Listeners registered any other way belong to
+/// class `A`.
+#[cfg(feature = "std")]
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum ListenerClass {
+    /// The first class, which contains every listener registered without a class.
+    A,
+
+    /// The second class.
+    B,
+}
+
+/// The error returned by [`Event::try_notify()`] when the event is busy.
+#[cfg(feature = "std")]
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct WouldBlock;
+
+#[cfg(feature = "std")]
+impl fmt::Display for WouldBlock {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str("the listener list of the event is locked")
+    }
+}
+
+#[cfg(feature = "std")]
+impl std::error::Error for WouldBlock {}
+
+/// Forward the notifications of an event to `parent`, with tags produced by `tag`.
+#[cfg(feature = "std")]
+fn forward_to<U, F>(parent: std::sync::Arc<Event<U>>, tag: F) -> std::sync::Arc<sys::Parent>
+where
+    U: Send + 'static,
+    F: Fn() -> U + Send + Sync + 'static,
+{
+    std::sync::Arc::new(move |count, additional| {
+        parent.notify(notify::GenericNotify::new(count, additional, &tag));
+    })
+}
+
+/// Remove the listener with the given ID from an event, waking it up with
+/// [`WaitError::Cancelled`].
+#[cfg(feature = "std")]
+fn cancel<T>(inner: &Inner<T>, id: ListenerId) -> bool {
+    match inner.interrupt(id.0, WaitError::Cancelled) {
+        Some(task) => {
+            if let Some(task) = task {
+                task.wake();
+            }
+            true
+        }
+        None => false,
+    }
+}
+
+/// Unwrap the outcome of a blocking wait, panicking if the listener was interrupted.
+#[cfg(all(feature = "std", not(target_family = "wasm")))]
+fn expect_uninterrupted<T>(result: Result<Option<T>, WaitError>) -> Option<T> {
+    match result {
+        Ok(tag) => tag,
+        Err(WaitError::TimedOut) => None,
+        Err(err) => panic!("{}", err),
+    }
+}
+
 /// A task that can be woken up.
 #[derive(Debug, Clone)]
 enum Task {
@@ -1446,10 +3648,11 @@ fn __test_send_and_sync() {
 
 #[doc(hidden)]
 mod __sealed {
-    use super::{EventListener, __private::StackListener};
+    use super::{BorrowedListener, EventListener, __private::StackListener};
 
     pub trait Sealed {}
     impl<T> Sealed for EventListener<T> {}
+    impl<T> Sealed for BorrowedListener<'_, T> {}
     impl<T> Sealed for StackListener<'_, '_, T> {}
 }
 
@@ -1524,7 +3727,7 @@ pub mod __private {
     impl<T> fmt::Debug for StackListener<'_, '_, T> {
         #[inline]
         fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
-            f.debug_struct("StackListener").finish_non_exhaustive()
+            self.listener().fmt_debug("StackListener", f)
         }
     }
 
@@ -0,0 +1,718 @@
+//! Async locks built on top of [`Event`].
+//!
+//! The locks are fair: waiters are registered as listeners of an event, and the lock is handed
+//! directly to the oldest waiter when it is unlocked. A waiter that arrives while others are
+//! queued waits its turn, even if the lock happens to be unlocked at that moment. An [`RwLock`]
+//! queues readers and writers as two [classes](crate::ListenerClass) of listeners, and
+//! prefers one of them when both are waiting.
+//!
+//! Every lock can be used from async code with `.await`, and from threads with the `blocking_`
+//! methods.
+//!
+//! This module is only available with the `locks` feature.
+//!
+//! # Examples
+//!
+//! ```
+//! use event_listener::locks::Mutex;
+//!
+//! let mutex = Mutex::new(0);
+//!
+//! # futures_lite::future::block_on(async {
+//! *mutex.lock().await += 1;
+//! # });
+//! assert_eq!(*mutex.blocking_lock(), 1);
+//! ```
+
+use crate::condvar::{AsyncLockable, Lockable};
+use crate::{Event, EventListener, IntoNotification, Listener, ListenerClass};
+
+use std::sync::{Mutex as StdMutex, MutexGuard as StdMutexGuard};
+
+use core::cell::UnsafeCell;
+use core::fmt;
+use core::future::Future;
+use core::ops::{Deref, DerefMut};
+use core::pin::Pin;
+use core::task::{Context, Poll};
+
+/// The state of a [`Mutex`].
+#[derive(Debug, Default)]
+struct MutexState {
+    /// Whether the mutex is locked, or handed to a waiter that has not woken up yet.
+    locked: bool,
+
+    /// The number of waiters that have not been handed the lock yet.
+    waiters: usize,
+}
+
+/// A fair mutex for async tasks and threads.
+///
+/// See the [module documentation](self) for more information.
+pub struct Mutex<T: ?Sized> {
+    /// The lock state.
+    state: StdMutex<MutexState>,
+
+    /// Waiters are notified, one at a time, when the lock is handed to them.
+    event: Event,
+
+    /// The protected value.
+    value: UnsafeCell<T>,
+}
+
+unsafe impl<T: ?Sized + Send> Send for Mutex<T> {}
+unsafe impl<T: ?Sized + Send> Sync for Mutex<T> {}
+
+impl<T: ?Sized + fmt::Debug> fmt::Debug for Mutex<T> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let mut s = f.debug_struct("Mutex");
+        match self.try_lock() {
+            Some(guard) => s.field("value", &&*guard),
+            None => s.field("value", &format_args!("<locked>")),
+        };
+        s.finish()
+    }
+}
+
+impl<T: Default> Default for Mutex<T> {
+    fn default() -> Self {
+        Self::new(T::default())
+    }
+}
+
+impl<T> Mutex<T> {
+    /// Creates a new mutex holding `value`.
+    pub fn new(value: T) -> Self {
+        Self {
+            state: StdMutex::new(MutexState::default()),
+            event: Event::new(),
+            value: UnsafeCell::new(value),
+        }
+    }
+
+    /// Returns the value held by the mutex.
+    pub fn into_inner(self) -> T {
+        self.value.into_inner()
+    }
+}
+
+impl<T: ?Sized> Mutex<T> {
+    /// Locks the mutex.
+    ///
+    /// The returned future is cancel-safe: if it is dropped after the lock was handed to it, the
+    /// lock is passed on to the next waiter.
+    pub fn lock(&self) -> Lock<'_, T> {
+        Lock {
+            mutex: self,
+            listener: None,
+        }
+    }
+
+    /// Blocks until the mutex is locked.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use std::sync::Arc;
+    /// use std::thread;
+    /// use event_listener::locks::Mutex;
+    ///
+    /// let mutex = Arc::new(Mutex::new(0));
+    /// let handles: Vec<_> = (0..4)
+    ///     .map(|_| {
+    ///         let mutex = mutex.clone();
+    ///         thread::spawn(move || *mutex.blocking_lock() += 1)
+    ///     })
+    ///     .collect();
+    ///
+    /// for handle in handles {
+    ///     handle.join().unwrap();
+    /// }
+    /// assert_eq!(*mutex.blocking_lock(), 4);
+    /// ```
+    #[cfg(not(target_family = "wasm"))]
+    pub fn blocking_lock(&self) -> MutexGuard<'_, T> {
+        if let Some(listener) = self.acquire_or_listen() {
+            listener.wait();
+        }
+        MutexGuard { mutex: self }
+    }
+
+    /// Locks the mutex, unless it is locked or other waiters are queued.
+    pub fn try_lock(&self) -> Option<MutexGuard<'_, T>> {
+        let mut state = self.state();
+        if state.locked || state.waiters > 0 {
+            return None;
+        }
+
+        state.locked = true;
+        Some(MutexGuard { mutex: self })
+    }
+
+    /// Returns a mutable reference to the value.
+    ///
+    /// No locking is needed, since the mutex is borrowed mutably.
+    pub fn get_mut(&mut self) -> &mut T {
+        self.value.get_mut()
+    }
+
+    /// Locks the mutex if it is free and nobody is queued, or queues up for it.
+    ///
+    /// Returns the listener that is notified once the lock is handed over.
+    fn acquire_or_listen(&self) -> Option<EventListener> {
+        let mut state = self.state();
+        if !state.locked && state.waiters == 0 {
+            state.locked = true;
+            return None;
+        }
+
+        state.waiters += 1;
+        Some(self.event.listen())
+    }
+
+    /// Hands the lock to the oldest waiter, or unlocks the mutex if nobody is waiting.
+    fn release(&self, state: &mut MutexState) {
+        if state.waiters > 0 {
+            // The lock stays locked on behalf of the waiter.
+            state.waiters -= 1;
+            self.event.notify(1.additional());
+        } else {
+            state.locked = false;
+        }
+    }
+
+    /// Lock the state of the mutex.
+    fn state(&self) -> StdMutexGuard<'_, MutexState> {
+        self.state.lock().unwrap_or_else(|e| e.into_inner())
+    }
+}
+
+/// The future returned by [`Mutex::lock()`].
+#[must_use = "futures do nothing unless you `.await` or poll them"]
+pub struct Lock<'a, T: ?Sized> {
+    mutex: &'a Mutex<T>,
+
+    /// Listening for the lock to be handed over, if the mutex was locked.
+    listener: Option<EventListener>,
+}
+
+impl<T: ?Sized> fmt::Debug for Lock<'_, T> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("Lock")
+            .field("queued", &self.listener.is_some())
+            .finish_non_exhaustive()
+    }
+}
+
+impl<'a, T: ?Sized> Future for Lock<'a, T> {
+    type Output = MutexGuard<'a, T>;
+
+    fn poll(mut self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Self::Output> {
+        let this = &mut *self;
+
+        let listener = match &mut this.listener {
+            Some(listener) => listener,
+            None => match this.mutex.acquire_or_listen() {
+                None => return Poll::Ready(MutexGuard { mutex: this.mutex }),
+                Some(listener) => this.listener.insert(listener),
+            },
+        };
+
+        if Pin::new(listener).poll(cx).is_pending() {
+            return Poll::Pending;
+        }
+        this.listener = None;
+        Poll::Ready(MutexGuard { mutex: this.mutex })
+    }
+}
+
+impl<T: ?Sized> Drop for Lock<'_, T> {
+    fn drop(&mut self) {
+        if let Some(listener) = self.listener.take() {
+            // Hold the state, so that the lock cannot be handed to us while we leave the queue.
+            let mut state = self.mutex.state();
+            if listener.discard() {
+                // We were handed the lock, pass it on.
+                self.mutex.release(&mut state);
+            } else {
+                state.waiters -= 1;
+            }
+        }
+    }
+}
+
+/// A guard that unlocks a [`Mutex`] when dropped.
+pub struct MutexGuard<'a, T: ?Sized> {
+    mutex: &'a Mutex<T>,
+}
+
+unsafe impl<T: ?Sized + Send> Send for MutexGuard<'_, T> {}
+unsafe impl<T: ?Sized + Sync> Sync for MutexGuard<'_, T> {}
+
+impl<T: ?Sized + fmt::Debug> fmt::Debug for MutexGuard<'_, T> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        fmt::Debug::fmt(&**self, f)
+    }
+}
+
+impl<T: ?Sized> Deref for MutexGuard<'_, T> {
+    type Target = T;
+
+    fn deref(&self) -> &T {
+        unsafe { &*self.mutex.value.get() }
+    }
+}
+
+impl<T: ?Sized> DerefMut for MutexGuard<'_, T> {
+    fn deref_mut(&mut self) -> &mut T {
+        unsafe { &mut *self.mutex.value.get() }
+    }
+}
+
+impl<T: ?Sized> Drop for MutexGuard<'_, T> {
+    fn drop(&mut self) {
+        self.mutex.release(&mut self.mutex.state());
+    }
+}
+
+#[cfg(not(target_family = "wasm"))]
+impl<'a, T: ?Sized + 'a> Lockable<'a> for Mutex<T> {
+    type Guard = MutexGuard<'a, T>;
+
+    fn lock(&'a self) -> Self::Guard {
+        self.blocking_lock()
+    }
+}
+
+impl<'a, T: ?Sized + 'a> AsyncLockable<'a> for Mutex<T> {
+    type Guard = MutexGuard<'a, T>;
+    type Lock = Lock<'a, T>;
+
+    fn lock_async(&'a self) -> Self::Lock {
+        Mutex::lock(self)
+    }
+}
+
+/// Readers are queued in this class.
+const READERS: ListenerClass = ListenerClass::A;
+
+/// Writers are queued in this class.
+const WRITERS: ListenerClass = ListenerClass::B;
+
+/// The state of an [`RwLock`].
+#[derive(Debug)]
+struct RwLockState {
+    /// The number of readers holding the lock, or handed it without having woken up yet.
+    readers: usize,
+
+    /// Whether a writer holds the lock, or has been handed it without having woken up yet.
+    writer: bool,
+
+    /// The number of queued readers that have not been handed the lock yet.
+    waiting_readers: usize,
+
+    /// The number of queued writers that have not been handed the lock yet.
+    waiting_writers: usize,
+
+    /// Whether queued writers go before queued and new readers.
+    prefer_writers: bool,
+}
+
+/// A fair read-write lock for async tasks and threads.
+///
+/// By default the lock prefers writers: once a writer is waiting, new readers queue up behind
+/// it, so a steady stream of readers cannot starve writers. A lock created with
+/// [`RwLock::with_writer_preference()`] and `false` prefers readers instead: readers never wait
+/// for queued writers, and all queued readers are let in before the next writer.
+///
+/// See the [module documentation](self) for more information.
+///
+/// # Examples
+///
+/// ```
+/// use event_listener::locks::RwLock;
+///
+/// let lock = RwLock::new(5);
+///
+/// # futures_lite::future::block_on(async {
+/// let r1 = lock.read().await;
+/// let r2 = lock.read().await;
+/// assert_eq!(*r1 + *r2, 10);
+/// drop((r1, r2));
+///
+/// *lock.write().await += 1;
+/// assert_eq!(*lock.read().await, 6);
+/// # });
+/// ```
+pub struct RwLock<T: ?Sized> {
+    /// The lock state.
+    state: StdMutex<RwLockState>,
+
+    /// Readers and writers are notified when the lock is handed to them.
+    event: Event,
+
+    /// The protected value.
+    value: UnsafeCell<T>,
+}
+
+unsafe impl<T: ?Sized + Send> Send for RwLock<T> {}
+unsafe impl<T: ?Sized + Send + Sync> Sync for RwLock<T> {}
+
+impl<T: ?Sized + fmt::Debug> fmt::Debug for RwLock<T> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let mut s = f.debug_struct("RwLock");
+        match self.try_read() {
+            Some(guard) => s.field("value", &&*guard),
+            None => s.field("value", &format_args!("<locked>")),
+        };
+        s.finish()
+    }
+}
+
+impl<T: Default> Default for RwLock<T> {
+    fn default() -> Self {
+        Self::new(T::default())
+    }
+}
+
+impl<T> RwLock<T> {
+    /// Creates a new read-write lock holding `value`, which prefers writers.
+    pub fn new(value: T) -> Self {
+        Self::with_writer_preference(value, true)
+    }
+
+    /// Creates a new read-write lock holding `value`, which prefers writers if
+    /// `prefer_writers` is `true`, and readers otherwise.
+    pub fn with_writer_preference(value: T, prefer_writers: bool) -> Self {
+        Self {
+            state: StdMutex::new(RwLockState {
+                readers: 0,
+                writer: false,
+                waiting_readers: 0,
+                waiting_writers: 0,
+                prefer_writers,
+            }),
+            event: Event::new(),
+            value: UnsafeCell::new(value),
+        }
+    }
+
+    /// Returns the value held by the lock.
+    pub fn into_inner(self) -> T {
+        self.value.into_inner()
+    }
+}
+
+impl<T: ?Sized> RwLock<T> {
+    /// Locks the lock for reading.
+    ///
+    /// The returned future is cancel-safe, like the one returned by [`Mutex::lock()`].
+    pub fn read(&self) -> Read<'_, T> {
+        Read {
+            lock: self,
+            listener: None,
+        }
+    }
+
+    /// Locks the lock for writing.
+    ///
+    /// The returned future is cancel-safe, like the one returned by [`Mutex::lock()`].
+    pub fn write(&self) -> Write<'_, T> {
+        Write {
+            lock: self,
+            listener: None,
+        }
+    }
+
+    /// Blocks until the lock is locked for reading.
+    #[cfg(not(target_family = "wasm"))]
+    pub fn blocking_read(&self) -> RwLockReadGuard<'_, T> {
+        if let Some(listener) = self.read_or_listen() {
+            listener.wait();
+        }
+        RwLockReadGuard { lock: self }
+    }
+
+    /// Blocks until the lock is locked for writing.
+    #[cfg(not(target_family = "wasm"))]
+    pub fn blocking_write(&self) -> RwLockWriteGuard<'_, T> {
+        if let Some(listener) = self.write_or_listen() {
+            listener.wait();
+        }
+        RwLockWriteGuard { lock: self }
+    }
+
+    /// Locks the lock for reading, unless that would have to wait.
+    pub fn try_read(&self) -> Option<RwLockReadGuard<'_, T>> {
+        let mut state = self.state();
+        if !Self::can_read(&state) {
+            return None;
+        }
+
+        state.readers += 1;
+        Some(RwLockReadGuard { lock: self })
+    }
+
+    /// Locks the lock for writing, unless that would have to wait.
+    pub fn try_write(&self) -> Option<RwLockWriteGuard<'_, T>> {
+        let mut state = self.state();
+        if !Self::can_write(&state) {
+            return None;
+        }
+
+        state.writer = true;
+        Some(RwLockWriteGuard { lock: self })
+    }
+
+    /// Returns a mutable reference to the value.
+    ///
+    /// No locking is needed, since the lock is borrowed mutably.
+    pub fn get_mut(&mut self) -> &mut T {
+        self.value.get_mut()
+    }
+
+    /// Whether a new reader can take the lock right away.
+    fn can_read(state: &RwLockState) -> bool {
+        !state.writer && (!state.prefer_writers || state.waiting_writers == 0)
+    }
+
+    /// Whether a new writer can take the lock right away.
+    fn can_write(state: &RwLockState) -> bool {
+        !state.writer
+            && state.readers == 0
+            && state.waiting_readers == 0
+            && state.waiting_writers == 0
+    }
+
+    /// Locks the lock for reading if possible, or queues up for it.
+    fn read_or_listen(&self) -> Option<EventListener> {
+        let mut state = self.state();
+        if Self::can_read(&state) {
+            state.readers += 1;
+            return None;
+        }
+
+        state.waiting_readers += 1;
+        Some(self.event.listen_class(READERS))
+    }
+
+    /// Locks the lock for writing if possible, or queues up for it.
+    fn write_or_listen(&self) -> Option<EventListener> {
+        let mut state = self.state();
+        if Self::can_write(&state) {
+            state.writer = true;
+            return None;
+        }
+
+        state.waiting_writers += 1;
+        Some(self.event.listen_class(WRITERS))
+    }
+
+    /// A reader unlocks the lock.
+    fn release_read(&self, state: &mut RwLockState) {
+        state.readers -= 1;
+        self.admit(state);
+    }
+
+    /// A writer unlocks the lock.
+    fn release_write(&self, state: &mut RwLockState) {
+        state.writer = false;
+        self.admit(state);
+    }
+
+    /// Hands the lock to queued waiters, if they can have it now.
+    fn admit(&self, state: &mut RwLockState) {
+        if state.writer {
+            return;
+        }
+
+        let writers_first = state.prefer_writers || state.waiting_readers == 0;
+        if writers_first && state.waiting_writers > 0 {
+            // The writer waits for the remaining readers to leave.
+            if state.readers == 0 {
+                state.writer = true;
+                state.waiting_writers -= 1;
+                self.event.notify_class(WRITERS, 1);
+            }
+        } else if state.waiting_readers > 0 {
+            state.readers += state.waiting_readers;
+            self.event.notify_class(READERS, state.waiting_readers);
+            state.waiting_readers = 0;
+        }
+    }
+
+    /// Lock the state of the lock.
+    fn state(&self) -> StdMutexGuard<'_, RwLockState> {
+        self.state.lock().unwrap_or_else(|e| e.into_inner())
+    }
+}
+
+/// The future returned by [`RwLock::read()`].
+#[must_use = "futures do nothing unless you `.await` or poll them"]
+pub struct Read<'a, T: ?Sized> {
+    lock: &'a RwLock<T>,
+
+    /// Listening for the lock to be handed over, if the reader had to queue.
+    listener: Option<EventListener>,
+}
+
+impl<T: ?Sized> fmt::Debug for Read<'_, T> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("Read")
+            .field("queued", &self.listener.is_some())
+            .finish_non_exhaustive()
+    }
+}
+
+impl<'a, T: ?Sized> Future for Read<'a, T> {
+    type Output = RwLockReadGuard<'a, T>;
+
+    fn poll(mut self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Self::Output> {
+        let this = &mut *self;
+
+        let listener = match &mut this.listener {
+            Some(listener) => listener,
+            None => match this.lock.read_or_listen() {
+                None => return Poll::Ready(RwLockReadGuard { lock: this.lock }),
+                Some(listener) => this.listener.insert(listener),
+            },
+        };
+
+        if Pin::new(listener).poll(cx).is_pending() {
+            return Poll::Pending;
+        }
+        this.listener = None;
+        Poll::Ready(RwLockReadGuard { lock: this.lock })
+    }
+}
+
+impl<T: ?Sized> Drop for Read<'_, T> {
+    fn drop(&mut self) {
+        if let Some(listener) = self.listener.take() {
+            // Hold the state, so that the lock cannot be handed to us while we leave the queue.
+            let mut state = self.lock.state();
+            if listener.discard() {
+                self.lock.release_read(&mut state);
+            } else {
+                state.waiting_readers -= 1;
+            }
+        }
+    }
+}
+
+/// The future returned by [`RwLock::write()`].
+#[must_use = "futures do nothing unless you `.await` or poll them"]
+pub struct Write<'a, T: ?Sized> {
+    lock: &'a RwLock<T>,
+
+    /// Listening for the lock to be handed over, if the writer had to queue.
+    listener: Option<EventListener>,
+}
+
+impl<T: ?Sized> fmt::Debug for Write<'_, T> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("Write")
+            .field("queued", &self.listener.is_some())
+            .finish_non_exhaustive()
+    }
+}
+
+impl<'a, T: ?Sized> Future for Write<'a, T> {
+    type Output = RwLockWriteGuard<'a, T>;
+
+    fn poll(mut self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Self::Output> {
+        let this = &mut *self;
+
+        let listener = match &mut this.listener {
+            Some(listener) => listener,
+            None => match this.lock.write_or_listen() {
+                None => return Poll::Ready(RwLockWriteGuard { lock: this.lock }),
+                Some(listener) => this.listener.insert(listener),
+            },
+        };
+
+        if Pin::new(listener).poll(cx).is_pending() {
+            return Poll::Pending;
+        }
+        this.listener = None;
+        Poll::Ready(RwLockWriteGuard { lock: this.lock })
+    }
+}
+
+impl<T: ?Sized> Drop for Write<'_, T> {
+    fn drop(&mut self) {
+        if let Some(listener) = self.listener.take() {
+            // Hold the state, so that the lock cannot be handed to us while we leave the queue.
+            let mut state = self.lock.state();
+            if listener.discard() {
+                self.lock.release_write(&mut state);
+            } else {
+                // Readers that queued up behind this writer may go in now.
+                state.waiting_writers -= 1;
+                self.lock.admit(&mut state);
+            }
+        }
+    }
+}
+
+/// A guard that releases the read lock of an [`RwLock`] when dropped.
+pub struct RwLockReadGuard<'a, T: ?Sized> {
+    lock: &'a RwLock<T>,
+}
+
+unsafe impl<T: ?Sized + Sync> Send for RwLockReadGuard<'_, T> {}
+unsafe impl<T: ?Sized + Sync> Sync for RwLockReadGuard<'_, T> {}
+
+impl<T: ?Sized + fmt::Debug> fmt::Debug for RwLockReadGuard<'_, T> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        fmt::Debug::fmt(&**self, f)
+    }
+}
+
+impl<T: ?Sized> Deref for RwLockReadGuard<'_, T> {
+    type Target = T;
+
+    fn deref(&self) -> &T {
+        unsafe { &*self.lock.value.get() }
+    }
+}
+
+impl<T: ?Sized> Drop for RwLockReadGuard<'_, T> {
+    fn drop(&mut self) {
+        self.lock.release_read(&mut self.lock.state());
+    }
+}
+
+/// A guard that releases the write lock of an [`RwLock`] when dropped.
+pub struct RwLockWriteGuard<'a, T: ?Sized> {
+    lock: &'a RwLock<T>,
+}
+
+unsafe impl<T: ?Sized + Send> Send for RwLockWriteGuard<'_, T> {}
+unsafe impl<T: ?Sized + Sync> Sync for RwLockWriteGuard<'_, T> {}
+
+impl<T: ?Sized + fmt::Debug> fmt::Debug for RwLockWriteGuard<'_, T> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        fmt::Debug::fmt(&**self, f)
+    }
+}
+
+impl<T: ?Sized> Deref for RwLockWriteGuard<'_, T> {
+    type Target = T;
+
+    fn deref(&self) -> &T {
+        unsafe { &*self.lock.value.get() }
+    }
+}
+
+impl<T: ?Sized> DerefMut for RwLockWriteGuard<'_, T> {
+    fn deref_mut(&mut self) -> &mut T {
+        unsafe { &mut *self.lock.value.get() }
+    }
+}
+
+impl<T: ?Sized> Drop for RwLockWriteGuard<'_, T> {
+    fn drop(&mut self) {
+        self.lock.release_write(&mut self.lock.state());
+    }
+}
@@ -12,9 +12,9 @@
 #[path = "no_std/node.rs"]
 mod node;
 
-use node::{Node, NothingProducer, TaskWaiting};
+use node::{Node, TaskWaiting};
 
-use crate::notify::{GenericNotify, Internal, Notification};
+use crate::notify::{GenericNotify, Internal, NothingProducer, Notification};
 use crate::sync::atomic::{AtomicBool, Ordering};
 use crate::sync::cell::{Cell, ConstPtr, UnsafeCell};
 use crate::sync::Arc;
@@ -39,6 +39,35 @@ impl<T> crate::Inner<T> {
         })
     }
 
+    /// Delivers the notifications in the mailbox, unless the list is locked by someone else, who
+    /// delivers them instead.
+    ///
+    /// This is called from interrupt handlers, so it does not allocate. Nodes that other threads
+    /// queued are left to the next thread that locks the list, since processing them allocates.
+    pub(crate) fn deliver_mailbox(&self) {
+        while let Some(mut list) = self.list.inner.try_lock() {
+            if let Some(notifications) = self.mailbox.take() {
+                for notify in notifications {
+                    list.notify(notify);
+                }
+            }
+
+            // Update the atomic `notified` counter.
+            let notified = if list.notified < list.len {
+                list.notified
+            } else {
+                usize::MAX
+            };
+            self.notified.store(notified, Ordering::Release);
+            drop(list);
+
+            // A notification may have been left in the mailbox while we held the lock.
+            if self.mailbox.is_empty() {
+                break;
+            }
+        }
+    }
+
     /// Force a queue update.
     fn queue_update(&self) {
         // Locking and unlocking the mutex will drain the queue if there is no contention.
@@ -153,6 +182,12 @@ impl<T> crate::Inner<T> {
         }
     }
 
+    /// Notifies all unnotified entries.
+    #[cold]
+    pub(crate) fn notify_all(&self, additional: bool, tags: impl FnMut() -> T) -> usize {
+        self.notify(GenericNotify::new(usize::MAX, additional, tags))
+    }
+
     /// Register a task to be notified when the event is triggered.
     ///
     /// Returns `true` if the listener was already notified, and `false` otherwise. If the listener
@@ -297,6 +332,14 @@ impl<T> Drop for ListGuard<'_, T> {
             // Process every node left in the queue.
             self.process_nodes();
 
+            // Deliver the notifications that interrupt handlers left while the list was locked.
+            if let Some(notifications) = self.inner.mailbox.take() {
+                let list = self.guard.as_mut().unwrap();
+                for notify in notifications {
+                    list.notify(notify);
+                }
+            }
+
             // Update the atomic `notified` counter.
             let list = self.guard.take().unwrap();
             let notified = if list.notified < list.len {
@@ -320,7 +363,7 @@ impl<T> Drop for ListGuard<'_, T> {
             // notifications not being properly delivered, or listeners not being added to the list.
             // Therefore check before we finish dropping if there is anything left in the queue, and
             // if so, lock it again and force a queue update.
-            if !self.inner.list.queue.is_empty() {
+            if !self.inner.list.queue.is_empty() || !self.inner.mailbox.is_empty() {
                 self.guard = self.inner.list.inner.try_lock();
             }
         }
@@ -530,7 +573,7 @@ impl<T> ListenerSlab<T> {
         };
 
         // Replace the tail with the new entry.
-        match mem::replace(&mut self.tail, Some(key)) {
+        match self.tail.replace(key) {
             None => self.head = Some(key),
             Some(tail) => {
                 let tail = &self.listeners[tail.get()];
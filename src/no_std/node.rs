@@ -2,7 +2,7 @@
 
 //! The node that makes up queues.
 
-use crate::notify::{GenericNotify, Internal, NotificationPrivate, TagProducer};
+use crate::notify::{GenericNotify, Internal, NothingProducer, NotificationPrivate};
 use crate::sync::atomic::{AtomicPtr, AtomicUsize, Ordering};
 use crate::sync::Arc;
 use crate::sys::ListenerSlab;
@@ -11,38 +11,9 @@ use crate::{State, Task};
 use alloc::boxed::Box;
 
 use core::fmt;
-use core::marker::PhantomData;
-use core::mem;
 use core::num::NonZeroUsize;
 use core::ptr;
 
-pub(crate) struct NothingProducer<T>(PhantomData<T>);
-
-impl<T> Default for NothingProducer<T> {
-    fn default() -> Self {
-        Self(PhantomData)
-    }
-}
-
-impl<T> fmt::Debug for NothingProducer<T> {
-    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
-        f.debug_struct("NothingProducer").finish()
-    }
-}
-
-impl<T> TagProducer for NothingProducer<T> {
-    type Tag = T;
-
-    fn next_tag(&mut self) -> Self::Tag {
-        // This has to be a zero-sized type with no drop handler.
-        assert_eq!(mem::size_of::<Self::Tag>(), 0);
-        assert!(!mem::needs_drop::<Self::Tag>());
-
-        // SAFETY: As this is a ZST without a drop handler, zero is valid.
-        unsafe { mem::zeroed() }
-    }
-}
-
 /// A node in the backup queue.
 pub(crate) enum Node<T> {
     /// This node is requesting to add a listener.
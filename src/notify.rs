@@ -1,8 +1,13 @@
 //! The `Notification` trait for specifying notification.
 
-use crate::sync::atomic::{self, Ordering};
-#[cfg(feature = "std")]
+use crate::fence::full_fence;
 use core::fmt;
+#[cfg(feature = "std")]
+use core::iter::Chain;
+use core::marker::PhantomData;
+use core::mem;
+#[cfg(feature = "std")]
+use std::vec::{self, Vec};
 
 pub(crate) use __private::Internal;
 
@@ -25,10 +30,49 @@ pub trait NotificationPrivate {
     /// Get the number of listeners to wake.
     fn count(&self, internal: Internal) -> usize;
 
+    /// Get the number of listeners to wake, given the state of the event.
+    #[cfg(feature = "std")]
+    fn count_in(&self, cx: &NotifyContext, internal: Internal) -> usize {
+        let _ = cx;
+        self.count(internal)
+    }
+
     /// Get a tag to be associated with a notification.
     ///
     /// This method is expected to be called `count()` times.
     fn next_tag(&mut self, internal: Internal) -> Self::Tag;
+
+    /// Get a tag to be associated with a notification, or `None` if there are no tags left.
+    #[cfg(feature = "std")]
+    fn try_next_tag(&mut self, internal: Internal) -> Option<Self::Tag> {
+        Some(self.next_tag(internal))
+    }
+
+    /// Give back a tag that no listener accepted.
+    ///
+    /// Returns the tag if the notification does not keep it.
+    #[cfg(feature = "std")]
+    fn reject_tag(&mut self, tag: Self::Tag, internal: Internal) -> Option<Self::Tag> {
+        let _ = internal;
+        Some(tag)
+    }
+
+    /// Whether the tags may differ from each other, so a rejected tag does not mean that the
+    /// next one is rejected too.
+    #[cfg(feature = "std")]
+    fn tags_vary(&self, internal: Internal) -> bool {
+        let _ = internal;
+        false
+    }
+
+    /// Get the tag that the notification holds on to, if it reached no listener.
+    ///
+    /// Notifications that only produce tags on demand have none.
+    #[cfg(feature = "std")]
+    fn unused_tag(&mut self, internal: Internal) -> Option<Self::Tag> {
+        let _ = internal;
+        None
+    }
 }
 
 /// A notification that can be used to notify an [`Event`].
@@ -60,15 +104,36 @@ pub trait Notification: NotificationPrivate {}
 impl<N: NotificationPrivate + ?Sized> Notification for N {}
 
 /// Notify a given number of unnotifed listeners.
+///
+/// This is the notification that numeric literals are converted into.
+///
+/// # Examples
+///
+/// ```
+/// use event_listener::{Event, Notify};
+///
+/// let event = Event::new();
+/// let _listener = event.listen();
+///
+/// let notify = Notify::new(2);
+/// assert_eq!(notify.count(), 2);
+/// assert_eq!(event.notify(notify), 1);
+/// ```
 #[derive(Debug, Clone)]
-#[doc(hidden)]
 pub struct Notify(usize);
 
 impl Notify {
     /// Create a new `Notify` with the given number of listeners to notify.
-    fn new(count: usize) -> Self {
+    #[inline]
+    pub const fn new(count: usize) -> Self {
         Self(count)
     }
+
+    /// The number of listeners to notify.
+    #[inline]
+    pub const fn count(&self) -> usize {
+        self.0
+    }
 }
 
 impl NotificationPrivate for Notify {
@@ -89,16 +154,155 @@ impl NotificationPrivate for Notify {
     fn next_tag(&mut self, _: Internal) -> Self::Tag {}
 }
 
+/// The state of an [`Event`] at the time a notification is delivered.
+///
+/// This is passed to the function of a [`NotifyWith`] notification.
+///
+/// [`Event`]: crate::Event
+#[cfg(feature = "std")]
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct NotifyContext {
+    /// The number of listeners that have not been notified yet.
+    waiting: usize,
+
+    /// The number of listeners that have been notified, but did not receive their notification.
+    notified: usize,
+}
+
+#[cfg(feature = "std")]
+impl NotifyContext {
+    /// Create a new context.
+    pub(crate) fn new(waiting: usize, notified: usize) -> Self {
+        Self { waiting, notified }
+    }
+
+    /// The number of listeners that have not been notified yet.
+    #[inline]
+    pub fn waiting(&self) -> usize {
+        self.waiting
+    }
+
+    /// The number of listeners that have been notified, but did not receive their notification
+    /// yet.
+    #[inline]
+    pub fn notified(&self) -> usize {
+        self.notified
+    }
+
+    /// The number of listeners registered with the event.
+    #[inline]
+    pub fn registered(&self) -> usize {
+        self.waiting + self.notified
+    }
+}
+
+/// Compute the number of listeners to notify from the state of the event.
+///
+/// The function is called with the event locked, right before the listeners are notified. Its
+/// result is used like any other count, so it is reduced by the number of notified listeners
+/// unless the notification is made [`additional`](IntoNotification::additional).
+///
+/// # Examples
+///
+/// ```
+/// use event_listener::{Event, IntoNotification, NotifyWith};
+///
+/// let event = Event::new();
+/// let _listeners: Vec<_> = (0..4).map(|_| event.listen()).collect();
+///
+/// // Wake up half of the listeners that are still waiting.
+/// let half = NotifyWith::new(|cx| cx.waiting() / 2).additional();
+/// assert_eq!(event.notify(half.clone()), 2);
+/// assert_eq!(event.notify(half), 1);
+/// ```
+#[cfg(feature = "std")]
+#[derive(Clone, Copy)]
+pub struct NotifyWith<F>(F);
+
+#[cfg(feature = "std")]
+impl<F> fmt::Debug for NotifyWith<F> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("NotifyWith").finish_non_exhaustive()
+    }
+}
+
+#[cfg(feature = "std")]
+impl<F> NotifyWith<F>
+where
+    F: Fn(&NotifyContext) -> usize,
+{
+    /// Create a new `NotifyWith` with the given function.
+    #[inline]
+    pub fn new(count: F) -> Self {
+        Self(count)
+    }
+}
+
+#[cfg(feature = "std")]
+impl<F> NotificationPrivate for NotifyWith<F>
+where
+    F: Fn(&NotifyContext) -> usize,
+{
+    type Tag = ();
+
+    fn is_additional(&self, _: Internal) -> bool {
+        false
+    }
+
+    fn fence(&self, _: Internal) {
+        full_fence();
+    }
+
+    fn count(&self, _: Internal) -> usize {
+        (self.0)(&NotifyContext::default())
+    }
+
+    fn count_in(&self, cx: &NotifyContext, _: Internal) -> usize {
+        (self.0)(cx)
+    }
+
+    fn next_tag(&mut self, _: Internal) -> Self::Tag {}
+}
+
 /// Make the underlying notification additional.
+///
+/// This is returned by [`IntoNotification::additional()`].
+///
+/// # Examples
+///
+/// ```
+/// use event_listener::{Additional, Event, Notify};
+///
+/// let event = Event::new();
+/// let _l1 = event.listen();
+/// let _l2 = event.listen();
+///
+/// event.notify(1);
+/// assert_eq!(event.notify(Additional::new(Notify::new(1))), 1);
+/// ```
 #[derive(Debug, Clone)]
-#[doc(hidden)]
 pub struct Additional<N: ?Sized>(N);
 
 impl<N> Additional<N> {
     /// Create a new `Additional` with the given notification.
-    fn new(inner: N) -> Self {
+    #[inline]
+    pub const fn new(inner: N) -> Self {
         Self(inner)
     }
+
+    /// Returns the underlying notification.
+    #[inline]
+    pub fn into_inner(self) -> N {
+        self.0
+    }
+}
+
+impl<N: ?Sized> Additional<N> {
+    /// Returns a reference to the underlying notification.
+    #[inline]
+    pub fn inner(&self) -> &N {
+        &self.0
+    }
 }
 
 impl<N> NotificationPrivate for Additional<N>
@@ -119,21 +323,62 @@ where
         self.0.count(i)
     }
 
+    #[cfg(feature = "std")]
+    fn count_in(&self, cx: &NotifyContext, i: Internal) -> usize {
+        self.0.count_in(cx, i)
+    }
+
     fn next_tag(&mut self, i: Internal) -> Self::Tag {
         self.0.next_tag(i)
     }
+
+    #[cfg(feature = "std")]
+    fn try_next_tag(&mut self, i: Internal) -> Option<Self::Tag> {
+        self.0.try_next_tag(i)
+    }
+
+    #[cfg(feature = "std")]
+    fn reject_tag(&mut self, tag: Self::Tag, i: Internal) -> Option<Self::Tag> {
+        self.0.reject_tag(tag, i)
+    }
+
+    #[cfg(feature = "std")]
+    fn tags_vary(&self, i: Internal) -> bool {
+        self.0.tags_vary(i)
+    }
+
+    #[cfg(feature = "std")]
+    fn unused_tag(&mut self, i: Internal) -> Option<Self::Tag> {
+        self.0.unused_tag(i)
+    }
 }
 
 /// Don't emit a fence for this notification.
+///
+/// This is returned by [`IntoNotification::relaxed()`].
 #[derive(Debug, Clone)]
-#[doc(hidden)]
 pub struct Relaxed<N: ?Sized>(N);
 
 impl<N> Relaxed<N> {
     /// Create a new `Relaxed` with the given notification.
-    fn new(inner: N) -> Self {
+    #[inline]
+    pub const fn new(inner: N) -> Self {
         Self(inner)
     }
+
+    /// Returns the underlying notification.
+    #[inline]
+    pub fn into_inner(self) -> N {
+        self.0
+    }
+}
+
+impl<N: ?Sized> Relaxed<N> {
+    /// Returns a reference to the underlying notification.
+    #[inline]
+    pub fn inner(&self) -> &N {
+        &self.0
+    }
 }
 
 impl<N> NotificationPrivate for Relaxed<N>
@@ -154,15 +399,56 @@ where
         self.0.count(i)
     }
 
+    #[cfg(feature = "std")]
+    fn count_in(&self, cx: &NotifyContext, i: Internal) -> usize {
+        self.0.count_in(cx, i)
+    }
+
     fn next_tag(&mut self, i: Internal) -> Self::Tag {
         self.0.next_tag(i)
     }
+
+    #[cfg(feature = "std")]
+    fn try_next_tag(&mut self, i: Internal) -> Option<Self::Tag> {
+        self.0.try_next_tag(i)
+    }
+
+    #[cfg(feature = "std")]
+    fn reject_tag(&mut self, tag: Self::Tag, i: Internal) -> Option<Self::Tag> {
+        self.0.reject_tag(tag, i)
+    }
+
+    #[cfg(feature = "std")]
+    fn tags_vary(&self, i: Internal) -> bool {
+        self.0.tags_vary(i)
+    }
+
+    #[cfg(feature = "std")]
+    fn unused_tag(&mut self, i: Internal) -> Option<Self::Tag> {
+        self.0.unused_tag(i)
+    }
 }
 
 /// Use a tag to notify listeners.
+///
+/// This is returned by [`IntoNotification::tag()`].
+///
+/// # Examples
+///
+/// ```
+/// use event_listener::{Event, Listener, Notify, Tag};
+///
+/// let event = Event::with_tag();
+/// let listener = event.listen();
+///
+/// let notify = Tag::new('x', Notify::new(1));
+/// assert_eq!(*notify.tag(), 'x');
+///
+/// event.notify(notify);
+/// assert_eq!(listener.wait(), 'x');
+/// ```
 #[cfg(feature = "std")]
 #[derive(Debug, Clone)]
-#[doc(hidden)]
 pub struct Tag<N: ?Sized, T> {
     tag: T,
     inner: N,
@@ -171,12 +457,34 @@ pub struct Tag<N: ?Sized, T> {
 #[cfg(feature = "std")]
 impl<N: ?Sized, T> Tag<N, T> {
     /// Create a new `Tag` with the given tag and notification.
-    fn new(tag: T, inner: N) -> Self
+    #[inline]
+    pub const fn new(tag: T, inner: N) -> Self
     where
         N: Sized,
     {
         Self { tag, inner }
     }
+
+    /// Returns a reference to the tag.
+    #[inline]
+    pub fn tag(&self) -> &T {
+        &self.tag
+    }
+
+    /// Returns a reference to the underlying notification.
+    #[inline]
+    pub fn inner(&self) -> &N {
+        &self.inner
+    }
+
+    /// Returns the tag and the underlying notification.
+    #[inline]
+    pub fn into_parts(self) -> (T, N)
+    where
+        N: Sized,
+    {
+        (self.tag, self.inner)
+    }
 }
 
 #[cfg(feature = "std")]
@@ -199,14 +507,119 @@ where
         self.inner.count(i)
     }
 
+    fn count_in(&self, cx: &NotifyContext, i: Internal) -> usize {
+        self.inner.count_in(cx, i)
+    }
+
     fn next_tag(&mut self, _: Internal) -> Self::Tag {
         self.tag.clone()
     }
+
+    fn unused_tag(&mut self, _: Internal) -> Option<Self::Tag> {
+        Some(self.tag.clone())
+    }
+}
+
+/// Use an iterator to generate tags to notify listeners.
+///
+/// This is returned by [`IntoNotification::tag_iter()`].
+#[cfg(feature = "std")]
+#[derive(Debug, Clone)]
+pub struct TagIter<N: ?Sized, I: Iterator> {
+    tags: I,
+    rejected: Vec<I::Item>,
+    inner: N,
+}
+
+#[cfg(feature = "std")]
+impl<N, I: Iterator> TagIter<N, I> {
+    /// Create a new `TagIter` with the given tag iterator and notification.
+    #[inline]
+    pub fn new(tags: I, inner: N) -> Self {
+        Self {
+            tags,
+            rejected: Vec::new(),
+            inner,
+        }
+    }
+
+    /// Returns the tags that were not taken from the iterator yet, and the underlying
+    /// notification.
+    ///
+    /// Tags that were taken but not accepted by any listener are dropped. Use
+    /// [`NotifyReport::into_undelivered()`] to get them back as well.
+    #[inline]
+    pub fn into_parts(self) -> (I, N) {
+        (self.tags, self.inner)
+    }
+}
+
+#[cfg(feature = "std")]
+impl<N: ?Sized, I: Iterator> TagIter<N, I> {
+    /// Never notify more listeners than there are tags.
+    fn cap(&self, count: usize) -> usize {
+        match self.tags.size_hint() {
+            (_, Some(upper)) => count.min(upper),
+            (_, None) => count,
+        }
+    }
+}
+
+#[cfg(feature = "std")]
+impl<N: ?Sized, I: Iterator> TagIter<N, I> {
+    /// Returns a reference to the underlying notification.
+    #[inline]
+    pub fn inner(&self) -> &N {
+        &self.inner
+    }
+}
+
+#[cfg(feature = "std")]
+impl<N, I> NotificationPrivate for TagIter<N, I>
+where
+    N: Notification + ?Sized,
+    I: Iterator,
+{
+    type Tag = I::Item;
+
+    fn is_additional(&self, i: Internal) -> bool {
+        self.inner.is_additional(i)
+    }
+
+    fn fence(&self, i: Internal) {
+        self.inner.fence(i);
+    }
+
+    fn count(&self, i: Internal) -> usize {
+        self.cap(self.inner.count(i))
+    }
+
+    fn count_in(&self, cx: &NotifyContext, i: Internal) -> usize {
+        self.cap(self.inner.count_in(cx, i))
+    }
+
+    fn next_tag(&mut self, _: Internal) -> Self::Tag {
+        self.tags.next().expect("tag iterator ran out of tags")
+    }
+
+    fn try_next_tag(&mut self, _: Internal) -> Option<Self::Tag> {
+        self.tags.next()
+    }
+
+    fn reject_tag(&mut self, tag: Self::Tag, _: Internal) -> Option<Self::Tag> {
+        self.rejected.push(tag);
+        None
+    }
+
+    fn tags_vary(&self, _: Internal) -> bool {
+        true
+    }
 }
 
 /// Use a function to generate a tag to notify listeners.
+///
+/// This is returned by [`IntoNotification::tag_with()`].
 #[cfg(feature = "std")]
-#[doc(hidden)]
 pub struct TagWith<N: ?Sized, F> {
     tag: F,
     inner: N,
@@ -232,10 +645,26 @@ impl<N: fmt::Debug, F> fmt::Debug for TagWith<N, F> {
 
 #[cfg(feature = "std")]
 impl<N, F> TagWith<N, F> {
-    /// Create a new `TagFn` with the given tag function and notification.
-    fn new(tag: F, inner: N) -> Self {
+    /// Create a new `TagWith` with the given tag function and notification.
+    #[inline]
+    pub const fn new(tag: F, inner: N) -> Self {
         Self { tag, inner }
     }
+
+    /// Returns the tag function and the underlying notification.
+    #[inline]
+    pub fn into_parts(self) -> (F, N) {
+        (self.tag, self.inner)
+    }
+}
+
+#[cfg(feature = "std")]
+impl<N: ?Sized, F> TagWith<N, F> {
+    /// Returns a reference to the underlying notification.
+    #[inline]
+    pub fn inner(&self) -> &N {
+        &self.inner
+    }
 }
 
 #[cfg(feature = "std")]
@@ -258,9 +687,257 @@ where
         self.inner.count(i)
     }
 
+    fn count_in(&self, cx: &NotifyContext, i: Internal) -> usize {
+        self.inner.count_in(cx, i)
+    }
+
     fn next_tag(&mut self, _: Internal) -> Self::Tag {
         (self.tag)()
     }
+
+    fn tags_vary(&self, _: Internal) -> bool {
+        true
+    }
+}
+
+/// Transform the tags of a notification with a function.
+///
+/// This is returned by [`IntoNotification::map_tag()`].
+#[cfg(feature = "std")]
+pub struct MapTag<N: ?Sized, F> {
+    map: F,
+    inner: N,
+}
+
+#[cfg(feature = "std")]
+impl<N: fmt::Debug, F> fmt::Debug for MapTag<N, F> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("MapTag")
+            .field("inner", &self.inner)
+            .finish_non_exhaustive()
+    }
+}
+
+#[cfg(feature = "std")]
+impl<N: Clone, F: Clone> Clone for MapTag<N, F> {
+    fn clone(&self) -> Self {
+        Self {
+            map: self.map.clone(),
+            inner: self.inner.clone(),
+        }
+    }
+}
+
+#[cfg(feature = "std")]
+impl<N, F> MapTag<N, F> {
+    /// Create a new `MapTag` with the given function and notification.
+    #[inline]
+    pub const fn new(map: F, inner: N) -> Self {
+        Self { map, inner }
+    }
+
+    /// Returns the function and the underlying notification.
+    #[inline]
+    pub fn into_parts(self) -> (F, N) {
+        (self.map, self.inner)
+    }
+}
+
+#[cfg(feature = "std")]
+impl<N: ?Sized, F> MapTag<N, F> {
+    /// Returns a reference to the underlying notification.
+    #[inline]
+    pub fn inner(&self) -> &N {
+        &self.inner
+    }
+}
+
+#[cfg(feature = "std")]
+impl<N, F, U> NotificationPrivate for MapTag<N, F>
+where
+    N: Notification + ?Sized,
+    F: FnMut(N::Tag) -> U,
+{
+    type Tag = U;
+
+    fn is_additional(&self, i: Internal) -> bool {
+        self.inner.is_additional(i)
+    }
+
+    fn fence(&self, i: Internal) {
+        self.inner.fence(i);
+    }
+
+    fn count(&self, i: Internal) -> usize {
+        self.inner.count(i)
+    }
+
+    fn count_in(&self, cx: &NotifyContext, i: Internal) -> usize {
+        self.inner.count_in(cx, i)
+    }
+
+    fn next_tag(&mut self, i: Internal) -> Self::Tag {
+        (self.map)(self.inner.next_tag(i))
+    }
+
+    fn try_next_tag(&mut self, i: Internal) -> Option<Self::Tag> {
+        self.inner.try_next_tag(i).map(&mut self.map)
+    }
+
+    fn tags_vary(&self, i: Internal) -> bool {
+        self.inner.tags_vary(i)
+    }
+
+    fn unused_tag(&mut self, i: Internal) -> Option<Self::Tag> {
+        self.inner.unused_tag(i).map(&mut self.map)
+    }
+}
+
+impl<N> NotificationPrivate for &mut N
+where
+    N: Notification + ?Sized,
+{
+    type Tag = N::Tag;
+
+    fn is_additional(&self, i: Internal) -> bool {
+        (**self).is_additional(i)
+    }
+
+    fn fence(&self, i: Internal) {
+        (**self).fence(i);
+    }
+
+    fn count(&self, i: Internal) -> usize {
+        (**self).count(i)
+    }
+
+    #[cfg(feature = "std")]
+    fn count_in(&self, cx: &NotifyContext, i: Internal) -> usize {
+        (**self).count_in(cx, i)
+    }
+
+    fn next_tag(&mut self, i: Internal) -> Self::Tag {
+        (**self).next_tag(i)
+    }
+
+    #[cfg(feature = "std")]
+    fn try_next_tag(&mut self, i: Internal) -> Option<Self::Tag> {
+        (**self).try_next_tag(i)
+    }
+
+    #[cfg(feature = "std")]
+    fn reject_tag(&mut self, tag: Self::Tag, i: Internal) -> Option<Self::Tag> {
+        (**self).reject_tag(tag, i)
+    }
+
+    #[cfg(feature = "std")]
+    fn tags_vary(&self, i: Internal) -> bool {
+        (**self).tags_vary(i)
+    }
+
+    #[cfg(feature = "std")]
+    fn unused_tag(&mut self, i: Internal) -> Option<Self::Tag> {
+        (**self).unused_tag(i)
+    }
+}
+
+/// The outcome of a call to [`Event::notify_report()`].
+///
+/// Besides the number of listeners that were notified, this reports how much of the
+/// notification went unused and gives the notification back, so that the tags that were not
+/// delivered can be retried or requeued.
+///
+/// [`Event::notify_report()`]: crate::Event::notify_report
+#[cfg(feature = "std")]
+#[derive(Debug)]
+#[must_use = "the report contains the tags that were not delivered"]
+pub struct NotifyReport<N> {
+    /// The number of listeners that were notified.
+    notified: usize,
+
+    /// The number of units that were not used.
+    undelivered: usize,
+
+    /// The notification itself.
+    notification: N,
+}
+
+#[cfg(feature = "std")]
+impl<N> NotifyReport<N> {
+    /// Create a new report.
+    pub(crate) fn new(notified: usize, undelivered: usize, notification: N) -> Self {
+        Self {
+            notified,
+            undelivered,
+            notification,
+        }
+    }
+
+    /// The number of listeners that were notified by this call.
+    ///
+    /// This is the same value that [`Event::notify()`](crate::Event::notify) returns.
+    #[inline]
+    pub fn notified(&self) -> usize {
+        self.notified
+    }
+
+    /// The number of requested notifications that did not reach a listener.
+    ///
+    /// For notifications that are not [`additional`](IntoNotification::additional), listeners
+    /// that were already notified count as having received the notification.
+    #[inline]
+    pub fn undelivered(&self) -> usize {
+        self.undelivered
+    }
+
+    /// Returns the notification that was used.
+    #[inline]
+    pub fn into_notification(self) -> N {
+        self.notification
+    }
+}
+
+#[cfg(feature = "std")]
+impl<N, T> NotifyReport<Tag<N, T>> {
+    /// Returns the tag that was used for this notification.
+    #[inline]
+    pub fn into_tag(self) -> T {
+        self.notification.tag
+    }
+}
+
+#[cfg(feature = "std")]
+impl<N, I: Iterator> NotifyReport<TagIter<N, I>> {
+    /// Returns the tags that were not delivered to any listener.
+    ///
+    /// Tags that were taken from the iterator but not accepted by any
+    /// [filtered](crate::Event::listen_filter) listener come first, followed by the tags that
+    /// were never taken.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use event_listener::{Event, IntoNotification, Listener};
+    ///
+    /// let event = Event::<i32>::with_tag();
+    /// let l1 = event.listen();
+    /// let l2 = event.listen();
+    ///
+    /// let report = event.notify_report(5.additional().tag_iter(vec![1, 2, 3, 4, 5]));
+    /// assert_eq!(report.notified(), 2);
+    /// assert_eq!(report.undelivered(), 3);
+    /// assert_eq!(report.into_undelivered().collect::<Vec<_>>(), [3, 4, 5]);
+    ///
+    /// assert_eq!(l1.wait(), 1);
+    /// assert_eq!(l2.wait(), 2);
+    /// ```
+    #[inline]
+    pub fn into_undelivered(self) -> Chain<vec::IntoIter<I::Item>, I> {
+        self.notification
+            .rejected
+            .into_iter()
+            .chain(self.notification.tags)
+    }
 }
 
 /// A generic notification.
@@ -322,6 +999,34 @@ impl<T, F: FnMut() -> T> TagProducer for F {
     }
 }
 
+/// Produces tags for events whose tag is a zero-sized type, such as `()`.
+pub(crate) struct NothingProducer<T>(PhantomData<T>);
+
+impl<T> Default for NothingProducer<T> {
+    fn default() -> Self {
+        Self(PhantomData)
+    }
+}
+
+impl<T> fmt::Debug for NothingProducer<T> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("NothingProducer").finish()
+    }
+}
+
+impl<T> TagProducer for NothingProducer<T> {
+    type Tag = T;
+
+    fn next_tag(&mut self) -> Self::Tag {
+        // This has to be a zero-sized type with no drop handler.
+        assert_eq!(mem::size_of::<Self::Tag>(), 0);
+        assert!(!mem::needs_drop::<Self::Tag>());
+
+        // SAFETY: As this is a ZST without a drop handler, zero is valid.
+        unsafe { mem::zeroed() }
+    }
+}
+
 /// A value that can be converted into a [`Notification`].
 ///
 /// This trait adds onto the [`Notification`] trait by providing combinators that can be applied to all
@@ -353,7 +1058,8 @@ impl<T, F: FnMut() -> T> TagProducer for F {
 /// ```
 ///
 /// This trait is implemented for all types that implement [`Notification`], as well as for non-floating-point
-/// numeric literals (`usize`, `i32`, etc).
+/// numeric literals (`usize`, `i32`, etc). A `bool` notifies one listener if it is `true`, so that
+/// `event.notify(changed)` can replace `if changed { event.notify(1); }`.
 ///
 /// This function can be thought of as being analogous to [`std::iter::IntoIterator`], but for [`Notification`].
 pub trait IntoNotification: __private::Sealed {
@@ -435,7 +1141,8 @@ pub trait IntoNotification: __private::Sealed {
     /// desirable to omit this fence in certain cases.
     ///
     /// - You are running the [`Event`] on a single thread, where no synchronization needs to occur.
-    /// - You are emitting the `SeqCst` fence yourself.
+    /// - You are emitting the `SeqCst` fence yourself, for example with
+    ///   [`fence::full_fence()`](crate::fence::full_fence).
     ///
     /// In these cases, `relaxed()` can be used to avoid emitting the `SeqCst` fence.
     ///
@@ -505,15 +1212,68 @@ pub trait IntoNotification: __private::Sealed {
         Tag::new(tag, self.into_notification())
     }
 
+    /// Use an iterator to generate the tags for this notification.
+    ///
+    /// Every notified listener receives the next item of the iterator. No more listeners are
+    /// notified than the iterator's [`size_hint()`](Iterator::size_hint) allows for, and the
+    /// notification stops early if the iterator runs out of items. Use
+    /// [`Event::notify_report()`] to get back the tags that were not delivered.
+    ///
+    /// The tags are handed out in registration order: the oldest unnotified listener receives
+    /// the first tag, the next one the second tag, and so on. The whole notification is made
+    /// with the event locked, so this also holds while other threads register listeners. Only
+    /// [grouped](crate::Event::listen_grouped) and [filtered](crate::Event::listen_filter)
+    /// listeners change which listener is next in line. A listener that is dropped without
+    /// receiving its tag passes the tag on to the next listener in line.
+    ///
+    /// Tagging functions cannot be implemented efficiently for `no_std`, so this is only available
+    /// when the `std` feature is enabled.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use event_listener::{IntoNotification, Listener, Event};
+    ///
+    /// let event = Event::<u8>::with_tag();
+    ///
+    /// let mut listener1 = event.listen();
+    /// let mut listener2 = event.listen();
+    ///
+    /// event.notify(2.additional().tag_iter(vec![1, 2]));
+    ///
+    /// assert_eq!(listener1.wait(), 1);
+    /// assert_eq!(listener2.wait(), 2);
+    /// ```
+    ///
+    /// [`Event::notify_report()`]: crate::Event::notify_report
+    #[cfg(feature = "std")]
+    fn tag_iter<I>(self, tags: I) -> TagIter<Self::Notify, I::IntoIter>
+    where
+        Self: Sized + IntoNotification<Tag = ()>,
+        I: IntoIterator,
+    {
+        TagIter::new(tags.into_iter(), self.into_notification())
+    }
+
     /// Use a function to generate a tag with this notification.
     ///
     /// In many cases, it is desired to send additional information to the listener of the [`Event`]. For instance,
     /// it is possible to optimize a `Mutex` implementation by locking directly on the next listener, without
     /// needing to ever unlock the mutex at all.
     ///
+    /// The function is called once for every notified listener, in the same registration order
+    /// as the tags of [`tag_iter()`](IntoNotification::tag_iter).
+    ///
     /// Tagging functions cannot be implemented efficiently for `no_std`, so this is only available
     /// when the `std` feature is enabled.
     ///
+    /// # Panics
+    ///
+    /// A panic in the function is caught while the event is locked. It ends the notification and
+    /// is passed on once the event is unlocked, as described for
+    /// [`Event::notify()`](crate::Event::notify). Panics in the filters of listeners are not
+    /// caught.
+    ///
     /// # Examples
     ///
     /// ```
@@ -539,6 +1299,37 @@ pub trait IntoNotification: __private::Sealed {
     {
         TagWith::new(tag, self.into_notification())
     }
+
+    /// Transform the tags of this notification with a function.
+    ///
+    /// This adapts a notification built for one tag type to listeners that expect another.
+    ///
+    /// Tagging functions cannot be implemented efficiently for `no_std`, so this is only available
+    /// when the `std` feature is enabled.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use event_listener::{IntoNotification, Listener, Event};
+    ///
+    /// let event = Event::<String>::with_tag();
+    ///
+    /// let mut listener1 = event.listen();
+    /// let mut listener2 = event.listen();
+    ///
+    /// event.notify(2.additional().tag_iter(1..).map_tag(|n: i32| n.to_string()));
+    ///
+    /// assert_eq!(listener1.wait(), "1");
+    /// assert_eq!(listener2.wait(), "2");
+    /// ```
+    #[cfg(feature = "std")]
+    fn map_tag<U, F>(self, map: F) -> MapTag<Self::Notify, F>
+    where
+        Self: Sized,
+        F: FnMut(Self::Tag) -> U,
+    {
+        MapTag::new(map, self.into_notification())
+    }
 }
 
 impl<N: Notification> IntoNotification for N {
@@ -572,38 +1363,18 @@ macro_rules! impl_for_numeric_types {
 
 impl_for_numeric_types! { usize u8 u16 u32 u64 u128 isize i8 i16 i32 i64 i128 }
 
-/// Equivalent to `atomic::fence(Ordering::SeqCst)`, but in some cases faster.
-#[inline]
-pub(super) fn full_fence() {
-    #[cfg(all(any(target_arch = "x86", target_arch = "x86_64"), not(miri), not(loom)))]
-    {
-        use core::{arch::asm, cell::UnsafeCell};
-        // HACK(stjepang): On x86 architectures there are two different ways of executing
-        // a `SeqCst` fence.
-        //
-        // 1. `atomic::fence(SeqCst)`, which compiles into a `mfence` instruction.
-        // 2. A `lock <op>` instruction.
-        //
-        // Both instructions have the effect of a full barrier, but empirical benchmarks have shown
-        // that the second one is sometimes a bit faster.
-        let a = UnsafeCell::new(0_usize);
-        // It is common to use `lock or` here, but when using a local variable, `lock not`, which
-        // does not change the flag, should be slightly more efficient.
-        // Refs: https://www.felixcloutier.com/x86/not
-        unsafe {
-            #[cfg(target_pointer_width = "64")]
-            asm!("lock not qword ptr [{0}]", in(reg) a.get(), options(nostack, preserves_flags));
-            #[cfg(target_pointer_width = "32")]
-            asm!("lock not dword ptr [{0:e}]", in(reg) a.get(), options(nostack, preserves_flags));
-        }
-        return;
-    }
-    #[allow(unreachable_code)]
-    {
-        atomic::fence(Ordering::SeqCst);
+/// `true` notifies one listener, and `false` notifies none.
+impl IntoNotification for bool {
+    type Tag = ();
+    type Notify = Notify;
+
+    fn into_notification(self) -> Self::Notify {
+        Notify::new(self as usize)
     }
 }
 
+impl __private::Sealed for bool {}
+
 mod __private {
     /// Make sure the NotificationPrivate trait can't be implemented outside of this crate.
     #[doc(hidden)]
@@ -0,0 +1,314 @@
+//! A cell that is initialized once, built on top of [`Event`].
+//!
+//! When several tasks or threads try to initialize a [`OnceCell`] at the same time, only one of
+//! them runs its initializer. The others wait as listeners of the cell's event, and all of them
+//! complete when the initializer finishes. If the initializer fails, panics or is cancelled, the
+//! waiters are woken up again and one of them takes over.
+//!
+//! This module is only available with the `std` feature.
+//!
+//! # Examples
+//!
+//! ```
+//! use event_listener::once_cell::OnceCell;
+//!
+//! static CONFIG: OnceCell<String> = OnceCell::new();
+//!
+//! # futures_lite::future::block_on(async {
+//! let config = CONFIG.get_or_init(|| async { "loaded".to_string() }).await;
+//! assert_eq!(config, "loaded");
+//! # });
+//! assert_eq!(CONFIG.get().map(String::as_str), Some("loaded"));
+//! ```
+
+use crate::{Event, EventListener};
+
+use core::cell::UnsafeCell;
+use core::convert::Infallible;
+use core::fmt;
+use core::future::Future;
+use core::mem::{self, MaybeUninit};
+use core::sync::atomic::{AtomicUsize, Ordering};
+
+/// The cell is empty, and nobody is initializing it.
+const UNINIT: usize = 0;
+
+/// An initializer is running.
+const RUNNING: usize = 1;
+
+/// The cell holds a value.
+const READY: usize = 2;
+
+/// What a caller that wants the value has to do next.
+enum Start {
+    /// The cell holds a value.
+    Ready,
+
+    /// The caller is the initializer.
+    Init,
+
+    /// Another initializer is running, and the caller is listening for it to finish.
+    Wait(EventListener),
+}
+
+/// A cell that is initialized once, even if many tasks or threads race to initialize it.
+///
+/// See the [module documentation](self) for more information.
+pub struct OnceCell<T> {
+    /// `UNINIT`, `RUNNING` or `READY`.
+    state: AtomicUsize,
+
+    /// Notified when an initializer finishes or gives up.
+    event: Event,
+
+    /// The value, once the state is `READY`.
+    value: UnsafeCell<MaybeUninit<T>>,
+}
+
+unsafe impl<T: Send> Send for OnceCell<T> {}
+unsafe impl<T: Send + Sync> Sync for OnceCell<T> {}
+
+impl<T: fmt::Debug> fmt::Debug for OnceCell<T> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self.get() {
+            Some(value) => f.debug_tuple("OnceCell").field(value).finish(),
+            None => f
+                .debug_tuple("OnceCell")
+                .field(&format_args!("<uninit>"))
+                .finish(),
+        }
+    }
+}
+
+impl<T> Default for OnceCell<T> {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl<T> From<T> for OnceCell<T> {
+    fn from(value: T) -> Self {
+        Self {
+            state: AtomicUsize::new(READY),
+            event: Event::new(),
+            value: UnsafeCell::new(MaybeUninit::new(value)),
+        }
+    }
+}
+
+impl<T> OnceCell<T> {
+    /// Creates an empty cell.
+    #[cfg(not(loom))]
+    pub const fn new() -> Self {
+        Self {
+            state: AtomicUsize::new(UNINIT),
+            event: Event::new(),
+            value: UnsafeCell::new(MaybeUninit::uninit()),
+        }
+    }
+
+    #[cfg(loom)]
+    pub fn new() -> Self {
+        Self {
+            state: AtomicUsize::new(UNINIT),
+            event: Event::new(),
+            value: UnsafeCell::new(MaybeUninit::uninit()),
+        }
+    }
+
+    /// Returns the value, or `None` if the cell is not initialized yet.
+    pub fn get(&self) -> Option<&T> {
+        if self.state.load(Ordering::Acquire) == READY {
+            Some(unsafe { self.get_unchecked() })
+        } else {
+            None
+        }
+    }
+
+    /// Returns a mutable reference to the value, or `None` if the cell is not initialized yet.
+    pub fn get_mut(&mut self) -> Option<&mut T> {
+        if *self.state.get_mut() == READY {
+            Some(unsafe { (*self.value.get()).assume_init_mut() })
+        } else {
+            None
+        }
+    }
+
+    /// Initializes the cell with `value`.
+    ///
+    /// Returns the value back if the cell is already initialized, or if an initializer is
+    /// running.
+    pub fn set(&self, value: T) -> Result<&T, T> {
+        match self.start() {
+            Start::Init => Ok(Initializing { cell: self }.finish(value)),
+            Start::Ready | Start::Wait(_) => Err(value),
+        }
+    }
+
+    /// Returns the value, initializing the cell with the future returned by `f` if it is empty.
+    ///
+    /// If another initializer is running, this waits for it instead. If that initializer is
+    /// cancelled, this runs `f` in its place.
+    pub async fn get_or_init<F, Fut>(&self, f: F) -> &T
+    where
+        F: FnOnce() -> Fut,
+        Fut: Future<Output = T>,
+    {
+        let result = self
+            .get_or_try_init(|| async move { Ok::<_, Infallible>(f().await) })
+            .await;
+        match result {
+            Ok(value) => value,
+            Err(never) => match never {},
+        }
+    }
+
+    /// Returns the value, initializing the cell with the future returned by `f` if it is empty.
+    ///
+    /// If the future resolves to an error, the cell stays empty, the error is returned, and one
+    /// of the waiting callers runs its own initializer.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use event_listener::once_cell::OnceCell;
+    ///
+    /// let cell = OnceCell::new();
+    ///
+    /// # futures_lite::future::block_on(async {
+    /// let failed = cell.get_or_try_init(|| async { Err("not yet") }).await;
+    /// assert_eq!(failed, Err("not yet"));
+    ///
+    /// let value = cell.get_or_try_init(|| async { Ok::<_, &str>(1) }).await;
+    /// assert_eq!(value, Ok(&1));
+    /// # });
+    /// ```
+    pub async fn get_or_try_init<E, F, Fut>(&self, f: F) -> Result<&T, E>
+    where
+        F: FnOnce() -> Fut,
+        Fut: Future<Output = Result<T, E>>,
+    {
+        loop {
+            match self.start() {
+                Start::Ready => return Ok(unsafe { self.get_unchecked() }),
+                Start::Wait(listener) => listener.await,
+                Start::Init => {
+                    // Gives up the initialization if `f` fails, panics or is cancelled.
+                    let guard = Initializing { cell: self };
+                    let value = f().await?;
+                    return Ok(guard.finish(value));
+                }
+            }
+        }
+    }
+
+    /// Blocks until the value is available, initializing the cell with `f` if it is empty.
+    #[cfg(not(target_family = "wasm"))]
+    pub fn get_or_init_blocking<F>(&self, f: F) -> &T
+    where
+        F: FnOnce() -> T,
+    {
+        match self.get_or_try_init_blocking(|| Ok::<_, Infallible>(f())) {
+            Ok(value) => value,
+            Err(never) => match never {},
+        }
+    }
+
+    /// Blocks until the value is available, initializing the cell with `f` if it is empty.
+    ///
+    /// This is the blocking version of [`OnceCell::get_or_try_init()`].
+    #[cfg(not(target_family = "wasm"))]
+    pub fn get_or_try_init_blocking<E, F>(&self, f: F) -> Result<&T, E>
+    where
+        F: FnOnce() -> Result<T, E>,
+    {
+        use crate::Listener;
+
+        loop {
+            match self.start() {
+                Start::Ready => return Ok(unsafe { self.get_unchecked() }),
+                Start::Wait(listener) => listener.wait(),
+                Start::Init => {
+                    let guard = Initializing { cell: self };
+                    let value = f()?;
+                    return Ok(guard.finish(value));
+                }
+            }
+        }
+    }
+
+    /// Takes the value out of the cell, leaving it empty.
+    pub fn take(&mut self) -> Option<T> {
+        if mem::replace(self.state.get_mut(), UNINIT) == READY {
+            Some(unsafe { (*self.value.get()).assume_init_read() })
+        } else {
+            None
+        }
+    }
+
+    /// Returns the value, or `None` if the cell is not initialized.
+    pub fn into_inner(mut self) -> Option<T> {
+        self.take()
+    }
+
+    /// Becomes the initializer, or finds out why the caller cannot be.
+    fn start(&self) -> Start {
+        loop {
+            match self
+                .state
+                .compare_exchange(UNINIT, RUNNING, Ordering::Acquire, Ordering::Acquire)
+            {
+                Ok(_) => return Start::Init,
+                Err(READY) => return Start::Ready,
+                Err(_) => {}
+            }
+
+            // Check again after starting to listen, so that the end of the initialization is
+            // not missed.
+            let listener = self.event.listen();
+            if self.state.load(Ordering::Acquire) == RUNNING {
+                return Start::Wait(listener);
+            }
+        }
+    }
+
+    /// Returns the value, which must be initialized.
+    unsafe fn get_unchecked(&self) -> &T {
+        (*self.value.get()).assume_init_ref()
+    }
+}
+
+impl<T> Drop for OnceCell<T> {
+    fn drop(&mut self) {
+        drop(self.take());
+    }
+}
+
+/// Held by the initializer of a [`OnceCell`]. Dropping it gives up the initialization.
+struct Initializing<'a, T> {
+    cell: &'a OnceCell<T>,
+}
+
+impl<'a, T> Initializing<'a, T> {
+    /// Stores the value, and wakes up everyone waiting for it.
+    fn finish(self, value: T) -> &'a T {
+        let cell = self.cell;
+        mem::forget(self);
+
+        unsafe {
+            (*cell.value.get()).write(value);
+        }
+        cell.state.store(READY, Ordering::Release);
+        cell.event.notify(usize::MAX);
+
+        unsafe { cell.get_unchecked() }
+    }
+}
+
+impl<T> Drop for Initializing<'_, T> {
+    fn drop(&mut self) {
+        // Let the waiters race to become the next initializer.
+        self.cell.state.store(UNINIT, Ordering::Release);
+        self.cell.event.notify(usize::MAX);
+    }
+}
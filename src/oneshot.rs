@@ -0,0 +1,152 @@
+//! A oneshot channel built on top of tagged events.
+//!
+//! The [`Receiver`] is a listener of an event whose tag is the message, and [`Sender::send()`]
+//! notifies it with the message as the tag. Dropping the [`Sender`] without sending notifies the
+//! receiver with no message, which it reports as a [`RecvError`]. A channel makes two
+//! allocations: the event and the listener.
+//!
+//! This module is only available with the `std` feature.
+//!
+//! # Examples
+//!
+//! ```
+//! use std::thread;
+//! use event_listener::oneshot;
+//!
+//! let (tx, rx) = oneshot::channel();
+//! thread::spawn(move || tx.send("done").unwrap());
+//!
+//! # futures_lite::future::block_on(async {
+//! assert_eq!(rx.await, Ok("done"));
+//! # });
+//! ```
+
+use crate::notify::{Internal, NotificationPrivate};
+use crate::sync::Arc;
+use crate::{Event, EventListener, Inner, IntoNotification};
+
+use std::error;
+
+use core::fmt;
+use core::future::Future;
+use core::pin::Pin;
+use core::task::{Context, Poll};
+
+/// Creates a oneshot channel.
+pub fn channel<T>() -> (Sender<T>, Receiver<T>) {
+    let event = Event::with_tag();
+    let listener = event.listen();
+
+    // The listener keeps the inner state alive, so the event itself is not needed.
+    let inner = listener.listener.event.clone();
+    drop(event);
+
+    (Sender { inner: Some(inner) }, Receiver { listener })
+}
+
+/// The sending half of a oneshot channel.
+pub struct Sender<T> {
+    /// The state of the event, until a message is sent.
+    inner: Option<Arc<Inner<Option<T>>>>,
+}
+
+unsafe impl<T: Send> Send for Sender<T> {}
+unsafe impl<T: Send> Sync for Sender<T> {}
+
+impl<T> fmt::Debug for Sender<T> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("Sender").finish_non_exhaustive()
+    }
+}
+
+impl<T> Sender<T> {
+    /// Sends a message to the receiver.
+    ///
+    /// Returns the message back if the receiver has been dropped.
+    pub fn send(mut self, message: T) -> Result<(), T> {
+        let inner = self.inner.take().unwrap();
+        let mut message = Some(message);
+        notify(&inner, || message.take());
+
+        match message {
+            Some(message) => Err(message),
+            None => Ok(()),
+        }
+    }
+}
+
+impl<T> Drop for Sender<T> {
+    fn drop(&mut self) {
+        if let Some(inner) = self.inner.take() {
+            // Tell the receiver that no message is coming.
+            notify(&inner, || None);
+        }
+    }
+}
+
+/// Notify the receiver with a tag produced by `tag`.
+fn notify<T>(inner: &Inner<Option<T>>, tag: impl FnMut() -> Option<T>) {
+    let notify = 1.tag_with(tag).into_notification();
+
+    // Make sure the notification comes after whatever triggered it.
+    notify.fence(Internal::new());
+    inner.notify(notify);
+}
+
+/// The receiving half of a oneshot channel.
+///
+/// It is a future that resolves to the message, or to a [`RecvError`] if the [`Sender`] was
+/// dropped without sending one.
+#[must_use = "futures do nothing unless you `.await` or poll them"]
+pub struct Receiver<T> {
+    listener: EventListener<Option<T>>,
+}
+
+impl<T> fmt::Debug for Receiver<T> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("Receiver").finish_non_exhaustive()
+    }
+}
+
+impl<T> Receiver<T> {
+    /// Blocks until the message is received.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use event_listener::oneshot;
+    ///
+    /// let (tx, rx) = oneshot::channel::<i32>();
+    /// drop(tx);
+    ///
+    /// assert!(rx.recv_blocking().is_err());
+    /// ```
+    #[cfg(not(target_family = "wasm"))]
+    pub fn recv_blocking(self) -> Result<T, RecvError> {
+        use crate::Listener;
+
+        self.listener.wait().ok_or(RecvError)
+    }
+}
+
+impl<T> Future for Receiver<T> {
+    type Output = Result<T, RecvError>;
+
+    fn poll(mut self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Self::Output> {
+        self.listener
+            .poll_wait(cx)
+            .map(|message| message.ok_or(RecvError))
+    }
+}
+
+/// The error returned by [`Receiver`] when the [`Sender`] was dropped without sending a message.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct RecvError;
+
+impl fmt::Display for RecvError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str("the sender was dropped without sending a message")
+    }
+}
+
+impl error::Error for RecvError {}
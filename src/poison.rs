@@ -0,0 +1,96 @@
+//! Poisoning an event when a notifier panics.
+
+use crate::Event;
+
+use std::thread;
+
+use core::fmt;
+
+/// A guard that poisons an [`Event`] if the thread holding it panics.
+///
+/// This is returned by [`Event::poison_guard()`]. A notifier holds it while it works on the
+/// condition that the listeners wait for. If the notifier panics before dropping the guard, the
+/// event is poisoned: every listener that has not been notified yet, as well as every listener
+/// that is created afterwards, completes with [`WaitError::Poisoned`] instead of waiting forever.
+///
+/// Listeners created with [`EventListener::checked()`] report the error. All other listeners
+/// panic with it.
+///
+/// [`WaitError::Poisoned`]: crate::WaitError::Poisoned
+/// [`EventListener::checked()`]: crate::EventListener::checked
+#[must_use = "the event is only poisoned if the guard is held during a panic"]
+pub struct PoisonGuard<'a, T = ()> {
+    event: &'a Event<T>,
+}
+
+impl<T> fmt::Debug for PoisonGuard<'_, T> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("PoisonGuard").finish_non_exhaustive()
+    }
+}
+
+impl<T> Drop for PoisonGuard<'_, T> {
+    fn drop(&mut self) {
+        if thread::panicking() {
+            let inner = unsafe { &*self.event.inner() };
+            inner.poison();
+        }
+    }
+}
+
+impl<T> Event<T> {
+    /// Returns a guard that poisons this event if the current thread panics while holding it.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use std::sync::Arc;
+    /// use std::thread;
+    /// use event_listener::{Event, WaitError};
+    ///
+    /// let event = Arc::new(Event::new());
+    /// let listener = event.listen().checked();
+    ///
+    /// let notifier = thread::spawn({
+    ///     let event = event.clone();
+    ///     move || {
+    ///         let _guard = event.poison_guard();
+    ///         panic!("the notifier failed");
+    ///     }
+    /// });
+    /// assert!(notifier.join().is_err());
+    ///
+    /// assert!(event.is_poisoned());
+    /// assert_eq!(listener.wait(), Err(WaitError::Poisoned));
+    /// ```
+    #[inline]
+    pub fn poison_guard(&self) -> PoisonGuard<'_, T> {
+        PoisonGuard { event: self }
+    }
+
+    /// Returns `true` if a notifier panicked while holding a [`PoisonGuard`] for this event.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use event_listener::Event;
+    ///
+    /// let event = Event::new();
+    /// assert!(!event.is_poisoned());
+    /// ```
+    #[inline]
+    pub fn is_poisoned(&self) -> bool {
+        self.try_inner().map_or(false, |inner| inner.is_poisoned())
+    }
+
+    /// Clears the poisoned state of this event.
+    ///
+    /// Listeners created afterwards wait for notifications again. Listeners that already
+    /// completed with [`WaitError::Poisoned`](crate::WaitError::Poisoned) are not affected.
+    #[inline]
+    pub fn clear_poison(&self) {
+        if let Some(inner) = self.try_inner() {
+            inner.clear_poison();
+        }
+    }
+}
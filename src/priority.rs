@@ -0,0 +1,68 @@
+//! Hooks that let a blocked thread lend its priority to the thread it waits for.
+
+use std::sync::Arc;
+
+use core::fmt;
+
+/// Boosts the thread that is expected to notify an event while another thread is blocked on it.
+///
+/// A real-time thread that blocks in [`Listener::wait()`](crate::Listener::wait) on an event
+/// whose producer runs at a lower priority can be held up for as long as the scheduler prefers
+/// medium-priority threads over the producer. This is priority inversion. An event does not know
+/// which thread will notify it, but the application does, so it registers a hook with
+/// [`Event::set_priority_inheritance()`](crate::Event::set_priority_inheritance) that raises the
+/// producer's priority while someone waits.
+///
+/// Both methods are called on the waiting thread, which can look up its own priority to decide
+/// how far to boost. Every call to `boost()` is followed by exactly one call to `restore()`, also
+/// when the wait times out or is interrupted. When several threads wait at the same time, the
+/// calls overlap, so the hook has to keep track of the boosts that are still active.
+///
+/// Waiting threads that do not block, because they were notified already, and async tasks do not
+/// call the hook.
+///
+/// This trait is only available with the `priority-inheritance` feature.
+pub trait PriorityInheritance: Send + Sync {
+    /// Raises the priority of the producer. Called right before the waiting thread blocks.
+    fn boost(&self);
+
+    /// Undoes the matching call to `boost()`. Called once the waiting thread stops waiting.
+    fn restore(&self);
+}
+
+/// Lets the application keep a handle to the hook, for example to update which thread it boosts.
+impl<P: PriorityInheritance + ?Sized> PriorityInheritance for Arc<P> {
+    fn boost(&self) {
+        (**self).boost();
+    }
+
+    fn restore(&self) {
+        (**self).restore();
+    }
+}
+
+/// The hook of an event.
+pub(crate) type Hook = dyn PriorityInheritance;
+
+/// Keeps the producer boosted until it is dropped.
+pub(crate) struct Boost(Arc<Hook>);
+
+impl fmt::Debug for Boost {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("Boost").finish_non_exhaustive()
+    }
+}
+
+impl Boost {
+    /// Boosts the producer with `hook`.
+    pub(crate) fn new(hook: Arc<Hook>) -> Self {
+        hook.boost();
+        Self(hook)
+    }
+}
+
+impl Drop for Boost {
+    fn drop(&mut self) {
+        self.0.restore();
+    }
+}
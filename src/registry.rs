@@ -0,0 +1,72 @@
+//! A process-wide registry of events that are looked up by name.
+
+use crate::Event;
+
+use std::collections::HashMap;
+use std::sync::atomic::{AtomicPtr, Ordering};
+use std::sync::Mutex;
+
+use core::ptr;
+
+/// The events that have been looked up so far.
+type Registry = Mutex<HashMap<Box<str>, Event>>;
+
+/// Get the registry, creating it if necessary.
+fn registry() -> &'static Registry {
+    static REGISTRY: AtomicPtr<Registry> = AtomicPtr::new(ptr::null_mut());
+
+    let registry = REGISTRY.load(Ordering::Acquire);
+    if let Some(registry) = unsafe { registry.as_ref() } {
+        return registry;
+    }
+
+    let new = Box::into_raw(Box::new(Mutex::new(HashMap::new())));
+    match REGISTRY.compare_exchange(ptr::null_mut(), new, Ordering::AcqRel, Ordering::Acquire) {
+        // SAFETY: The registry is never freed.
+        Ok(_) => unsafe { &*new },
+
+        Err(existing) => {
+            // Someone else already created the registry.
+            drop(unsafe { Box::from_raw(new) });
+            unsafe { &*existing }
+        }
+    }
+}
+
+impl Event {
+    /// Returns the event with the given name from a process-wide registry.
+    ///
+    /// The first call with a name creates the event, and every later call with the same name
+    /// returns another handle to it. This lets loosely coupled
+    /// components meet on well-known events, such as `"subsystem.ready"`, without passing
+    /// handles around.
+    ///
+    /// Named events are never removed from the registry, so the names should come from a fixed
+    /// set rather than from, for example, user input.
+    ///
+    /// This is only available with the `registry` feature.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use event_listener::{Event, Listener};
+    ///
+    /// let listener = Event::named("subsystem.ready").listen();
+    ///
+    /// // Somewhere else entirely.
+    /// Event::named("subsystem.ready").notify(1);
+    ///
+    /// listener.wait();
+    /// ```
+    pub fn named(name: &str) -> Event {
+        let mut registry = registry().lock().unwrap_or_else(|e| e.into_inner());
+        match registry.get(name) {
+            Some(event) => event.clone(),
+            None => {
+                let event = Event::new();
+                registry.insert(name.into(), event.clone());
+                event
+            }
+        }
+    }
+}
@@ -0,0 +1,525 @@
+//! Collections of listeners that can be polled as a whole.
+
+#[cfg(feature = "std")]
+use crate::Event;
+use crate::EventListener;
+
+use alloc::vec::Vec;
+#[cfg(feature = "std")]
+use std::sync::Arc;
+
+use core::fmt;
+use core::future::Future;
+use core::pin::Pin;
+#[cfg(all(feature = "std", not(target_family = "wasm")))]
+use core::task::Waker;
+use core::task::{Context, Poll};
+#[cfg(all(feature = "std", not(target_family = "wasm")))]
+use std::task::Wake;
+
+/// A collection of [`EventListener`]s that can be polled as a whole.
+///
+/// Every listener in the set is identified by the key that was returned when it was inserted.
+/// The listeners can belong to different [`Event`](crate::Event)s, as long as they share a tag
+/// type. Polling the set yields the key of a notified listener along with its tag, and removes
+/// that listener from the set. Keys of removed listeners may be reused by later insertions.
+///
+/// Each poll visits every listener in the set, so this is meant for a moderate number of
+/// listeners. The same waker is registered with all of them.
+///
+/// # Examples
+///
+/// ```
+/// use event_listener::{Event, ListenerSet};
+///
+/// let event1 = Event::new();
+/// let event2 = Event::new();
+///
+/// let mut set = ListenerSet::new();
+/// let _key1 = set.insert(event1.listen());
+/// let key2 = set.insert(event2.listen());
+///
+/// event2.notify(1);
+///
+/// # futures_lite::future::block_on(async {
+/// assert_eq!(set.notified().await, Some((key2, ())));
+/// assert_eq!(set.len(), 1);
+/// # });
+/// ```
+pub struct ListenerSet<T = ()> {
+    /// The slots of the set, which are `None` if they are vacant.
+    slots: Vec<Option<EventListener<T>>>,
+
+    /// Indices of vacant slots.
+    vacant: Vec<usize>,
+
+    /// The slot to start polling from, so that one busy listener cannot starve the others.
+    cursor: usize,
+}
+
+impl<T> fmt::Debug for ListenerSet<T> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("ListenerSet")
+            .field("len", &self.len())
+            .finish_non_exhaustive()
+    }
+}
+
+impl<T> Default for ListenerSet<T> {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl<T> ListenerSet<T> {
+    /// Creates an empty set.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use event_listener::ListenerSet;
+    ///
+    /// let set = ListenerSet::<()>::new();
+    /// assert!(set.is_empty());
+    /// ```
+    pub const fn new() -> Self {
+        Self {
+            slots: Vec::new(),
+            vacant: Vec::new(),
+            cursor: 0,
+        }
+    }
+
+    /// Returns the number of listeners in the set.
+    #[inline]
+    pub fn len(&self) -> usize {
+        self.slots.len() - self.vacant.len()
+    }
+
+    /// Returns `true` if there are no listeners in the set.
+    #[inline]
+    pub fn is_empty(&self) -> bool {
+        self.len() == 0
+    }
+
+    /// Inserts a listener into the set and returns its key.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use event_listener::{Event, ListenerSet};
+    ///
+    /// let event = Event::new();
+    /// let mut set = ListenerSet::new();
+    ///
+    /// let key = set.insert(event.listen());
+    /// assert!(set.contains(key));
+    /// ```
+    pub fn insert(&mut self, listener: EventListener<T>) -> usize {
+        match self.vacant.pop() {
+            Some(key) => {
+                self.slots[key] = Some(listener);
+                key
+            }
+            None => {
+                self.slots.push(Some(listener));
+                self.slots.len() - 1
+            }
+        }
+    }
+
+    /// Returns `true` if the set contains a listener with this key.
+    #[inline]
+    pub fn contains(&self, key: usize) -> bool {
+        self.get(key).is_some()
+    }
+
+    /// Returns a reference to the listener with this key.
+    #[inline]
+    pub fn get(&self, key: usize) -> Option<&EventListener<T>> {
+        self.slots.get(key).and_then(Option::as_ref)
+    }
+
+    /// Returns a mutable reference to the listener with this key.
+    #[inline]
+    pub fn get_mut(&mut self, key: usize) -> Option<&mut EventListener<T>> {
+        self.slots.get_mut(key).and_then(Option::as_mut)
+    }
+
+    /// Removes the listener with this key from the set and returns it.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use event_listener::{Event, ListenerSet};
+    ///
+    /// let event = Event::new();
+    /// let mut set = ListenerSet::new();
+    ///
+    /// let key = set.insert(event.listen());
+    /// assert!(set.remove(key).is_some());
+    /// assert!(set.remove(key).is_none());
+    /// ```
+    pub fn remove(&mut self, key: usize) -> Option<EventListener<T>> {
+        let listener = self.slots.get_mut(key)?.take()?;
+        self.vacant.push(key);
+        Some(listener)
+    }
+
+    /// Removes all listeners from the set.
+    pub fn clear(&mut self) {
+        self.slots.clear();
+        self.vacant.clear();
+        self.cursor = 0;
+    }
+
+    /// Returns an iterator over the keys and listeners in the set.
+    pub fn iter(&self) -> impl Iterator<Item = (usize, &EventListener<T>)> + '_ {
+        self.slots
+            .iter()
+            .enumerate()
+            .filter_map(|(key, slot)| slot.as_ref().map(|listener| (key, listener)))
+    }
+
+    /// Polls the listeners in the set for a notification.
+    ///
+    /// Returns the key and tag of a notified listener, which is removed from the set. Returns
+    /// `Poll::Ready(None)` if the set is empty.
+    pub fn poll_next(&mut self, cx: &mut Context<'_>) -> Poll<Option<(usize, T)>> {
+        if self.is_empty() {
+            return Poll::Ready(None);
+        }
+
+        let len = self.slots.len();
+        for offset in 0..len {
+            let key = (self.cursor + offset) % len;
+
+            let listener = match &mut self.slots[key] {
+                Some(listener) => listener,
+                None => continue,
+            };
+
+            if let Poll::Ready(tag) = Pin::new(listener).poll(cx) {
+                self.slots[key] = None;
+                self.vacant.push(key);
+                self.cursor = (key + 1) % len;
+                return Poll::Ready(Some((key, tag)));
+            }
+        }
+
+        Poll::Pending
+    }
+
+    /// Waits for one of the listeners in the set to be notified.
+    ///
+    /// Returns the key and tag of the notified listener, which is removed from the set. Returns
+    /// `None` if the set is empty.
+    pub fn notified(&mut self) -> NextNotified<'_, T> {
+        NextNotified { set: self }
+    }
+
+    /// Blocks until one of the listeners in the set is notified.
+    ///
+    /// This is the blocking version of [`ListenerSet::notified()`]. The thread parks once until
+    /// any of the listeners is notified, rather than waiting on them one by one.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use event_listener::{Event, ListenerSet};
+    ///
+    /// let event1 = Event::new();
+    /// let event2 = Event::new();
+    ///
+    /// let mut set = ListenerSet::new();
+    /// let _key1 = set.insert(event1.listen());
+    /// let key2 = set.insert(event2.listen());
+    ///
+    /// event2.notify(1);
+    /// assert_eq!(set.wait(), Some((key2, ())));
+    /// ```
+    #[cfg(all(feature = "std", not(target_family = "wasm")))]
+    pub fn wait(&mut self) -> Option<(usize, T)> {
+        block_on(|cx| self.poll_next(cx))
+    }
+}
+
+/// The future returned by [`ListenerSet::notified()`].
+#[must_use = "futures do nothing unless you `.await` or poll them"]
+pub struct NextNotified<'a, T> {
+    set: &'a mut ListenerSet<T>,
+}
+
+impl<T> fmt::Debug for NextNotified<'_, T> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("NextNotified").finish_non_exhaustive()
+    }
+}
+
+impl<T> Future for NextNotified<'_, T> {
+    type Output = Option<(usize, T)>;
+
+    fn poll(mut self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Self::Output> {
+        self.set.poll_next(cx)
+    }
+}
+
+/// A set of [`Event`]s that are listened to as a whole.
+///
+/// Unlike a [`ListenerSet`], the events stay attached after one of them fires: the group keeps
+/// one listener registered with every attached event, and replaces it as soon as its
+/// notification is received. Each event is identified by the key that was returned when it was
+/// attached. Keys of detached events may be reused by later attachments.
+///
+/// The group takes part in the notifications of every attached event like any other listener.
+/// A notification that the group receives is kept until the group is polled, or passed on to
+/// another listener if the event is detached first.
+///
+/// Event groups need the `std` feature.
+///
+/// # Examples
+///
+/// ```
+/// use std::sync::Arc;
+/// use event_listener::{Event, EventGroup};
+///
+/// let event1 = Arc::new(Event::new());
+/// let event2 = Arc::new(Event::new());
+///
+/// let mut group = EventGroup::new();
+/// let key1 = group.attach(event1.clone());
+/// let key2 = group.attach(event2.clone());
+///
+/// # futures_lite::future::block_on(async {
+/// event2.notify(1);
+/// assert_eq!(group.notified().await, Some((key2, ())));
+///
+/// // The events are still attached.
+/// event1.notify(1);
+/// assert_eq!(group.notified().await, Some((key1, ())));
+/// assert_eq!(group.len(), 2);
+/// # });
+/// ```
+#[cfg(feature = "std")]
+pub struct EventGroup<T = ()> {
+    /// The slots of the group, which are `None` if they are vacant.
+    slots: Vec<Option<Source<T>>>,
+
+    /// Indices of vacant slots.
+    vacant: Vec<usize>,
+
+    /// The slot to start polling from, so that one busy event cannot starve the others.
+    cursor: usize,
+}
+
+/// An event attached to a group.
+#[cfg(feature = "std")]
+struct Source<T> {
+    /// The attached event.
+    event: Arc<Event<T>>,
+
+    /// The listener registered with the event.
+    listener: EventListener<T>,
+}
+
+#[cfg(feature = "std")]
+impl<T> fmt::Debug for EventGroup<T> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("EventGroup")
+            .field("len", &self.len())
+            .finish_non_exhaustive()
+    }
+}
+
+#[cfg(feature = "std")]
+impl<T> Default for EventGroup<T> {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+#[cfg(feature = "std")]
+impl<T> EventGroup<T> {
+    /// Creates an empty group.
+    pub const fn new() -> Self {
+        Self {
+            slots: Vec::new(),
+            vacant: Vec::new(),
+            cursor: 0,
+        }
+    }
+
+    /// Returns the number of events in the group.
+    #[inline]
+    pub fn len(&self) -> usize {
+        self.slots.len() - self.vacant.len()
+    }
+
+    /// Returns `true` if there are no events in the group.
+    #[inline]
+    pub fn is_empty(&self) -> bool {
+        self.len() == 0
+    }
+
+    /// Attaches an event to the group and returns its key.
+    ///
+    /// The group starts listening to the event right away.
+    pub fn attach(&mut self, event: Arc<Event<T>>) -> usize {
+        let source = Source {
+            listener: event.listen(),
+            event,
+        };
+
+        match self.vacant.pop() {
+            Some(key) => {
+                self.slots[key] = Some(source);
+                key
+            }
+            None => {
+                self.slots.push(Some(source));
+                self.slots.len() - 1
+            }
+        }
+    }
+
+    /// Returns `true` if the group contains an event with this key.
+    #[inline]
+    pub fn contains(&self, key: usize) -> bool {
+        self.get(key).is_some()
+    }
+
+    /// Returns the event with this key.
+    #[inline]
+    pub fn get(&self, key: usize) -> Option<&Arc<Event<T>>> {
+        self.slots
+            .get(key)
+            .and_then(Option::as_ref)
+            .map(|source| &source.event)
+    }
+
+    /// Detaches the event with this key from the group and returns it.
+    ///
+    /// A notification that the group received from the event but did not yield yet is passed on
+    /// to another listener of the event.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use std::sync::Arc;
+    /// use event_listener::{Event, EventGroup};
+    ///
+    /// let event = Arc::new(Event::new());
+    /// let mut group = EventGroup::new();
+    ///
+    /// let key = group.attach(event.clone());
+    /// assert!(group.detach(key).is_some());
+    /// assert!(group.is_empty());
+    /// ```
+    pub fn detach(&mut self, key: usize) -> Option<Arc<Event<T>>> {
+        let source = self.slots.get_mut(key)?.take()?;
+        self.vacant.push(key);
+        Some(source.event)
+    }
+
+    /// Detaches all events from the group.
+    pub fn clear(&mut self) {
+        self.slots.clear();
+        self.vacant.clear();
+        self.cursor = 0;
+    }
+
+    /// Polls the events in the group for a notification.
+    ///
+    /// Returns the key of an event that fired along with the tag of its notification. The event
+    /// stays attached. Returns `Poll::Ready(None)` if the group is empty.
+    pub fn poll_next(&mut self, cx: &mut Context<'_>) -> Poll<Option<(usize, T)>> {
+        if self.is_empty() {
+            return Poll::Ready(None);
+        }
+
+        let len = self.slots.len();
+        for offset in 0..len {
+            let key = (self.cursor + offset) % len;
+
+            let source = match &mut self.slots[key] {
+                Some(source) => source,
+                None => continue,
+            };
+
+            if let Poll::Ready(tag) = Pin::new(&mut source.listener).poll(cx) {
+                // Keep listening to the event.
+                source.listener = source.event.listen();
+                self.cursor = (key + 1) % len;
+                return Poll::Ready(Some((key, tag)));
+            }
+        }
+
+        Poll::Pending
+    }
+
+    /// Waits for one of the events in the group to fire.
+    ///
+    /// Returns the key of the event along with the tag of its notification. Returns `None` if
+    /// the group is empty.
+    pub fn notified(&mut self) -> GroupNotified<'_, T> {
+        GroupNotified { group: self }
+    }
+
+    /// Blocks until one of the events in the group fires.
+    ///
+    /// This is the blocking version of [`EventGroup::notified()`].
+    #[cfg(not(target_family = "wasm"))]
+    pub fn wait(&mut self) -> Option<(usize, T)> {
+        block_on(|cx| self.poll_next(cx))
+    }
+}
+
+/// The future returned by [`EventGroup::notified()`].
+#[must_use = "futures do nothing unless you `.await` or poll them"]
+#[cfg(feature = "std")]
+pub struct GroupNotified<'a, T> {
+    group: &'a mut EventGroup<T>,
+}
+
+#[cfg(feature = "std")]
+impl<T> fmt::Debug for GroupNotified<'_, T> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("GroupNotified").finish_non_exhaustive()
+    }
+}
+
+#[cfg(feature = "std")]
+impl<T> Future for GroupNotified<'_, T> {
+    type Output = Option<(usize, T)>;
+
+    fn poll(mut self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Self::Output> {
+        self.group.poll_next(cx)
+    }
+}
+
+/// Polls with a waker that unparks the current thread, and parks until the poll is ready.
+#[cfg(all(feature = "std", not(target_family = "wasm")))]
+pub(crate) fn block_on<R>(mut poll: impl FnMut(&mut Context<'_>) -> Poll<R>) -> R {
+    struct Unpark(parking::Unparker);
+
+    impl Wake for Unpark {
+        fn wake(self: Arc<Self>) {
+            self.0.unpark();
+        }
+
+        fn wake_by_ref(self: &Arc<Self>) {
+            self.0.unpark();
+        }
+    }
+
+    let (parker, unparker) = parking::pair();
+    let waker = Waker::from(Arc::new(Unpark(unparker)));
+    let mut cx = Context::from_waker(&waker);
+
+    loop {
+        match poll(&mut cx) {
+            Poll::Ready(result) => return result,
+            Poll::Pending => parker.park(),
+        }
+    }
+}
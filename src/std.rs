@@ -2,17 +2,31 @@
 //!
 //! This implementation crates an intrusive linked list of listeners.
 
-use crate::notify::{GenericNotify, Internal, Notification};
+use crate::ack::Ack;
+#[cfg(feature = "instrument")]
+use crate::instrument::{Snapshot, Stats, Timings};
+use crate::notify::{GenericNotify, Internal, Notification, NotificationPrivate, NotifyContext};
+#[cfg(feature = "priority-inheritance")]
+use crate::priority::Hook;
 use crate::sync::atomic::Ordering;
 use crate::sync::cell::{Cell, UnsafeCell};
 use crate::sync::{Mutex, MutexGuard};
-use crate::{RegisterResult, State, TaskRef};
+use crate::{
+    Filter, ListenOptions, ListenerClass, RegisterResult, State, Task, TaskRef, WaitError,
+};
 
 use core::marker::PhantomPinned;
-use core::mem;
+use core::mem::{self, ManuallyDrop};
 use core::ops::{Deref, DerefMut};
 use core::pin::Pin;
 use core::ptr::NonNull;
+use core::task::Poll;
+
+use std::any::Any;
+use std::collections::VecDeque;
+use std::panic::{self, AssertUnwindSafe};
+use std::sync::{Arc, TryLockError};
+use std::thread;
 
 pub(super) struct List<T>(Mutex<Inner<T>>);
 
@@ -31,6 +45,106 @@ struct Inner<T> {
 
     /// The number of notified listeners.
     notified: usize,
+
+    /// The combined weight of the notified listeners.
+    ///
+    /// This is wider than `usize`, because `notify_all` ignores the weights and can notify
+    /// listeners whose weights add up to more than `usize::MAX`.
+    notified_weight: u128,
+
+    /// The number of listeners that belong to a group.
+    grouped: usize,
+
+    /// The number of listeners with a tag filter.
+    filtered: usize,
+
+    /// The group of the last listener that was notified.
+    last_group: Option<Option<usize>>,
+
+    /// The ID to give to the next listener.
+    next_id: u64,
+
+    /// Whether a notifier panicked while holding a poison guard.
+    poisoned: bool,
+
+    /// Whether the event was closed, so that new listeners are rejected.
+    closed: bool,
+
+    /// Notifications that are kept for future listeners, for counting and latched events.
+    store: Option<Store<T>>,
+
+    /// Forwards notifications to the parent event, if there is one.
+    parent: Option<Arc<Parent>>,
+
+    /// Tasks of notified listeners, which are woken once the lock is released.
+    wake: Wake,
+
+    /// Receives the tags that no listener received, if set.
+    undelivered: Option<Arc<Undelivered<T>>>,
+
+    /// Boosts the producer while threads are blocked on the event, if set.
+    #[cfg(feature = "priority-inheritance")]
+    priority: Option<Arc<Hook>>,
+
+    /// How long listeners wait.
+    #[cfg(feature = "instrument")]
+    stats: Stats,
+
+    /// Tags that no listener received, which are handed to `undelivered` once the lock is
+    /// released.
+    dropped: Vec<T>,
+
+    /// Subscriptions that were notified while the list is locked. Their notifications are moved
+    /// to their buffers before the lock is released, so that they are listening again.
+    rearm: Vec<NonNull<Link<T>>>,
+
+    /// A panic while producing a tag, which is resumed once the lock is released.
+    panic: Option<Box<dyn Any + Send>>,
+}
+
+/// Notifies a parent event with a count and whether the notification is additional.
+pub(crate) type Parent = dyn Fn(usize, bool) + Send + Sync;
+
+/// Receives a tag that no listener received.
+pub(crate) type Undelivered<T> = dyn Fn(T) + Send + Sync;
+
+/// Tasks to wake once the lock is released.
+///
+/// The first task is kept inline, so that notifying a single listener does not allocate.
+#[derive(Default)]
+struct Wake {
+    first: Option<Task>,
+    rest: Vec<Task>,
+}
+
+impl Wake {
+    fn push(&mut self, task: Task) {
+        match self.first {
+            None => self.first = Some(task),
+            Some(_) => self.rest.push(task),
+        }
+    }
+
+    /// Wakes the tasks and returns the emptied buffer, so that its capacity can be reused.
+    fn wake(mut self) -> Vec<Task> {
+        self.first
+            .into_iter()
+            .chain(self.rest.drain(..))
+            .for_each(Task::wake);
+        self.rest
+    }
+}
+
+/// Notifications that are kept for future listeners.
+struct Store<T> {
+    /// Whether this is a latched event rather than a counting one.
+    latched: bool,
+
+    /// The number of stored units, which is zero or one for a latched event.
+    count: usize,
+
+    /// Creates the tag for a stored notification.
+    tag: fn() -> T,
 }
 
 impl<T> List<T> {
@@ -42,6 +156,24 @@ impl<T> List<T> {
             next: None,
             len: 0,
             notified: 0,
+            notified_weight: 0,
+            grouped: 0,
+            filtered: 0,
+            last_group: None,
+            next_id: 0,
+            poisoned: false,
+            closed: false,
+            store: None,
+            parent: None,
+            wake: Wake::default(),
+            undelivered: None,
+            #[cfg(feature = "priority-inheritance")]
+            priority: None,
+            #[cfg(feature = "instrument")]
+            stats: Stats::default(),
+            dropped: Vec::new(),
+            rearm: Vec::new(),
+            panic: None,
         }))
     }
 
@@ -60,43 +192,127 @@ impl<T> crate::Inner<T> {
     fn lock(&self) -> ListLock<'_, '_, T> {
         ListLock {
             inner: self,
-            lock: self.list.0.lock().unwrap_or_else(|e| e.into_inner()),
+            lock: ManuallyDrop::new(self.list.0.lock().unwrap_or_else(|e| e.into_inner())),
         }
     }
 
+    /// Delivers the notifications in the mailbox, unless the list is locked by someone else, who
+    /// delivers them instead.
+    pub(crate) fn deliver_mailbox(&self) {
+        drop(self.try_lock());
+    }
+
+    /// Lock the list, unless it is locked by someone else.
+    fn try_lock(&self) -> Option<ListLock<'_, '_, T>> {
+        let lock = match self.list.0.try_lock() {
+            Ok(lock) => lock,
+            Err(TryLockError::Poisoned(e)) => e.into_inner(),
+            Err(TryLockError::WouldBlock) => return None,
+        };
+
+        Some(ListLock {
+            inner: self,
+            lock: ManuallyDrop::new(lock),
+        })
+    }
+
     /// Add a new listener to the list.
-    pub(crate) fn insert(&self, mut listener: Pin<&mut Option<Listener<T>>>) {
+    pub(crate) fn insert(&self, listener: Pin<&mut Option<Listener<T>>>) {
+        self.insert_with(listener, ListenOptions::default());
+    }
+
+    /// Add a new listener to the list with the given options.
+    ///
+    /// Returns the ID of the new listener.
+    pub(crate) fn insert_with(
+        &self,
+        mut listener: Pin<&mut Option<Listener<T>>>,
+        options: ListenOptions<T>,
+    ) -> u64 {
         let mut inner = self.lock();
+        let id = inner.next_id;
+        inner.next_id += 1;
+
+        // Listeners of a poisoned or closed event are never linked into the list.
+        let rejected = if inner.poisoned {
+            Some(WaitError::Poisoned)
+        } else if inner.closed {
+            Some(WaitError::Closed)
+        } else {
+            None
+        };
+        let state = match rejected {
+            Some(reason) => State::Interrupted(reason),
+            None => State::Created,
+        };
 
         listener.as_mut().set(Some(Listener {
             link: UnsafeCell::new(Link {
-                state: Cell::new(State::Created),
+                state: Cell::new(state),
                 prev: Cell::new(inner.tail),
                 next: Cell::new(None),
+                weight: options.weight,
+                clone_tag: options.clone_tag,
+                group: options.group,
+                filter: options.filter,
+                class: options.class,
+                id,
+                ack: Cell::new(None),
+                buffer: options.subscribed.then(|| Cell::new(VecDeque::new())),
+                #[cfg(feature = "instrument")]
+                timings: Timings::new(),
             }),
             _pin: PhantomPinned,
         }));
         let listener = listener.as_pin_mut().unwrap();
+        if rejected.is_some() {
+            return id;
+        }
+
+        let entry_guard = listener.link.get();
+        // SAFETY: We are locked, so we can access the inner `link`.
+        let entry = unsafe { entry_guard.deref() };
 
-        {
-            let entry_guard = listener.link.get();
-            // SAFETY: We are locked, so we can access the inner `link`.
-            let entry = unsafe { entry_guard.deref() };
+        // Replace the tail with the new entry.
+        match inner.tail.replace(entry.into()) {
+            None => inner.head = Some(entry.into()),
+            Some(t) => unsafe { t.as_ref().next.set(Some(entry.into())) },
+        };
 
-            // Replace the tail with the new entry.
-            match mem::replace(&mut inner.tail, Some(entry.into())) {
-                None => inner.head = Some(entry.into()),
-                Some(t) => unsafe { t.as_ref().next.set(Some(entry.into())) },
-            };
-        }
+        match inner.take_stored(entry) {
+            // A stored notification completes the new entry right away.
+            Some(tag) => {
+                entry.state.set(State::Notified {
+                    additional: true,
+                    tag,
+                });
+                #[cfg(feature = "instrument")]
+                inner.stats.notified(&entry.timings);
+                inner.notified += 1;
+                inner.notified_weight += options.weight as u128;
+                if entry.buffer.is_some() {
+                    inner.rearm.push(entry.into());
+                }
+            }
 
-        // If there are no unnotified entries, this is the first one.
-        if inner.next.is_none() {
-            inner.next = inner.tail;
+            // If there are no unnotified entries, this is the first one.
+            None => {
+                if inner.next.is_none() {
+                    inner.next = inner.tail;
+                }
+            }
         }
 
         // Bump the entry count.
         inner.len += 1;
+        if options.group.is_some() {
+            inner.grouped += 1;
+        }
+        if entry.filter.is_some() {
+            inner.filtered += 1;
+        }
+
+        id
     }
 
     /// Remove a listener from the list.
@@ -105,13 +321,177 @@ impl<T> crate::Inner<T> {
         listener: Pin<&mut Option<Listener<T>>>,
         propagate: bool,
     ) -> Option<State<T>> {
+        // A listener that is not in the list doesn't need the lock.
+        if listener.is_none() {
+            return None;
+        }
+
         self.lock().remove(listener, propagate)
     }
 
+    /// Remove a listener from the list, unless the list is locked by someone else.
+    ///
+    /// Returns `false` if the listener is still in the list.
+    pub(crate) fn try_remove(
+        &self,
+        listener: Pin<&mut Option<Listener<T>>>,
+        propagate: bool,
+    ) -> bool {
+        if listener.is_none() {
+            return true;
+        }
+
+        match self.try_lock() {
+            Some(mut inner) => {
+                inner.remove(listener, propagate);
+                true
+            }
+            None => false,
+        }
+    }
+
+    /// Removes an entry, and takes its notification if it was notified.
+    ///
+    /// Returns `None` if the listener was never inserted.
+    #[cfg(not(loom))]
+    pub(crate) fn take(&self, mut listener: Pin<&mut Option<Listener<T>>>) -> Option<Option<T>> {
+        let mut inner = self.lock();
+        let entry_guard = listener.as_mut().as_pin_mut()?.link.get();
+        // SAFETY: We are locked, so we can access the inner `link`.
+        let entry = unsafe { entry_guard.deref() };
+
+        let tag = match entry.state.replace(State::NotifiedTaken) {
+            State::Notified { tag, .. } => {
+                #[cfg(feature = "instrument")]
+                inner.stats.polled(&entry.timings);
+                Some(tag)
+            }
+            state => {
+                entry.state.set(state);
+                None
+            }
+        };
+
+        inner.remove(listener, false);
+        Some(tag)
+    }
+
     /// Notifies a number of entries.
     #[cold]
     pub(crate) fn notify(&self, notify: impl Notification<Tag = T>) -> usize {
-        self.lock().notify(notify)
+        self.notify_counted(notify).0
+    }
+
+    /// Notifies all unnotified entries, regardless of their weight and group.
+    #[cold]
+    pub(crate) fn notify_all(&self, additional: bool, tags: impl FnMut() -> T) -> usize {
+        let mut inner = self.lock();
+        let count = inner.notify_all(additional, tags);
+
+        // Notify the parent without holding the lock.
+        let parent = inner.parent.clone();
+        drop(inner);
+        if let Some(parent) = parent {
+            parent(usize::MAX, additional);
+        }
+
+        count
+    }
+
+    /// Notifies all unnotified entries, and interrupts every listener that is left or registered
+    /// later.
+    #[cold]
+    pub(crate) fn close(&self, tags: impl FnMut() -> T) -> usize {
+        let mut inner = self.lock();
+        let count = inner.notify_all(false, tags);
+        inner.closed = true;
+        inner.interrupt_unnotified(WaitError::Closed);
+
+        // Notify the parent without holding the lock.
+        let parent = inner.parent.clone();
+        drop(inner);
+        if let Some(parent) = parent {
+            parent(usize::MAX, false);
+        }
+
+        count
+    }
+
+    /// Notifies up to `n` unnotified entries of a class, regardless of their weight and group.
+    ///
+    /// If no entry of the class was notified, every unnotified entry of the `or_else` class is
+    /// notified instead.
+    #[cold]
+    pub(crate) fn notify_class(
+        &self,
+        class: ListenerClass,
+        n: usize,
+        or_else: Option<ListenerClass>,
+        mut tags: impl FnMut() -> T,
+    ) -> usize {
+        let mut inner = self.lock();
+
+        let mut count = inner.notify_class(class, n, &mut tags);
+        if count == 0 {
+            if let Some(class) = or_else {
+                count = inner.notify_class(class, usize::MAX, &mut tags);
+            }
+        }
+
+        // Notify the parent without holding the lock.
+        let parent = inner.parent.clone();
+        drop(inner);
+        if let Some(parent) = parent {
+            if count > 0 {
+                parent(count, true);
+            }
+        }
+
+        count
+    }
+
+    /// Notifies a number of entries, and also returns the number of units that were not used.
+    #[cold]
+    pub(crate) fn notify_counted(&self, notify: impl Notification<Tag = T>) -> (usize, usize) {
+        self.lock().notify_and_forward(notify, None)
+    }
+
+    /// Notifies a number of entries, and tracks when they are done with their notifications.
+    pub(crate) fn notify_acked(&self, notify: impl Notification<Tag = T>, ack: &Arc<Ack>) -> usize {
+        self.lock().notify_and_forward(notify, Some(ack)).0
+    }
+
+    /// Notifies a number of entries, unless the list is locked by someone else.
+    pub(crate) fn try_notify(&self, notify: impl Notification<Tag = T>) -> Option<usize> {
+        Some(self.try_lock()?.notify_and_forward(notify, None).0)
+    }
+
+    /// Set or remove the parent of the event.
+    pub(crate) fn set_parent(&self, parent: Option<Arc<Parent>>) {
+        self.lock().parent = parent;
+    }
+
+    /// Set the function that receives the tags that no listener received.
+    pub(crate) fn set_undelivered(&self, undelivered: Option<Arc<Undelivered<T>>>) {
+        self.lock().undelivered = undelivered;
+    }
+
+    /// Set the hook that boosts the producer while threads are blocked on the event.
+    #[cfg(feature = "priority-inheritance")]
+    pub(crate) fn set_priority_inheritance(&self, priority: Option<Arc<Hook>>) {
+        self.lock().priority = priority;
+    }
+
+    /// Get a copy of the wait-time histograms.
+    #[cfg(feature = "instrument")]
+    pub(crate) fn snapshot(&self) -> Snapshot {
+        self.lock().stats.snapshot()
+    }
+
+    /// Get the hook that boosts the producer while threads are blocked on the event.
+    #[cfg(feature = "priority-inheritance")]
+    pub(crate) fn priority_inheritance(&self) -> Option<Arc<Hook>> {
+        self.lock().priority.clone()
     }
 
     /// Register a task to be notified when the event is triggered.
@@ -134,11 +514,21 @@ impl<T> crate::Inner<T> {
         // Take out the state and check it.
         match entry.state.replace(State::NotifiedTaken) {
             State::Notified { tag, .. } => {
+                #[cfg(feature = "instrument")]
+                inner.stats.polled(&entry.timings);
+
                 // We have been notified, remove the listener.
                 inner.remove(listener, false);
                 RegisterResult::Notified(tag)
             }
 
+            State::Interrupted(reason) => {
+                // We have been detached from the list, so clean up the listener.
+                entry.state.set(State::Interrupted(reason));
+                inner.remove(listener, false);
+                RegisterResult::Interrupted(reason)
+            }
+
             State::Task(other_task) => {
                 // Only replace the task if it's different.
                 entry.state.set(State::Task({
@@ -159,6 +549,201 @@ impl<T> crate::Inner<T> {
             }
         }
     }
+
+    /// Take the oldest buffered notification of a subscription, or register a task to be
+    /// notified once there is one.
+    ///
+    /// Once the subscription has been detached and its buffer is empty, returns the reason.
+    pub(crate) fn poll_subscription(
+        &self,
+        listener: Pin<&mut Option<Listener<T>>>,
+        task: TaskRef<'_>,
+    ) -> Poll<Result<T, WaitError>> {
+        let mut inner = self.lock();
+        let entry_guard = listener
+            .as_pin_mut()
+            .expect("subscriptions are always inserted")
+            .link
+            .get();
+        // SAFETY: We are locked, so we can access the inner `link`.
+        let entry = unsafe { entry_guard.deref() };
+        let buffer = entry.buffer.as_ref().unwrap();
+
+        let mut tags = buffer.take();
+        let tag = tags.pop_front();
+        buffer.set(tags);
+        if let Some(tag) = tag {
+            #[cfg(feature = "instrument")]
+            inner.stats.polled(&entry.timings);
+            return Poll::Ready(Ok(tag));
+        }
+
+        // An open latched event completes every wait right away.
+        if !entry.is_interrupted() {
+            if let Some(tag) = inner.take_stored(entry) {
+                return Poll::Ready(Ok(tag));
+            }
+        }
+
+        match entry.state.replace(State::Created) {
+            State::Interrupted(reason) => {
+                entry.state.set(State::Interrupted(reason));
+                Poll::Ready(Err(reason))
+            }
+
+            State::Task(other_task) if task.will_wake(other_task.as_task_ref()) => {
+                entry.state.set(State::Task(other_task));
+                Poll::Pending
+            }
+
+            _ => {
+                entry.state.set(State::Task(task.into_task()));
+                Poll::Pending
+            }
+        }
+    }
+
+    /// Get the number of notifications that a subscription has not picked up yet.
+    pub(crate) fn buffered(&self, listener: Option<&Listener<T>>) -> usize {
+        let _lock = self.lock();
+        let entry_guard = match listener {
+            Some(listener) => listener.link.get(),
+            None => return 0,
+        };
+        // SAFETY: We are locked, so we can access the inner `link`.
+        let entry = unsafe { entry_guard.deref() };
+
+        entry.buffer.as_ref().map_or(0, |buffer| {
+            let tags = buffer.take();
+            let len = tags.len();
+            buffer.set(tags);
+            len
+        })
+    }
+
+    /// Inspect the state of a listener.
+    ///
+    /// Returns `None` if the listener isn't inserted.
+    pub(crate) fn with_state<R>(
+        &self,
+        listener: Option<&Listener<T>>,
+        f: impl FnOnce(&State<T>) -> R,
+    ) -> Option<R> {
+        let _lock = self.lock();
+        let entry_guard = listener?.link.get();
+        // SAFETY: We are locked, so we can access the inner `link`.
+        let entry = unsafe { entry_guard.deref() };
+        Some(entry.with_state(f))
+    }
+
+    /// Inspect the state of a listener, unless the list is locked by someone else.
+    ///
+    /// Returns `None` if the list is locked or the listener isn't inserted.
+    pub(crate) fn try_with_state<R>(
+        &self,
+        listener: Option<&Listener<T>>,
+        f: impl FnOnce(&State<T>) -> R,
+    ) -> Option<R> {
+        let _lock = self.list.0.try_lock().ok()?;
+        let entry_guard = listener?.link.get();
+        // SAFETY: We are locked, so we can access the inner `link`.
+        let entry = unsafe { entry_guard.deref() };
+        Some(entry.with_state(f))
+    }
+
+    /// Get the ID of a listener.
+    ///
+    /// Returns `None` if the listener isn't inserted.
+    pub(crate) fn id(&self, listener: Option<&Listener<T>>) -> Option<u64> {
+        let _lock = self.lock();
+        let entry_guard = listener?.link.get();
+        // SAFETY: We are locked, so we can access the inner `link`.
+        let entry = unsafe { entry_guard.deref() };
+        Some(entry.id)
+    }
+
+    /// Detach the listener with the given ID from the list without notifying it.
+    ///
+    /// The listener completes with the given reason the next time it is polled. Listeners that
+    /// have already been notified are left alone. Returns `None` if no listener was detached,
+    /// and the task to wake otherwise.
+    pub(crate) fn interrupt(&self, id: u64, reason: WaitError) -> Option<Option<Task>> {
+        let mut inner = self.lock();
+
+        let mut cursor = inner.head;
+        while let Some(e) = cursor {
+            let entry = unsafe { e.as_ref() };
+            cursor = entry.next.get();
+
+            if entry.id != id {
+                continue;
+            }
+
+            // The notification wins over the interruption.
+            if entry.is_notified() {
+                return None;
+            }
+
+            inner.unlink(entry);
+            return match entry.state.replace(State::Interrupted(reason)) {
+                State::Task(task) => Some(Some(task)),
+                _ => Some(None),
+            };
+        }
+
+        None
+    }
+
+    /// Mark the event as poisoned, and detach every listener that has not been notified.
+    pub(crate) fn poison(&self) {
+        let mut inner = self.lock();
+        inner.poisoned = true;
+        inner.interrupt_unnotified(WaitError::Poisoned);
+    }
+
+    /// Detach every listener that has not been notified.
+    pub(crate) fn drain(&self) -> usize {
+        self.lock().interrupt_unnotified(WaitError::Drained)
+    }
+
+    /// Tell whether the event is closed.
+    pub(crate) fn is_closed(&self) -> bool {
+        self.lock().closed
+    }
+
+    /// Tell whether the event is poisoned.
+    pub(crate) fn is_poisoned(&self) -> bool {
+        self.lock().poisoned
+    }
+
+    /// Clear the poisoned flag of the event.
+    pub(crate) fn clear_poison(&self) {
+        self.lock().poisoned = false;
+    }
+
+    /// Keep notifications that are not received by any listener for future listeners.
+    ///
+    /// A counting event stores the leftover units of every notification. A latched event
+    /// stays open after being notified, until it is reset.
+    pub(crate) fn set_store(&self, latched: bool, tag: fn() -> T) {
+        self.lock().store = Some(Store {
+            latched,
+            count: 0,
+            tag,
+        });
+    }
+
+    /// Get the number of stored notifications.
+    pub(crate) fn stored(&self) -> usize {
+        self.lock().store.as_ref().map_or(0, |store| store.count)
+    }
+
+    /// Drop all stored notifications.
+    pub(crate) fn reset(&self) {
+        if let Some(store) = &mut self.lock().store {
+            store.count = 0;
+        }
+    }
 }
 
 impl<T> Inner<T> {
@@ -170,6 +755,226 @@ impl<T> Inner<T> {
         let entry_guard = listener.as_mut().as_pin_mut()?.link.get();
         let entry = unsafe { entry_guard.deref() };
 
+        // Interrupted entries have already been unlinked.
+        let detached = entry.is_interrupted();
+        if !detached {
+            self.unlink(entry);
+        }
+
+        // The entry is now fully unlinked, so we can now take it out safely.
+        let entry = unsafe {
+            listener
+                .get_unchecked_mut()
+                .take()
+                .unwrap()
+                .link
+                .into_inner()
+        };
+
+        // This State::Created is immediately dropped and exists as a workaround for the absence of
+        // loom::cell::Cell::into_inner. The intent is `let mut state = entry.state.into_inner();`
+        //
+        // refs: https://github.com/tokio-rs/loom/pull/341
+        let mut state = entry.state.replace(State::Created);
+
+        // The notifications that a subscription did not pick up are not delivered.
+        if let Some(buffer) = &entry.buffer {
+            for tag in buffer.take() {
+                self.drop_tag(tag);
+            }
+        }
+
+        if detached {
+            return Some(state);
+        }
+
+        // The listener is done with its notification, whether it received it or not.
+        if let Some(ack) = entry.ack.replace(None) {
+            ack.release(matches!(state, State::NotifiedTaken));
+        }
+
+        // Update the notified count.
+        if state.is_notified() {
+            self.notified -= 1;
+            self.notified_weight -= entry.weight as u128;
+
+            let state = mem::replace(&mut state, State::NotifiedTaken);
+            if let State::Notified { additional, tag } = state {
+                // A latched event is still open, so there is no need to pass the notification on.
+                if propagate && !self.store.as_ref().map_or(false, |store| store.latched) {
+                    match entry.clone_tag {
+                        // The units may be spread across several listeners, so clone the tag.
+                        Some(clone_tag) if entry.weight > 1 => {
+                            let tags = || clone_tag(&tag);
+                            let (count, _) =
+                                self.notify(GenericNotify::new(entry.weight, additional, tags));
+                            if count == 0 {
+                                self.drop_tag(tag);
+                            }
+                        }
+
+                        _ => {
+                            let mut tag = Some(tag);
+                            let tags = || tag.take().expect("tag already taken");
+                            self.notify(GenericNotify::new(entry.weight, additional, tags));
+                            if let Some(tag) = tag {
+                                self.drop_tag(tag);
+                            }
+                        }
+                    }
+                } else {
+                    self.drop_tag(tag);
+                }
+            }
+        }
+
+        Some(state)
+    }
+
+    /// Get the next tag of a notification.
+    ///
+    /// Returns `None` if there are no tags left, or if producing the tag panicked. The panic is
+    /// resumed once the lock is released, so that the notification ends like one whose tags
+    /// ran out.
+    fn next_tag<N: Notification<Tag = T>>(&mut self, notify: &mut N) -> Option<T> {
+        self.catch_tag(|| notify.try_next_tag(Internal::new()))
+    }
+
+    /// Produce a tag, catching a panic like [`ListLock::next_tag()`] does.
+    fn catch_tag(&mut self, f: impl FnOnce() -> Option<T>) -> Option<T> {
+        if self.panic.is_some() {
+            return None;
+        }
+
+        match panic::catch_unwind(AssertUnwindSafe(f)) {
+            Ok(tag) => tag,
+            Err(payload) => {
+                self.panic = Some(payload);
+                None
+            }
+        }
+    }
+
+    /// Hand a tag that no listener received to the `undelivered` function, if there is one.
+    fn drop_tag(&mut self, tag: T) {
+        if self.undelivered.is_some() {
+            self.dropped.push(tag);
+        }
+    }
+
+    /// Hand the tag of a notification that reached no listener to the `undelivered` function.
+    fn drop_unused<N: Notification<Tag = T>>(&mut self, notify: &mut N) {
+        if self.undelivered.is_some() {
+            if let Some(tag) = self.catch_tag(|| notify.unused_tag(Internal::new())) {
+                self.dropped.push(tag);
+            }
+        }
+    }
+
+    /// Take a stored notification for the given entry, if there is one that it accepts.
+    fn take_stored(&mut self, entry: &Link<T>) -> Option<T> {
+        let store = self.store.as_ref()?;
+
+        if store.latched {
+            return (store.count > 0)
+                .then(store.tag)
+                .filter(|tag| entry.accepts(tag));
+        }
+        if store.count < entry.weight {
+            return None;
+        }
+        let tag = Some((store.tag)()).filter(|tag| entry.accepts(tag))?;
+
+        // Older listeners that are still waiting for enough units go first.
+        let entry = NonNull::from(entry);
+        let older_waiting = core::iter::successors(self.next, |e| unsafe { e.as_ref() }.next.get())
+            .take_while(|&e| e != entry)
+            .any(|e| {
+                let older = unsafe { e.as_ref() };
+                !older.is_notified() && older.accepts(&tag)
+            });
+        if older_waiting {
+            return None;
+        }
+
+        self.store.as_mut().unwrap().count -= unsafe { entry.as_ref() }.weight;
+        Some(tag)
+    }
+
+    /// Move the notifications of the subscriptions that were notified into their buffers.
+    ///
+    /// Each subscription is then listening again, behind every listener that is waiting already,
+    /// as if it had registered right after its notification. On a counting event, it takes the
+    /// units that are still stored as well. Subscriptions of a closed or
+    /// poisoned event are detached instead, once their notification is buffered.
+    fn rearm(&mut self) {
+        for e in mem::take(&mut self.rearm) {
+            // SAFETY: Subscriptions are only removed from the list by their owner, which cannot
+            // happen between being notified and this call, since the list stays locked.
+            let entry = unsafe { e.as_ref() };
+            let buffer = entry.buffer.as_ref().unwrap();
+
+            let tag = match entry.state.replace(State::Created) {
+                State::Notified { tag, .. } => tag,
+                state => {
+                    entry.state.set(state);
+                    continue;
+                }
+            };
+            let mut tags = buffer.take();
+            tags.push_back(tag);
+
+            // A waiting subscription leaves no units stored on a counting event. A latched event
+            // is handled when the subscription is polled instead, since it stays open.
+            self.unlink(entry);
+            if self.store.as_ref().map_or(false, |store| !store.latched) {
+                while let Some(tag) = self.take_stored(entry) {
+                    tags.push_back(tag);
+                }
+            }
+            buffer.set(tags);
+
+            // The subscription is done with this notification.
+            if let Some(ack) = entry.ack.replace(None) {
+                ack.release(true);
+            }
+            self.notified -= 1;
+            self.notified_weight -= entry.weight as u128;
+
+            if self.poisoned {
+                entry.state.set(State::Interrupted(WaitError::Poisoned));
+            } else if self.closed {
+                entry.state.set(State::Interrupted(WaitError::Closed));
+            } else {
+                self.link_back(entry);
+            }
+        }
+    }
+
+    /// Link an unnotified entry in at the end of the list.
+    fn link_back(&mut self, entry: &Link<T>) {
+        entry.prev.set(self.tail);
+        entry.next.set(None);
+        match self.tail.replace(entry.into()) {
+            None => self.head = Some(entry.into()),
+            Some(t) => unsafe { t.as_ref().next.set(Some(entry.into())) },
+        }
+
+        if self.next.is_none() {
+            self.next = self.tail;
+        }
+
+        self.len += 1;
+        if entry.group.is_some() {
+            self.grouped += 1;
+        }
+        if entry.filter.is_some() {
+            self.filtered += 1;
+        }
+    }
+
+    /// Unlink an entry from the list.
+    fn unlink(&mut self, entry: &Link<T>) {
         let prev = entry.prev.get();
         let next = entry.next.get();
 
@@ -191,94 +996,417 @@ impl<T> Inner<T> {
 
         // If this was the first unnotified entry, update the next pointer.
         if self.next == Some(entry.into()) {
-            self.next = next;
+            self.next = first_unnotified(next);
         }
 
-        // The entry is now fully unlinked, so we can now take it out safely.
-        let entry = unsafe {
-            listener
-                .get_unchecked_mut()
-                .take()
-                .unwrap()
-                .link
-                .into_inner()
+        self.len -= 1;
+        if entry.group.is_some() {
+            self.grouped -= 1;
+        }
+        if entry.filter.is_some() {
+            self.filtered -= 1;
+        }
+    }
+
+    /// The state of the list, as seen by a notification.
+    fn context(&self) -> NotifyContext {
+        NotifyContext::new(self.len - self.notified, self.notified)
+    }
+
+    /// Find the listener to notify with the given tag, starting with the selected one.
+    ///
+    /// The listeners after the selected one are tried in order, then the ones before it.
+    fn find_accepting(&self, selected: NonNull<Link<T>>, tag: &T) -> Option<NonNull<Link<T>>> {
+        let accepting = |e: &NonNull<Link<T>>| {
+            let entry = unsafe { e.as_ref() };
+            !entry.is_notified() && entry.accepts(tag)
         };
 
-        // This State::Created is immediately dropped and exists as a workaround for the absence of
-        // loom::cell::Cell::into_inner. The intent is `let mut state = entry.state.into_inner();`
-        //
-        // refs: https://github.com/tokio-rs/loom/pull/341
-        let mut state = entry.state.replace(State::Created);
+        let after = core::iter::successors(Some(selected), |e| unsafe { e.as_ref() }.next.get());
+        let before = core::iter::successors(self.next, |e| unsafe { e.as_ref() }.next.get())
+            .take_while(|&e| e != selected);
+        after.chain(before).find(accepting)
+    }
 
-        // Update the notified count.
-        if state.is_notified() {
-            self.notified -= 1;
+    /// Notifies all unnotified entries, regardless of their weight and group.
+    fn notify_all(&mut self, additional: bool, mut tags: impl FnMut() -> T) -> usize {
+        let mut count = 0;
 
-            if propagate {
-                let state = mem::replace(&mut state, State::NotifiedTaken);
-                if let State::Notified { additional, tag } = state {
-                    let tags = {
-                        let mut tag = Some(tag);
-                        move || tag.take().expect("tag already taken")
-                    };
-                    self.notify(GenericNotify::new(1, additional, tags));
+        // Open a latched event.
+        if let Some(store) = &mut self.store {
+            if store.latched {
+                store.count = 1;
+            }
+        }
+
+        let mut cursor = self.next.take();
+        while let Some(e) = cursor {
+            let entry = unsafe { e.as_ref() };
+            cursor = entry.next.get();
+
+            // With groups, listeners after the first unnotified one may be notified already.
+            if entry.is_notified() {
+                continue;
+            }
+
+            // Once producing a tag panics, the remaining listeners stay unnotified.
+            let tag = match self.catch_tag(|| Some(tags())) {
+                Some(tag) => tag,
+                None => {
+                    if self.next.is_none() {
+                        self.next = Some(e);
+                    }
+                    break;
+                }
+            };
+
+            // Listeners that reject their tag stay unnotified.
+            if !entry.accepts(&tag) {
+                if self.next.is_none() {
+                    self.next = Some(e);
                 }
+                continue;
             }
+
+            if let State::Task(task) = entry.state.replace(State::Notified { additional, tag }) {
+                self.wake.push(task);
+            }
+            #[cfg(feature = "instrument")]
+            self.stats.notified(&entry.timings);
+            if entry.buffer.is_some() {
+                self.rearm.push(e);
+            }
+
+            self.notified += 1;
+            self.notified_weight += entry.weight as u128;
+            count += 1;
         }
-        self.len -= 1;
 
-        Some(state)
+        count
+    }
+
+    /// Detaches every unnotified listener, which then completes with the given error.
+    ///
+    /// Returns the number of detached listeners.
+    fn interrupt_unnotified(&mut self, reason: WaitError) -> usize {
+        let mut count = 0;
+
+        let mut cursor = self.head;
+        while let Some(e) = cursor {
+            let entry = unsafe { e.as_ref() };
+            cursor = entry.next.get();
+
+            if entry.is_notified() {
+                continue;
+            }
+
+            self.unlink(entry);
+            if let State::Task(task) = entry.state.replace(State::Interrupted(reason)) {
+                self.wake.push(task);
+            }
+            count += 1;
+        }
+
+        count
     }
 
+    /// Notifies up to `n` unnotified entries of a class, oldest first.
+    fn notify_class(
+        &mut self,
+        class: ListenerClass,
+        mut n: usize,
+        tags: &mut impl FnMut() -> T,
+    ) -> usize {
+        let mut count = 0;
+
+        let mut cursor = self.next;
+        while let Some(e) = cursor {
+            if n == 0 {
+                break;
+            }
+
+            let entry = unsafe { e.as_ref() };
+            cursor = entry.next.get();
+
+            if entry.class != class || entry.is_notified() {
+                continue;
+            }
+
+            // Listeners that reject their tag stay unnotified.
+            let tag = match self.catch_tag(|| Some(tags())) {
+                Some(tag) => tag,
+                None => break,
+            };
+            if !entry.accepts(&tag) {
+                continue;
+            }
+
+            if let State::Task(task) = entry.state.replace(State::Notified {
+                additional: true,
+                tag,
+            }) {
+                self.wake.push(task);
+            }
+            #[cfg(feature = "instrument")]
+            self.stats.notified(&entry.timings);
+            if entry.buffer.is_some() {
+                self.rearm.push(e);
+            }
+
+            self.notified += 1;
+            self.notified_weight += entry.weight as u128;
+            count += 1;
+            n -= 1;
+        }
+
+        // The oldest unnotified listener may have been notified.
+        self.next = first_unnotified(self.next);
+        count
+    }
+
+    /// Select the next listener to notify.
+    ///
+    /// With groups, this visits every unnotified listener.
+    fn select(&self) -> Option<NonNull<Link<T>>> {
+        if self.grouped == 0 {
+            // Without groups, just notify the oldest unnotified listener.
+            return self.next;
+        }
+
+        // Find the oldest listener of the group after the last notified one, wrapping around to
+        // the lowest group if there is no such group.
+        let mut lowest: Option<(Option<usize>, NonNull<Link<T>>)> = None;
+        let mut after_last: Option<(Option<usize>, NonNull<Link<T>>)> = None;
+
+        let mut cursor = self.next;
+        while let Some(e) = cursor {
+            let entry = unsafe { e.as_ref() };
+            cursor = entry.next.get();
+
+            if entry.is_notified() {
+                continue;
+            }
+
+            // Strict comparisons keep the oldest listener of every group.
+            let group = entry.group;
+            if lowest.map_or(true, |(lowest, _)| group < lowest) {
+                lowest = Some((group, e));
+            }
+            if self.last_group.map_or(true, |last| group > last)
+                && after_last.map_or(true, |(after_last, _)| group < after_last)
+            {
+                after_last = Some((group, e));
+            }
+        }
+
+        after_last.or(lowest).map(|(_, e)| e)
+    }
+
+    /// Notifies a number of listeners.
+    ///
+    /// Returns the number of notified listeners and the number of units that were left over.
+    fn notify(&mut self, notify: impl Notification<Tag = T>) -> (usize, usize) {
+        self.notify_acked(notify, None)
+    }
+
+    /// Notifies a number of listeners, and hands them the given acknowledgement.
+    ///
+    /// Returns the number of notified listeners and the number of units that were left over.
     #[cold]
-    fn notify(&mut self, mut notify: impl Notification<Tag = T>) -> usize {
-        let mut n = notify.count(Internal::new());
+    fn notify_acked(
+        &mut self,
+        mut notify: impl Notification<Tag = T>,
+        ack: Option<&Arc<Ack>>,
+    ) -> (usize, usize) {
+        let mut n = notify.count_in(&self.context(), Internal::new());
         let is_additional = notify.is_additional(Internal::new());
 
+        // Opening a latched event wakes up every listener.
+        let mut stored = 0;
+        if let Some(store) = &mut self.store {
+            if store.latched {
+                if n == 0 {
+                    self.drop_unused(&mut notify);
+                    return (0, 0);
+                }
+                store.count = 1;
+                n = usize::MAX;
+            } else {
+                stored = store.count;
+            }
+        }
+
         if !is_additional {
-            if n < self.notified {
-                return 0;
+            // Stored units count as notified listeners.
+            let outstanding = self.notified_weight + stored as u128;
+            if (n as u128) < outstanding {
+                self.drop_unused(&mut notify);
+                return (0, 0);
             }
-            n -= self.notified;
+            n -= outstanding as usize;
         }
 
-        let original_count = n;
-        while n > 0 {
-            n -= 1;
+        // Stored units go to the waiting listeners first, so that a weighted listener that
+        // needs more units than were stored is not overtaken by newer listeners.
+        if let Some(store) = &mut self.store {
+            if !store.latched {
+                n = n.saturating_add(mem::take(&mut store.count));
+            }
+        }
 
-            // Notify the next entry.
-            match self.next {
-                None => return original_count - n - 1,
-
-                Some(e) => {
-                    // Get the entry and move the pointer forwards.
-                    let entry = unsafe { e.as_ref() };
-                    self.next = entry.next.get();
-
-                    // Set the state to `Notified` and notify.
-                    let tag = notify.next_tag(Internal::new());
-                    if let State::Task(task) = entry.state.replace(State::Notified {
-                        additional: is_additional,
-                        tag,
-                    }) {
-                        task.wake();
+        let mut count = 0;
+        let mut pending = None;
+        let mut rejected = 0;
+        let mut misses = 0;
+        'notify: while let Some(mut e) = self.select() {
+            // With filters, the tag decides which listener gets it. A tag that nobody accepts
+            // uses up one unit and is handed back. The next tag is only tried if it may differ,
+            // and is retried at most as often in a row as there are filtered listeners, so that
+            // a large count does not keep the list locked while every tag is rejected.
+            if self.filtered > 0 {
+                loop {
+                    if n == 0 {
+                        break 'notify;
                     }
+                    let tag = match pending.take() {
+                        Some(tag) => tag,
+                        None => match self.next_tag(&mut notify) {
+                            Some(tag) => tag,
+                            None => break 'notify,
+                        },
+                    };
 
-                    // Bump the notified count.
-                    self.notified += 1;
+                    match self.find_accepting(e, &tag) {
+                        Some(accepting) => {
+                            e = accepting;
+                            pending = Some(tag);
+                            misses = 0;
+                            break;
+                        }
+                        None => {
+                            if let Some(tag) = notify.reject_tag(tag, Internal::new()) {
+                                self.drop_tag(tag);
+                            }
+                            n -= 1;
+                            rejected += 1;
+                            misses += 1;
+                            if !notify.tags_vary(Internal::new()) || misses > self.filtered {
+                                break 'notify;
+                            }
+                        }
+                    }
                 }
             }
+
+            // Get the entry and make sure we have enough units left to notify it.
+            let entry = unsafe { e.as_ref() };
+            if entry.weight > n {
+                break;
+            }
+
+            // Stop once an iterator of tags runs out.
+            let tag = match pending.take() {
+                Some(tag) => tag,
+                None => match self.next_tag(&mut notify) {
+                    Some(tag) => tag,
+                    None => break,
+                },
+            };
+            n -= entry.weight;
+
+            // Set the state to `Notified` and notify.
+            if let State::Task(task) = entry.state.replace(State::Notified {
+                additional: is_additional,
+                tag,
+            }) {
+                self.wake.push(task);
+            }
+            #[cfg(feature = "instrument")]
+            self.stats.notified(&entry.timings);
+            if entry.buffer.is_some() {
+                self.rearm.push(e);
+            }
+
+            // The notifier waits until this listener is done with the notification.
+            if let Some(ack) = ack {
+                ack.acquire();
+                entry.ack.set(Some(ack.clone()));
+            }
+
+            // Bump the notified count.
+            self.notified += 1;
+            self.notified_weight += entry.weight as u128;
+            count += 1;
+
+            // Move the pointer forwards.
+            if self.next == Some(e) {
+                self.next = first_unnotified(entry.next.get());
+            }
+            self.last_group = Some(entry.group);
         }
 
-        original_count - n
+        // A tag that was taken for a listener that could not be notified is not lost.
+        if let Some(tag) = pending {
+            if let Some(tag) = notify.reject_tag(tag, Internal::new()) {
+                self.drop_tag(tag);
+            }
+        } else if count == 0 && rejected == 0 {
+            self.drop_unused(&mut notify);
+        }
+
+        // Keep the units that no listener took.
+        if let Some(store) = &mut self.store {
+            if !store.latched {
+                store.count = store.count.saturating_add(n);
+            }
+            n = 0;
+        }
+
+        (count, n + rejected)
+    }
+}
+
+/// Find the first unnotified listener, starting at the given one.
+fn first_unnotified<T>(mut cursor: Option<NonNull<Link<T>>>) -> Option<NonNull<Link<T>>> {
+    while let Some(e) = cursor {
+        let entry = unsafe { e.as_ref() };
+        if !entry.is_notified() {
+            break;
+        }
+        cursor = entry.next.get();
     }
+
+    cursor
 }
 
 struct ListLock<'a, 'b, T> {
-    lock: MutexGuard<'a, Inner<T>>,
+    lock: ManuallyDrop<MutexGuard<'a, Inner<T>>>,
     inner: &'b crate::Inner<T>,
 }
 
+impl<T> ListLock<'_, '_, T> {
+    /// Notifies a number of entries, and then the parent of the event.
+    fn notify_and_forward(
+        mut self,
+        notify: impl Notification<Tag = T>,
+        ack: Option<&Arc<Ack>>,
+    ) -> (usize, usize) {
+        let parent = match self.parent.clone() {
+            Some(parent) => parent,
+            None => return self.notify_acked(notify, ack),
+        };
+
+        let count = notify.count_in(&self.context(), Internal::new());
+        let additional = notify.is_additional(Internal::new());
+        let result = self.notify_acked(notify, ack);
+
+        // Notify the parent without holding the lock.
+        drop(self);
+        parent(count, additional);
+        result
+    }
+}
+
 impl<T> Deref for ListLock<'_, '_, T> {
     type Target = Inner<T>;
 
@@ -295,16 +1423,82 @@ impl<T> DerefMut for ListLock<'_, '_, T> {
 
 impl<T> Drop for ListLock<'_, '_, T> {
     fn drop(&mut self) {
-        let list = &mut **self;
+        // Deliver the notifications that interrupt handlers left while the list was locked.
+        let delivered = self.inner.mailbox.take().map(|notifications| {
+            notifications.map(|notify| {
+                let count = notify.count(Internal::new());
+                let additional = notify.is_additional(Internal::new());
+                self.notify(notify);
+                (count, additional)
+            })
+        });
 
-        // Update the notified count.
-        let notified = if list.notified < list.len {
-            list.notified
-        } else {
-            usize::MAX
+        let list = &mut **self;
+        list.rearm();
+
+        // Update the notified count. Stored notifications count as notified listeners, and
+        // events that store notifications always need to be locked to be notified.
+        let notified = match &list.store {
+            Some(store) => list
+                .notified
+                .saturating_add(store.count)
+                .min(usize::MAX - 1),
+            None if list.notified < list.len => list.notified,
+
+            // Events with a parent always need to be locked to be notified, so that the parent
+            // is notified too.
+            None if list.parent.is_some() => usize::MAX - 1,
+            None => usize::MAX,
         };
 
         self.inner.notified.store(notified, Ordering::Release);
+
+        // Wake the notified listeners without holding the lock, so that they do not have to wait
+        // for it as soon as they wake up.
+        let wake = mem::take(&mut self.wake);
+        let forward = delivered.and_then(|delivered| Some((self.parent.clone()?, delivered)));
+        let dropped = mem::take(&mut self.dropped);
+        let panic = self.panic.take();
+        let undelivered = match dropped.is_empty() {
+            true => None,
+            false => self.undelivered.clone(),
+        };
+
+        // SAFETY: The guard is not used after this.
+        unsafe { ManuallyDrop::drop(&mut self.lock) };
+        let rest = wake.wake();
+
+        // Hand the buffer back for the next notification, unless the list is locked again.
+        if rest.capacity() > 0 {
+            if let Ok(mut list) = self.inner.list.0.try_lock() {
+                if list.wake.rest.capacity() == 0 {
+                    list.wake.rest = rest;
+                }
+            }
+        }
+
+        // Notify the parent of the delivered notifications without holding the lock.
+        if let Some((parent, delivered)) = forward {
+            for (count, additional) in delivered {
+                parent(count, additional);
+            }
+        }
+
+        // A notification may have been left in the mailbox after it was emptied.
+        if !self.inner.mailbox.is_empty() {
+            self.inner.deliver_mailbox();
+        }
+
+        if let Some(undelivered) = undelivered {
+            dropped.into_iter().for_each(|tag| undelivered(tag));
+        }
+
+        // Pass a panic from a tag on to the notifier, unless it is already unwinding.
+        if let Some(payload) = panic {
+            if !thread::panicking() {
+                panic::resume_unwind(payload);
+            }
+        }
     }
 }
 
@@ -329,6 +1523,60 @@ struct Link<T> {
 
     /// The next link in the linked list.
     next: Cell<Option<NonNull<Link<T>>>>,
+
+    /// The number of notification units this listener consumes.
+    weight: usize,
+
+    /// Clones the tag when this listener's notification is forwarded to several listeners.
+    clone_tag: Option<fn(&T) -> T>,
+
+    /// The group that this listener belongs to, if any.
+    group: Option<usize>,
+
+    /// Decides which tags this listener accepts.
+    filter: Option<Filter<T>>,
+
+    /// The class of this listener.
+    class: ListenerClass,
+
+    /// The ID of this listener.
+    id: u64,
+
+    /// Tracks when the listener is done with its notification, if the notifier asked for it.
+    ack: Cell<Option<Arc<Ack>>>,
+
+    /// The notifications that a subscription has not picked up yet, or `None` for a listener
+    /// that completes with its first notification.
+    buffer: Option<Cell<VecDeque<T>>>,
+
+    /// When the listener was created and notified.
+    #[cfg(feature = "instrument")]
+    timings: Timings,
+}
+
+impl<T> Link<T> {
+    /// Inspect the current state of this listener.
+    fn with_state<R>(&self, f: impl FnOnce(&State<T>) -> R) -> R {
+        let state = self.state.replace(State::NotifiedTaken);
+        let result = f(&state);
+        self.state.set(state);
+        result
+    }
+
+    /// Tell whether this listener has been notified.
+    fn is_notified(&self) -> bool {
+        self.with_state(State::is_notified)
+    }
+
+    /// Tell whether this listener accepts the given tag.
+    fn accepts(&self, tag: &T) -> bool {
+        self.filter.as_ref().map_or(true, |filter| filter(tag))
+    }
+
+    /// Tell whether this listener has been detached from the list.
+    fn is_interrupted(&self) -> bool {
+        self.with_state(|state| matches!(state, State::Interrupted(_)))
+    }
 }
 
 #[cfg(test)]
@@ -0,0 +1,129 @@
+//! Listeners that stay registered and buffer their notifications.
+
+use crate::{EventListener, WaitError};
+
+use core::fmt;
+use core::future::Future;
+use core::pin::Pin;
+use core::task::{Context, Poll};
+
+/// A listener that stays registered with an [`Event`] and receives every notification.
+///
+/// This is returned by [`Event::subscribe()`]. Unlike an [`EventListener`], a subscription does
+/// not complete with its first notification. Every notification that selects it is stored in a
+/// buffer, and [`Subscription::next()`] returns them one at a time, oldest first, along with
+/// their tags. Since the subscription is listening the whole time, notifications that arrive
+/// while its owner is busy are not lost, and a stream of notifications does not need to create a
+/// new listener, and check its condition again, after every one of them.
+///
+/// A subscription never counts as notified, so every notification that reaches it adds to the
+/// buffer, even if the buffer already holds notifications. After each notification, it moves
+/// behind the listeners that are already waiting, as if it had registered again right away.
+///
+/// On a [counting](crate::Event::counting) event, a subscription takes every stored unit as a
+/// notification of its own. On an open [latched](crate::Event::latched) event, every call to
+/// [`Subscription::next()`] completes right away, like every wait does.
+///
+/// Once the event is [closed](crate::Event::notify_all_and_close),
+/// [poisoned](crate::Event::poison_guard) or [drained](crate::Event::drain), the subscription is
+/// removed from the event. [`Subscription::next()`] then returns the buffered notifications,
+/// followed by the [`WaitError`].
+///
+/// Dropping a subscription drops its buffered notifications. Their tags are handed to the
+/// [undelivered tag callback](crate::Event::on_undelivered), but they are not passed on to other
+/// listeners.
+///
+/// [`Event`]: crate::Event
+/// [`Event::subscribe()`]: crate::Event::subscribe
+pub struct Subscription<T = ()> {
+    /// The listener, which stays in the list of the event.
+    listener: EventListener<T>,
+}
+
+impl<T> fmt::Debug for Subscription<T> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("Subscription")
+            .field("buffered", &self.buffered())
+            .finish_non_exhaustive()
+    }
+}
+
+impl<T> Subscription<T> {
+    /// Wrap a listener that was registered as a subscription.
+    pub(crate) fn new(listener: EventListener<T>) -> Self {
+        Self { listener }
+    }
+
+    /// Waits for the next notification.
+    ///
+    /// The returned future resolves right away if a notification is buffered. Dropping it does
+    /// not lose any notifications.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use event_listener::{Event, IntoNotification};
+    ///
+    /// let event = Event::<i32>::with_tag();
+    /// let mut subscription = event.subscribe();
+    ///
+    /// // Both notifications are kept, even though nobody is waiting.
+    /// event.notify(1.tag(1));
+    /// event.notify(1.tag(2));
+    ///
+    /// # futures_lite::future::block_on(async {
+    /// assert_eq!(subscription.next().await, Ok(1));
+    /// assert_eq!(subscription.next().await, Ok(2));
+    /// # });
+    /// ```
+    #[inline]
+    #[allow(clippy::should_implement_trait)] // This is the async counterpart of `Iterator::next`.
+    pub fn next(&mut self) -> NextNotification<'_, T> {
+        NextNotification { subscription: self }
+    }
+
+    /// Polls for the next notification.
+    ///
+    /// This is the poll-based version of [`Subscription::next()`], for use in hand-written
+    /// futures and streams.
+    pub fn poll_next(&mut self, cx: &mut Context<'_>) -> Poll<Result<T, WaitError>> {
+        self.listener.listener_mut().poll_subscription(cx)
+    }
+
+    /// Blocks until the next notification.
+    ///
+    /// This is the blocking version of [`Subscription::next()`].
+    #[cfg(not(target_family = "wasm"))]
+    pub fn next_blocking(&mut self) -> Result<T, WaitError> {
+        crate::set::block_on(|cx| self.poll_next(cx))
+    }
+
+    /// Returns the number of notifications that have been received but not returned by
+    /// [`Subscription::next()`] yet.
+    pub fn buffered(&self) -> usize {
+        self.listener.listener().buffered()
+    }
+}
+
+/// The future returned by [`Subscription::next()`].
+#[must_use = "futures do nothing unless you `.await` or poll them"]
+pub struct NextNotification<'a, T> {
+    subscription: &'a mut Subscription<T>,
+}
+
+impl<T> fmt::Debug for NextNotification<'_, T> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("NextNotification")
+            .field("subscription", &self.subscription)
+            .finish()
+    }
+}
+
+impl<T> Future for NextNotification<'_, T> {
+    type Output = Result<T, WaitError>;
+
+    #[inline]
+    fn poll(mut self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Self::Output> {
+        self.subscription.poll_next(cx)
+    }
+}
@@ -0,0 +1,567 @@
+//! Test doubles and checkers for code that is built on top of [`Event`].
+//!
+//! This module is only available with the `test-util` feature.
+
+use crate::{Event, EventListener, IntoNotification};
+
+use std::alloc::{GlobalAlloc, Layout, System};
+use std::boxed::Box;
+use std::cell::Cell;
+use std::collections::VecDeque;
+use std::fmt;
+use std::future::Future;
+use std::pin::Pin;
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::sync::{Arc, Mutex};
+use std::task::{Context, Wake, Waker};
+use std::vec::Vec;
+
+/// A notification that has been sent but not delivered yet.
+type Pending<T> = Box<dyn FnOnce(&Event<T>) -> usize + Send>;
+
+/// An [`Event`] whose notifications are only delivered when the test asks for it.
+///
+/// Calling [`MockEvent::notify()`] queues the notification instead of delivering it.
+/// Notifications are delivered one at a time with [`MockEvent::step()`], in the order that they
+/// were sent. This makes it possible to check the state of the code under test in between
+/// wakeups.
+///
+/// # Examples
+///
+/// ```
+/// use event_listener::test_util::MockEvent;
+/// use event_listener::Listener;
+///
+/// let event = MockEvent::new();
+/// let listener = event.listen();
+/// event.assert_listeners(1);
+///
+/// // Nothing is delivered yet.
+/// event.notify(1);
+/// assert_eq!(event.pending(), 1);
+///
+/// // Deliver the notification.
+/// assert_eq!(event.step(), Some(1));
+/// listener.wait();
+/// event.assert_listeners(0);
+/// ```
+pub struct MockEvent<T = ()> {
+    /// The underlying event.
+    event: Event<T>,
+
+    /// Notifications that are not delivered yet.
+    queue: Mutex<VecDeque<Pending<T>>>,
+}
+
+impl<T> fmt::Debug for MockEvent<T> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("MockEvent")
+            .field("event", &self.event)
+            .field("pending", &self.pending())
+            .finish()
+    }
+}
+
+impl Default for MockEvent {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl MockEvent {
+    /// Creates a new `MockEvent`.
+    pub fn new() -> Self {
+        Self::from_event(Event::new())
+    }
+}
+
+impl<T> MockEvent<T> {
+    /// Creates a new `MockEvent` with a tag type.
+    pub fn with_tag() -> Self {
+        Self::from_event(Event::with_tag())
+    }
+
+    /// Wrap the given event.
+    fn from_event(event: Event<T>) -> Self {
+        Self {
+            event,
+            queue: Mutex::new(VecDeque::new()),
+        }
+    }
+
+    /// Returns a guard listening for a notification.
+    ///
+    /// See [`Event::listen()`] for more information.
+    pub fn listen(&self) -> EventListener<T> {
+        self.event.listen()
+    }
+
+    /// Returns the underlying event.
+    ///
+    /// Notifications sent directly to this event are delivered right away.
+    pub fn event(&self) -> &Event<T> {
+        &self.event
+    }
+
+    /// Queues a notification, to be delivered by a later call to [`MockEvent::step()`].
+    pub fn notify<N>(&self, notify: N)
+    where
+        N: IntoNotification<Tag = T>,
+        N::Notify: Send + 'static,
+    {
+        let notify = notify.into_notification();
+        self.queue()
+            .push_back(Box::new(move |event: &Event<T>| event.notify(notify)));
+    }
+
+    /// Delivers the oldest queued notification.
+    ///
+    /// Returns the number of listeners that were notified, or `None` if there was no queued
+    /// notification.
+    pub fn step(&self) -> Option<usize> {
+        // Don't hold the lock while delivering, in case a woken listener sends a notification.
+        let pending = self.queue().pop_front()?;
+        Some(pending(&self.event))
+    }
+
+    /// Delivers queued notifications until there are none left, including any that are queued in
+    /// the meantime.
+    ///
+    /// Returns the total number of listeners that were notified.
+    pub fn run(&self) -> usize {
+        let mut notified = 0;
+        while let Some(count) = self.step() {
+            notified += count;
+        }
+        notified
+    }
+
+    /// Returns the number of queued notifications.
+    pub fn pending(&self) -> usize {
+        self.queue().len()
+    }
+
+    /// Returns the number of listeners that are registered.
+    pub fn total_listeners(&self) -> usize {
+        self.event.total_listeners()
+    }
+
+    /// Asserts that the given number of listeners is registered.
+    ///
+    /// # Panics
+    ///
+    /// Panics if another number of listeners is registered.
+    #[track_caller]
+    pub fn assert_listeners(&self, expected: usize) {
+        let actual = self.total_listeners();
+        assert_eq!(
+            actual, expected,
+            "expected {} registered listeners, found {}",
+            expected, actual
+        );
+    }
+
+    /// Asserts that the given number of notifications is queued.
+    ///
+    /// # Panics
+    ///
+    /// Panics if another number of notifications is queued.
+    #[track_caller]
+    pub fn assert_pending(&self, expected: usize) {
+        let actual = self.pending();
+        assert_eq!(
+            actual, expected,
+            "expected {} queued notifications, found {}",
+            expected, actual
+        );
+    }
+
+    /// Lock the queue of pending notifications.
+    fn queue(&self) -> std::sync::MutexGuard<'_, VecDeque<Pending<T>>> {
+        self.queue.lock().unwrap_or_else(|e| e.into_inner())
+    }
+}
+
+/// A global allocator that counts the allocations made by every thread.
+///
+/// Install it in a test binary with `#[global_allocator]`, and wrap the code that must not
+/// allocate, such as waiting on a [stack listener](crate::listener) or notifying an event, in
+/// [`assert_no_alloc()`]. This catches changes that bring back allocations on those paths.
+///
+/// # Examples
+///
+/// ```
+/// use event_listener::test_util::{assert_no_alloc, CountingAllocator};
+/// use event_listener::Event;
+///
+/// #[global_allocator]
+/// static ALLOCATOR: CountingAllocator = CountingAllocator::new();
+///
+/// let event = Event::new();
+/// let listener = event.listen();
+///
+/// assert_no_alloc(|| event.notify(1));
+/// ```
+#[derive(Debug, Default)]
+pub struct CountingAllocator<A = System> {
+    inner: A,
+}
+
+impl CountingAllocator {
+    /// Creates a counting allocator on top of the [`System`] allocator.
+    pub const fn new() -> Self {
+        Self { inner: System }
+    }
+}
+
+impl<A> CountingAllocator<A> {
+    /// Creates a counting allocator on top of another allocator.
+    pub const fn wrap(inner: A) -> Self {
+        Self { inner }
+    }
+}
+
+unsafe impl<A: GlobalAlloc> GlobalAlloc for CountingAllocator<A> {
+    unsafe fn alloc(&self, layout: Layout) -> *mut u8 {
+        count_allocation();
+        self.inner.alloc(layout)
+    }
+
+    unsafe fn alloc_zeroed(&self, layout: Layout) -> *mut u8 {
+        count_allocation();
+        self.inner.alloc_zeroed(layout)
+    }
+
+    unsafe fn realloc(&self, ptr: *mut u8, layout: Layout, new_size: usize) -> *mut u8 {
+        count_allocation();
+        self.inner.realloc(ptr, layout, new_size)
+    }
+
+    unsafe fn dealloc(&self, ptr: *mut u8, layout: Layout) {
+        self.inner.dealloc(ptr, layout)
+    }
+}
+
+/// Whether a `CountingAllocator` has counted anything yet.
+static INSTALLED: AtomicBool = AtomicBool::new(false);
+
+std::thread_local! {
+    /// The number of allocations made by the current thread.
+    static ALLOCATIONS: Cell<usize> = const { Cell::new(0) };
+}
+
+fn count_allocation() {
+    INSTALLED.store(true, Ordering::Relaxed);
+
+    // The thread-local may already be gone while the thread exits.
+    let _ = ALLOCATIONS.try_with(|count| count.set(count.get() + 1));
+}
+
+/// Returns the number of allocations that the current thread has made through a
+/// [`CountingAllocator`].
+pub fn allocations() -> usize {
+    ALLOCATIONS.with(Cell::get)
+}
+
+/// Runs `f`, and asserts that it did not allocate on the current thread.
+///
+/// Allocations made by other threads in the meantime are not counted.
+///
+/// # Panics
+///
+/// Panics if `f` allocated, or if no [`CountingAllocator`] is installed.
+#[track_caller]
+pub fn assert_no_alloc<R>(f: impl FnOnce() -> R) -> R {
+    let before = allocations();
+    let result = f();
+    let allocated = allocations() - before;
+
+    assert!(
+        INSTALLED.load(Ordering::Relaxed),
+        "`assert_no_alloc` needs a `CountingAllocator` to be installed as the global allocator"
+    );
+    assert_eq!(allocated, 0, "expected no allocations, found {}", allocated);
+    result
+}
+
+/// A task spawned on an [`Interleaver`].
+struct Spawned {
+    /// The future of the task.
+    future: Pin<Box<dyn Future<Output = ()>>>,
+
+    /// Set when the task is woken up.
+    woken: Arc<Flag>,
+}
+
+/// A wake-up flag that can be used as a waker.
+struct Flag(AtomicBool);
+
+impl Wake for Flag {
+    fn wake(self: Arc<Self>) {
+        self.0.store(true, Ordering::SeqCst);
+    }
+
+    fn wake_by_ref(self: &Arc<Self>) {
+        self.0.store(true, Ordering::SeqCst);
+    }
+}
+
+/// Runs tasks on the current thread, in an order picked by a seeded pseudo-random generator.
+///
+/// Each step polls one of the tasks that have been woken up; which one is decided by the seed,
+/// so a failing interleaving can be replayed by running the test again with the same seed.
+/// Running a test under many seeds explores many orders of `notify()` and `listen()` calls in
+/// code built on [`Event`].
+///
+/// [`Interleaver::run()`] checks that no wakeup is lost: it panics if some tasks are still
+/// waiting but none of them has been woken up. Use [`Ledger`] to check that every operation
+/// completes exactly once.
+///
+/// # Examples
+///
+/// ```
+/// use std::rc::Rc;
+/// use event_listener::test_util::Interleaver;
+/// use event_listener::Event;
+///
+/// for seed in 0..100 {
+///     let event = Rc::new(Event::new());
+///     let mut interleaver = Interleaver::new(seed);
+///
+///     let listener = event.listen();
+///     interleaver.spawn(listener);
+///     interleaver.spawn({
+///         let event = event.clone();
+///         async move {
+///             event.notify(1);
+///         }
+///     });
+///
+///     interleaver.run();
+/// }
+/// ```
+pub struct Interleaver {
+    /// The seed the generator started with.
+    seed: u64,
+
+    /// The state of the generator.
+    state: u64,
+
+    /// The tasks that have not completed yet.
+    tasks: Vec<Spawned>,
+
+    /// Whether tasks are also polled without being woken up.
+    spurious: bool,
+
+    /// The number of steps after which `run()` gives up.
+    max_steps: usize,
+}
+
+impl fmt::Debug for Interleaver {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("Interleaver")
+            .field("seed", &self.seed)
+            .field("tasks", &self.tasks.len())
+            .field("spurious", &self.spurious)
+            .finish()
+    }
+}
+
+impl Interleaver {
+    /// Creates an interleaver that picks tasks based on `seed`.
+    pub fn new(seed: u64) -> Self {
+        Self {
+            seed,
+            state: seed,
+            tasks: Vec::new(),
+            spurious: false,
+            max_steps: 100_000,
+        }
+    }
+
+    /// Returns the seed of the interleaver.
+    pub fn seed(&self) -> u64 {
+        self.seed
+    }
+
+    /// Also polls tasks that have not been woken up.
+    ///
+    /// Futures have to cope with being polled at any time, so this checks that a spurious poll
+    /// neither completes a task too early nor loses its wakeup.
+    pub fn spurious_polls(&mut self, enabled: bool) -> &mut Self {
+        self.spurious = enabled;
+        self
+    }
+
+    /// Sets the number of steps after which [`Interleaver::run()`] panics.
+    ///
+    /// This catches tasks that keep waking each other up without making progress. The default
+    /// is 100,000 steps.
+    pub fn max_steps(&mut self, max_steps: usize) -> &mut Self {
+        self.max_steps = max_steps;
+        self
+    }
+
+    /// Adds a task. Every task is woken up once when it is spawned.
+    pub fn spawn<F>(&mut self, future: F) -> &mut Self
+    where
+        F: Future + 'static,
+    {
+        self.tasks.push(Spawned {
+            future: Box::pin(async move {
+                future.await;
+            }),
+            woken: Arc::new(Flag(AtomicBool::new(true))),
+        });
+        self
+    }
+
+    /// Runs the tasks until all of them have completed.
+    ///
+    /// Returns the number of steps that were taken.
+    ///
+    /// # Panics
+    ///
+    /// Panics if some tasks are still waiting but none of them has been woken up, which means
+    /// that a wakeup was lost, or if the tasks did not complete within the maximum number of
+    /// steps. The message contains the seed. Panics from the tasks are passed on.
+    #[track_caller]
+    pub fn run(&mut self) -> usize {
+        let mut steps = 0;
+
+        while !self.tasks.is_empty() {
+            let woken: Vec<usize> = (0..self.tasks.len())
+                .filter(|&i| self.tasks[i].woken.0.load(Ordering::SeqCst))
+                .collect();
+
+            assert!(
+                !woken.is_empty(),
+                "lost wakeup: {} tasks are waiting, but none of them was woken up (seed {})",
+                self.tasks.len(),
+                self.seed
+            );
+            assert!(
+                steps < self.max_steps,
+                "the tasks did not complete within {} steps (seed {})",
+                self.max_steps,
+                self.seed
+            );
+
+            // Pick a woken task, or now and then any task if spurious polls are enabled.
+            let index = if self.spurious && self.next() % 4 == 0 {
+                self.next() as usize % self.tasks.len()
+            } else {
+                woken[self.next() as usize % woken.len()]
+            };
+
+            let task = &mut self.tasks[index];
+            task.woken.0.store(false, Ordering::SeqCst);
+            let waker = Waker::from(task.woken.clone());
+            if task
+                .future
+                .as_mut()
+                .poll(&mut Context::from_waker(&waker))
+                .is_ready()
+            {
+                self.tasks.swap_remove(index);
+            }
+
+            steps += 1;
+        }
+
+        steps
+    }
+
+    /// Returns the next pseudo-random number.
+    fn next(&mut self) -> u64 {
+        // SplitMix64, which works for any seed, including zero.
+        self.state = self.state.wrapping_add(0x9e37_79b9_7f4a_7c15);
+        let mut z = self.state;
+        z = (z ^ (z >> 30)).wrapping_mul(0xbf58_476d_1ce4_e5b9);
+        z = (z ^ (z >> 27)).wrapping_mul(0x94d0_49bb_1331_11eb);
+        z ^ (z >> 31)
+    }
+}
+
+/// Checks that every operation completes exactly once.
+///
+/// Each operation, such as a sent message or an acquired permit, is [started](Ledger::start)
+/// and later [completed](Ledger::complete) with the ID it was given. Completing an operation
+/// twice panics right away, and [`Ledger::assert_all_completed()`] panics if an operation never
+/// completed. The ledger can be shared between tasks of an [`Interleaver`] and between threads.
+///
+/// # Examples
+///
+/// ```
+/// use event_listener::test_util::Ledger;
+///
+/// let ledger = Ledger::new();
+/// let first = ledger.start();
+/// let second = ledger.start();
+///
+/// ledger.complete(second);
+/// ledger.complete(first);
+/// ledger.assert_all_completed();
+/// ```
+#[derive(Debug, Default)]
+pub struct Ledger {
+    /// Whether each started operation has completed.
+    completed: Mutex<Vec<bool>>,
+}
+
+impl Ledger {
+    /// Creates an empty ledger.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Starts an operation, and returns its ID.
+    pub fn start(&self) -> usize {
+        let mut completed = self.completed();
+        completed.push(false);
+        completed.len() - 1
+    }
+
+    /// Completes the operation with the given ID.
+    ///
+    /// # Panics
+    ///
+    /// Panics if the operation was not started, or has already completed.
+    #[track_caller]
+    pub fn complete(&self, id: usize) {
+        let mut completed = self.completed();
+        match completed.get_mut(id) {
+            Some(done) if !*done => *done = true,
+            Some(_) => panic!("operation {} completed twice", id),
+            None => panic!("operation {} was never started", id),
+        }
+    }
+
+    /// Returns the number of operations that have not completed yet.
+    pub fn outstanding(&self) -> usize {
+        self.completed().iter().filter(|done| !**done).count()
+    }
+
+    /// Asserts that every started operation has completed.
+    ///
+    /// # Panics
+    ///
+    /// Panics if an operation has not completed.
+    #[track_caller]
+    pub fn assert_all_completed(&self) {
+        let completed = self.completed();
+        if let Some(id) = completed.iter().position(|done| !*done) {
+            panic!(
+                "operation {} never completed ({} of {} outstanding)",
+                id,
+                completed.iter().filter(|done| !**done).count(),
+                completed.len()
+            );
+        }
+    }
+
+    /// Lock the completion flags.
+    fn completed(&self) -> std::sync::MutexGuard<'_, Vec<bool>> {
+        self.completed.lock().unwrap_or_else(|e| e.into_inner())
+    }
+}
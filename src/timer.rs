@@ -0,0 +1,593 @@
+//! Listeners that give up at a deadline, scheduled and periodic notifications, and the timer
+//! thread that enforces them.
+
+use crate::sync::atomic::{AtomicPtr, Ordering};
+use crate::{EventListener, Inner, Task, WaitError};
+
+use std::collections::{BTreeMap, HashMap};
+use std::panic::{self, AssertUnwindSafe};
+use std::sync::{Condvar, Mutex};
+use std::thread;
+use std::time::{Duration, Instant};
+
+use core::fmt;
+use core::future::Future;
+use core::pin::Pin;
+use core::ptr;
+use core::task::{Context, Poll};
+
+/// A guard waiting for a notification from an [`Event`] until a deadline.
+///
+/// This is returned by [`Event::listen_timeout()`]. It resolves to `Some` with the tag of the
+/// notification, or to `None` once the deadline has passed.
+///
+/// If a notified `TimeoutListener` is dropped without receiving its notification, another
+/// active listener is notified instead, just like with an [`EventListener`].
+///
+/// Like a plain listener, a `TimeoutListener` panics if the event is
+/// [poisoned](crate::Event::poison_guard). Use [`TimeoutListener::checked()`] to get a
+/// [`WaitError`] instead.
+///
+/// [`Event`]: crate::Event
+/// [`Event::listen_timeout()`]: crate::Event::listen_timeout
+pub struct TimeoutListener<T = ()> {
+    /// The underlying listener.
+    listener: EventListener<T>,
+
+    /// The deadline of this listener, or `None` if it can never be reached.
+    deadline: Option<Instant>,
+
+    /// The key of the timer entry, if one was scheduled.
+    timer: Option<TimerKey>,
+}
+
+impl<T> fmt::Debug for TimeoutListener<T> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("TimeoutListener")
+            .field("deadline", &self.deadline)
+            .finish_non_exhaustive()
+    }
+}
+
+impl<T> Unpin for TimeoutListener<T> {}
+
+impl<T: Send> TimeoutListener<T> {
+    /// Wrap the listener with the given ID, and schedule its deadline.
+    pub(crate) fn new(listener: EventListener<T>, id: u64, deadline: Option<Instant>) -> Self {
+        let timer = deadline.map(|deadline| {
+            let inner: &Inner<T> = &listener.listener().event;
+            Timer::get().schedule(
+                deadline,
+                Entry::Timeout {
+                    inner: inner as *const Inner<T> as *const (),
+                    id,
+                    fire: fire::<T>,
+                },
+            )
+        });
+
+        Self {
+            listener,
+            deadline,
+            timer,
+        }
+    }
+}
+
+impl<T> TimeoutListener<T> {
+    /// Returns the deadline of this listener.
+    ///
+    /// This is `None` if the timeout was too large to be represented.
+    #[inline]
+    pub fn deadline(&self) -> Option<Instant> {
+        self.deadline
+    }
+
+    /// Returns the time left until the deadline of this listener.
+    ///
+    /// This is zero once the deadline has passed, and `None` if the deadline could not be
+    /// represented. A caller that waits again after a notification can pass this on, so that
+    /// the total time spent waiting stays within the original budget. Since
+    /// [`wait()`](TimeoutListener::wait) consumes the listener, get the
+    /// [`deadline()`](TimeoutListener::deadline) first when blocking.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use std::time::Duration;
+    /// use event_listener::Event;
+    ///
+    /// let event = Event::new();
+    /// let listener = event.listen_timeout(Duration::from_secs(10));
+    ///
+    /// let remaining = listener.remaining().unwrap();
+    /// assert!(remaining <= Duration::from_secs(10));
+    /// ```
+    #[inline]
+    pub fn remaining(&self) -> Option<Duration> {
+        self.deadline
+            .map(|deadline| deadline.saturating_duration_since(Instant::now()))
+    }
+
+    /// Blocks until a notification is received or the deadline is reached.
+    ///
+    /// Returns `None` if the deadline was reached first.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use std::time::Duration;
+    /// use event_listener::Event;
+    ///
+    /// let event = Event::new();
+    /// let listener = event.listen_timeout(Duration::from_secs(1));
+    ///
+    /// event.notify(1);
+    /// assert_eq!(listener.wait(), Some(()));
+    /// ```
+    pub fn wait(mut self) -> Option<T> {
+        let deadline = self.deadline;
+        crate::expect_uninterrupted(self.listener.listener_mut().wait_internal(deadline))
+    }
+
+    /// Wraps this listener so that it reports why it completed without a notification.
+    ///
+    /// The returned [`CheckedTimeoutListener`] resolves to [`WaitError::TimedOut`] once the
+    /// deadline has passed, and to [`WaitError::Poisoned`] if the event was
+    /// [poisoned](crate::Event::poison_guard), instead of panicking.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use std::time::Duration;
+    /// use event_listener::{Event, WaitError};
+    ///
+    /// let event = Event::new();
+    /// let listener = event.listen_timeout(Duration::from_millis(10)).checked();
+    ///
+    /// assert_eq!(listener.wait(), Err(WaitError::TimedOut));
+    /// ```
+    #[inline]
+    pub fn checked(self) -> CheckedTimeoutListener<T> {
+        CheckedTimeoutListener { listener: self }
+    }
+
+    /// Drops this listener and discards its notification (if any) without notifying another
+    /// active listener.
+    ///
+    /// Returns `true` if a notification was discarded.
+    pub fn discard(mut self) -> bool {
+        self.cancel();
+        self.listener.listener_mut().discard()
+    }
+
+    /// Cancel the timer entry of this listener.
+    fn cancel(&mut self) {
+        if let Some(key) = self.timer.take() {
+            Timer::get().cancel(key);
+        }
+    }
+}
+
+impl<T> Future for TimeoutListener<T> {
+    type Output = Option<T>;
+
+    fn poll(mut self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Self::Output> {
+        match self.listener.listener_mut().poll_checked(cx) {
+            Poll::Ready(Ok(tag)) => Poll::Ready(Some(tag)),
+            Poll::Ready(Err(WaitError::TimedOut)) => Poll::Ready(None),
+            Poll::Ready(Err(err)) => panic!("{}", err),
+            Poll::Pending => Poll::Pending,
+        }
+    }
+}
+
+impl<T> Drop for TimeoutListener<T> {
+    fn drop(&mut self) {
+        // The timer must not touch the event after the listener is gone.
+        self.cancel();
+    }
+}
+
+/// A [`TimeoutListener`] that reports why it completed without a notification.
+///
+/// This is created by [`TimeoutListener::checked()`]. It resolves to `Ok` with the tag of the
+/// notification, or to a [`WaitError`] if the deadline passed or the listener was interrupted.
+pub struct CheckedTimeoutListener<T = ()> {
+    listener: TimeoutListener<T>,
+}
+
+impl<T> fmt::Debug for CheckedTimeoutListener<T> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("CheckedTimeoutListener")
+            .field("deadline", &self.listener.deadline)
+            .finish_non_exhaustive()
+    }
+}
+
+impl<T> Unpin for CheckedTimeoutListener<T> {}
+
+impl<T> CheckedTimeoutListener<T> {
+    /// Returns the deadline of this listener.
+    ///
+    /// See [`TimeoutListener::deadline()`].
+    #[inline]
+    pub fn deadline(&self) -> Option<Instant> {
+        self.listener.deadline()
+    }
+
+    /// Returns the time left until the deadline of this listener.
+    ///
+    /// See [`TimeoutListener::remaining()`].
+    #[inline]
+    pub fn remaining(&self) -> Option<Duration> {
+        self.listener.remaining()
+    }
+
+    /// Blocks until a notification is received, the deadline is reached or the listener is
+    /// interrupted.
+    pub fn wait(mut self) -> Result<T, WaitError> {
+        let deadline = self.listener.deadline;
+        match self
+            .listener
+            .listener
+            .listener_mut()
+            .wait_internal(deadline)
+        {
+            Ok(Some(tag)) => Ok(tag),
+            Ok(None) => Err(WaitError::TimedOut),
+            Err(err) => Err(err),
+        }
+    }
+
+    /// Returns the wrapped listener.
+    #[inline]
+    pub fn into_inner(self) -> TimeoutListener<T> {
+        self.listener
+    }
+}
+
+impl<T> Future for CheckedTimeoutListener<T> {
+    type Output = Result<T, WaitError>;
+
+    #[inline]
+    fn poll(mut self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Self::Output> {
+        self.listener.listener.listener_mut().poll_checked(cx)
+    }
+}
+
+/// A notification that is delivered to an [`Event`] at a deadline.
+///
+/// This is returned by [`Event::notify_at()`] and [`Event::notify_after()`]. Dropping the handle
+/// does not cancel the notification; call [`ScheduledNotify::cancel()`] for that.
+///
+/// [`Event`]: crate::Event
+/// [`Event::notify_at()`]: crate::Event::notify_at
+/// [`Event::notify_after()`]: crate::Event::notify_after
+pub struct ScheduledNotify {
+    /// The deadline of the notification, or `None` if it can never be reached.
+    deadline: Option<Instant>,
+
+    /// The key of the timer entry, if one was scheduled.
+    timer: Option<TimerKey>,
+}
+
+impl fmt::Debug for ScheduledNotify {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("ScheduledNotify")
+            .field("deadline", &self.deadline)
+            .finish_non_exhaustive()
+    }
+}
+
+impl ScheduledNotify {
+    /// Schedule `notify` to run at the deadline.
+    pub(crate) fn new(deadline: Option<Instant>, notify: Box<dyn FnOnce() + Send>) -> Self {
+        let timer = deadline.map(|deadline| Timer::get().schedule(deadline, Entry::Notify(notify)));
+        Self { deadline, timer }
+    }
+
+    /// Returns the deadline of the notification.
+    ///
+    /// This is `None` if the delay was too large to be represented, in which case the
+    /// notification is never delivered.
+    #[inline]
+    pub fn deadline(&self) -> Option<Instant> {
+        self.deadline
+    }
+
+    /// Cancels the notification.
+    ///
+    /// Returns `true` if the notification was cancelled, and `false` if it has already been
+    /// delivered.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use std::time::Duration;
+    /// use event_listener::Event;
+    ///
+    /// let event = Event::new();
+    /// let scheduled = event.notify_after(Duration::from_secs(60), 1);
+    ///
+    /// assert!(scheduled.cancel());
+    /// ```
+    pub fn cancel(self) -> bool {
+        match self.timer {
+            Some(key) => Timer::get().cancel(key),
+            None => true,
+        }
+    }
+}
+
+/// A guard that notifies an [`Event`] periodically until it is dropped.
+///
+/// This is returned by [`Event::tick_every()`].
+///
+/// [`Event`]: crate::Event
+/// [`Event::tick_every()`]: crate::Event::tick_every
+#[must_use = "the ticker stops when it is dropped"]
+pub struct Ticker {
+    /// The time between two ticks.
+    period: Duration,
+
+    /// The sequence number of the ticker's timer entries.
+    seq: u64,
+}
+
+impl fmt::Debug for Ticker {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("Ticker")
+            .field("period", &self.period)
+            .finish_non_exhaustive()
+    }
+}
+
+impl Ticker {
+    /// Schedule `notify` to run every `period`, starting one period from now.
+    pub(crate) fn new(period: Duration, notify: Box<dyn FnMut() + Send>) -> Self {
+        assert!(period > Duration::ZERO, "tick period must be non-zero");
+
+        let seq = Timer::get().schedule_tick(period, notify);
+        Self { period, seq }
+    }
+
+    /// Returns the time between two ticks.
+    #[inline]
+    pub fn period(&self) -> Duration {
+        self.period
+    }
+}
+
+impl Drop for Ticker {
+    fn drop(&mut self) {
+        Timer::get().stop_tick(self.seq);
+    }
+}
+
+/// Detach the listener with the given ID from its event.
+///
+/// # Safety
+///
+/// `inner` must point to a live `Inner<T>`.
+unsafe fn fire<T>(inner: *const (), id: u64) -> Option<Task> {
+    let inner = &*(inner as *const Inner<T>);
+    inner.interrupt(id, WaitError::TimedOut).flatten()
+}
+
+/// The key of an entry in the timer.
+type TimerKey = (Instant, u64);
+
+/// A scheduled timeout or notification.
+enum Entry {
+    /// A listener that gives up at the deadline.
+    Timeout {
+        /// The event that the listener belongs to.
+        inner: *const (),
+
+        /// The ID of the listener.
+        id: u64,
+
+        /// Detach the listener from the event.
+        fire: unsafe fn(*const (), u64) -> Option<Task>,
+    },
+
+    /// A notification made by [`ScheduledNotify`], which keeps its event alive.
+    Notify(Box<dyn FnOnce() + Send>),
+
+    /// The next notification of a [`Ticker`], which keeps its event alive.
+    Tick {
+        /// The time between two ticks.
+        period: Duration,
+
+        /// Notify the event.
+        notify: Box<dyn FnMut() + Send>,
+    },
+}
+
+// SAFETY: The event of a timeout is only accessed through `fire`, which requires the tag to be
+// `Send`.
+unsafe impl Send for Entry {}
+
+/// The global timer, which detaches listeners whose deadline has passed.
+struct Timer {
+    /// The scheduled timeouts.
+    state: Mutex<TimerState>,
+
+    /// Notified when a timeout is scheduled.
+    condvar: Condvar,
+}
+
+struct TimerState {
+    /// The scheduled timeouts, ordered by their deadline.
+    entries: BTreeMap<TimerKey, Entry>,
+
+    /// The deadlines of the next ticks of the running tickers, by their sequence number.
+    ticks: HashMap<u64, Instant>,
+
+    /// The sequence number for the next timeout.
+    next_seq: u64,
+}
+
+impl Timer {
+    /// Get the global timer, starting it if necessary.
+    fn get() -> &'static Timer {
+        static TIMER: AtomicPtr<Timer> = AtomicPtr::new(ptr::null_mut());
+
+        let timer = TIMER.load(Ordering::Acquire);
+        if let Some(timer) = unsafe { timer.as_ref() } {
+            return timer;
+        }
+
+        let new = Box::into_raw(Box::new(Timer {
+            state: Mutex::new(TimerState {
+                entries: BTreeMap::new(),
+                ticks: HashMap::new(),
+                next_seq: 0,
+            }),
+            condvar: Condvar::new(),
+        }));
+
+        match TIMER.compare_exchange(ptr::null_mut(), new, Ordering::AcqRel, Ordering::Acquire) {
+            Ok(_) => {
+                // SAFETY: The timer is never freed.
+                let timer = unsafe { &*new };
+                thread::Builder::new()
+                    .name("event-listener-timer".into())
+                    .spawn(move || timer.run())
+                    .expect("failed to spawn the timer thread");
+                timer
+            }
+
+            Err(existing) => {
+                // Someone else already started the timer.
+                drop(unsafe { Box::from_raw(new) });
+                unsafe { &*existing }
+            }
+        }
+    }
+
+    /// Schedule a timeout.
+    fn schedule(&self, deadline: Instant, entry: Entry) -> TimerKey {
+        let mut state = self.state.lock().unwrap_or_else(|e| e.into_inner());
+        let key = (deadline, state.next_seq);
+        state.next_seq += 1;
+        state.entries.insert(key, entry);
+        self.condvar.notify_one();
+        key
+    }
+
+    /// Schedule a ticker, and return its sequence number.
+    ///
+    /// All entries of the ticker share this sequence number, so that it can be found again.
+    fn schedule_tick(&self, period: Duration, notify: Box<dyn FnMut() + Send>) -> u64 {
+        let mut state = self.state.lock().unwrap_or_else(|e| e.into_inner());
+        let seq = state.next_seq;
+        state.next_seq += 1;
+
+        if let Some(deadline) = Instant::now().checked_add(period) {
+            state.ticks.insert(seq, deadline);
+            state
+                .entries
+                .insert((deadline, seq), Entry::Tick { period, notify });
+            self.condvar.notify_one();
+        }
+
+        seq
+    }
+
+    /// Stop a ticker.
+    ///
+    /// A tick that is being delivered right now still completes.
+    fn stop_tick(&self, seq: u64) {
+        let mut state = self.state.lock().unwrap_or_else(|e| e.into_inner());
+        if let Some(deadline) = state.ticks.remove(&seq) {
+            state.entries.remove(&(deadline, seq));
+        }
+    }
+
+    /// Cancel a timeout, if it has not fired yet.
+    ///
+    /// Returns `false` if it has already fired.
+    fn cancel(&self, key: TimerKey) -> bool {
+        let mut state = self.state.lock().unwrap_or_else(|e| e.into_inner());
+        state.entries.remove(&key).is_some()
+    }
+
+    /// Run the timer loop.
+    fn run(&self) {
+        let mut state = self.state.lock().unwrap_or_else(|e| e.into_inner());
+        let mut tasks = Vec::new();
+        let mut notifications = Vec::new();
+        let mut ticks = Vec::new();
+
+        loop {
+            // Detach all listeners whose deadline has passed. This happens with the lock held, so
+            // that the listeners cannot be dropped in the meantime.
+            let now = Instant::now();
+            while let Some(&key) = state.entries.keys().next() {
+                if key.0 > now {
+                    break;
+                }
+
+                match state.entries.remove(&key).unwrap() {
+                    Entry::Timeout { inner, id, fire } => {
+                        tasks.extend(unsafe { fire(inner, id) });
+                    }
+                    Entry::Notify(notify) => notifications.push(notify),
+                    Entry::Tick { period, notify } => ticks.push((key, period, notify)),
+                }
+            }
+
+            // Wake the tasks and deliver the notifications without holding the lock.
+            if !tasks.is_empty() || !notifications.is_empty() || !ticks.is_empty() {
+                drop(state);
+                tasks.drain(..).for_each(Task::wake);
+                // A panic in a tag or the undelivered tag callback must not stop the timer, or no
+                // deadline would ever be enforced again.
+                for notify in notifications.drain(..) {
+                    let _ = panic::catch_unwind(AssertUnwindSafe(notify));
+                }
+                for (_, _, notify) in &mut ticks {
+                    let _ = panic::catch_unwind(AssertUnwindSafe(notify));
+                }
+                state = self.state.lock().unwrap_or_else(|e| e.into_inner());
+
+                // Schedule the next ticks of the tickers that have not been stopped in the
+                // meantime. Ticks that were missed because the timer fell behind are skipped.
+                let now = Instant::now();
+                let state = &mut *state;
+                for ((deadline, seq), period, notify) in ticks.drain(..) {
+                    let next = match deadline.checked_add(period) {
+                        Some(next) if next > now => Some(next),
+                        _ => now.checked_add(period),
+                    };
+
+                    match (state.ticks.get_mut(&seq), next) {
+                        (Some(current), Some(next)) => {
+                            *current = next;
+                            state
+                                .entries
+                                .insert((next, seq), Entry::Tick { period, notify });
+                        }
+                        _ => {
+                            state.ticks.remove(&seq);
+                        }
+                    }
+                }
+                continue;
+            }
+
+            // Wait until the next deadline.
+            state = match state.entries.keys().next() {
+                Some(&(deadline, _)) => {
+                    self.condvar
+                        .wait_timeout(state, deadline.saturating_duration_since(now))
+                        .unwrap_or_else(|e| e.into_inner())
+                        .0
+                }
+                None => self.condvar.wait(state).unwrap_or_else(|e| e.into_inner()),
+            };
+        }
+    }
+}
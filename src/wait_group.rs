@@ -0,0 +1,195 @@
+//! A wait group built on top of [`Event`].
+//!
+//! A [`WaitGroup`] counts outstanding pieces of work. [`WaitGroup::add()`] raises the counter,
+//! [`WaitGroup::done()`] lowers it, and waiting completes once it is back at zero. The group can
+//! be reused: after the counter reaches zero, adding to it again starts a new round.
+//!
+//! This module is only available with the `std` feature.
+//!
+//! # Examples
+//!
+//! ```
+//! use std::sync::Arc;
+//! use std::thread;
+//! use event_listener::wait_group::WaitGroup;
+//!
+//! let group = Arc::new(WaitGroup::new());
+//!
+//! for _ in 0..4 {
+//!     group.add(1);
+//!     let group = group.clone();
+//!     thread::spawn(move || {
+//!         // Do some work...
+//!         group.done();
+//!     });
+//! }
+//!
+//! group.wait_blocking();
+//! assert_eq!(group.count(), 0);
+//! ```
+
+use crate::{Event, EventListener};
+
+use core::fmt;
+use core::future::Future;
+use core::pin::Pin;
+use core::sync::atomic::{AtomicUsize, Ordering};
+use core::task::{Context, Poll};
+
+/// Waits for a number of pieces of work to be done.
+///
+/// See the [module documentation](self) for more information.
+pub struct WaitGroup {
+    /// The number of pieces of work that are not done yet.
+    count: AtomicUsize,
+
+    /// Notified when the counter reaches zero.
+    event: Event,
+}
+
+impl fmt::Debug for WaitGroup {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("WaitGroup")
+            .field("count", &self.count())
+            .finish()
+    }
+}
+
+impl Default for WaitGroup {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl WaitGroup {
+    /// Creates a wait group with a counter of zero.
+    #[cfg(not(loom))]
+    pub const fn new() -> Self {
+        Self {
+            count: AtomicUsize::new(0),
+            event: Event::new(),
+        }
+    }
+
+    #[cfg(loom)]
+    pub fn new() -> Self {
+        Self {
+            count: AtomicUsize::new(0),
+            event: Event::new(),
+        }
+    }
+
+    /// Adds `n` pieces of work to the counter.
+    ///
+    /// # Panics
+    ///
+    /// Panics if the counter overflows.
+    pub fn add(&self, n: usize) {
+        self.count
+            .fetch_update(Ordering::Relaxed, Ordering::Relaxed, |count| {
+                count.checked_add(n)
+            })
+            .expect("wait group counter overflow");
+    }
+
+    /// Marks one piece of work as done.
+    ///
+    /// Wakes up every waiter if this brings the counter to zero.
+    ///
+    /// # Panics
+    ///
+    /// Panics if the counter is already zero.
+    pub fn done(&self) {
+        // Leave the counter alone on misuse, so that waiters are not stuck behind a wrapped count.
+        let old = self
+            .count
+            .fetch_update(Ordering::AcqRel, Ordering::Acquire, |count| {
+                count.checked_sub(1)
+            })
+            .expect("`WaitGroup::done()` called more often than `add()`");
+
+        if old == 1 {
+            self.event.notify(usize::MAX);
+        }
+    }
+
+    /// Returns the current value of the counter.
+    pub fn count(&self) -> usize {
+        self.count.load(Ordering::Acquire)
+    }
+
+    /// Waits until the counter is zero.
+    ///
+    /// If the counter is already zero, the returned future resolves right away. Otherwise it
+    /// resolves once the counter reaches zero, even if work for the next round has been added
+    /// by the time it is polled.
+    pub fn wait(&self) -> Wait<'_> {
+        Wait {
+            group: self,
+            listener: None,
+        }
+    }
+
+    /// Blocks until the counter is zero.
+    ///
+    /// This is the blocking version of [`WaitGroup::wait()`].
+    #[cfg(not(target_family = "wasm"))]
+    pub fn wait_blocking(&self) {
+        use crate::Listener;
+
+        if self.count() == 0 {
+            return;
+        }
+
+        // Check again after starting to listen, so that the last `done()` is not missed.
+        let listener = self.event.listen();
+        if self.count() != 0 {
+            listener.wait();
+        }
+    }
+}
+
+/// The future returned by [`WaitGroup::wait()`].
+#[must_use = "futures do nothing unless you `.await` or poll them"]
+pub struct Wait<'a> {
+    group: &'a WaitGroup,
+    listener: Option<EventListener>,
+}
+
+impl fmt::Debug for Wait<'_> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("Wait")
+            .field("group", self.group)
+            .finish_non_exhaustive()
+    }
+}
+
+impl Future for Wait<'_> {
+    type Output = ();
+
+    fn poll(mut self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<()> {
+        let this = &mut *self;
+
+        if this.listener.is_none() {
+            if this.group.count() == 0 {
+                return Poll::Ready(());
+            }
+
+            // Check again after starting to listen, so that the last `done()` is not missed.
+            let listener = this.group.event.listen();
+            if this.group.count() == 0 {
+                return Poll::Ready(());
+            }
+            this.listener = Some(listener);
+        }
+
+        // Only the counter reaching zero notifies the event. Once that happened, a new round
+        // started by `add()` does not make this waiter wait again.
+        let listener = this.listener.as_mut().unwrap();
+        if Pin::new(listener).poll(cx).is_pending() {
+            return Poll::Pending;
+        }
+        this.listener = None;
+        Poll::Ready(())
+    }
+}
@@ -0,0 +1,118 @@
+#![cfg(feature = "std")]
+
+use std::future::Future;
+use std::pin::Pin;
+use std::task::{Context, Poll};
+
+use event_listener::broadcast::{self, RecvError, TryRecvError};
+use waker_fn::waker_fn;
+
+#[cfg(target_family = "wasm")]
+use wasm_bindgen_test::wasm_bindgen_test as test;
+
+#[test]
+fn every_receiver_gets_every_message() {
+    let (tx, mut rx1) = broadcast::channel(4);
+    let mut rx2 = rx1.clone();
+    assert_eq!(tx.receiver_count(), 2);
+
+    assert_eq!(tx.send(1), Ok(2));
+    assert_eq!(tx.send(2), Ok(2));
+
+    assert_eq!(rx1.try_recv(), Ok(1));
+    assert_eq!(rx1.try_recv(), Ok(2));
+    assert_eq!(rx1.try_recv(), Err(TryRecvError::Empty));
+    assert_eq!(rx2.try_recv(), Ok(1));
+    assert_eq!(rx2.try_recv(), Ok(2));
+
+    // New subscribers only see later messages.
+    let mut rx3 = tx.subscribe();
+    assert_eq!(rx3.try_recv(), Err(TryRecvError::Empty));
+    tx.send(3).unwrap();
+    assert_eq!(rx3.try_recv(), Ok(3));
+}
+
+#[test]
+fn waiting_receiver_is_woken_with_message() {
+    let (tx, mut rx) = broadcast::channel(1);
+
+    let waker = waker_fn(|| ());
+    let mut cx = Context::from_waker(&waker);
+    let mut recv = rx.recv();
+    assert_eq!(Pin::new(&mut recv).poll(&mut cx), Poll::Pending);
+
+    tx.send("a").unwrap();
+    assert_eq!(Pin::new(&mut recv).poll(&mut cx), Poll::Ready(Ok("a")));
+    drop(recv);
+
+    // Dropping a pending `recv` does not lose the message.
+    let mut recv = rx.recv();
+    assert_eq!(Pin::new(&mut recv).poll(&mut cx), Poll::Pending);
+    drop(recv);
+    tx.send("b").unwrap();
+    assert_eq!(rx.try_recv(), Ok("b"));
+}
+
+#[test]
+fn lagging_receiver() {
+    let (tx, mut rx) = broadcast::channel(2);
+    for i in 0..5 {
+        tx.send(i).unwrap();
+    }
+
+    futures_lite::future::block_on(async {
+        assert_eq!(rx.recv().await, Err(RecvError::Lagged(3)));
+        assert_eq!(rx.recv().await, Ok(3));
+        assert_eq!(rx.recv().await, Ok(4));
+    });
+}
+
+#[test]
+fn closed_after_draining() {
+    let (tx, mut rx) = broadcast::channel(2);
+    let tx2 = tx.clone();
+
+    tx.send(1).unwrap();
+    drop(tx);
+    assert_eq!(rx.try_recv(), Ok(1));
+    assert_eq!(rx.try_recv(), Err(TryRecvError::Empty));
+
+    let waker = waker_fn(|| ());
+    let mut cx = Context::from_waker(&waker);
+    let mut recv = rx.recv();
+    assert_eq!(Pin::new(&mut recv).poll(&mut cx), Poll::Pending);
+
+    drop(tx2);
+    assert_eq!(
+        Pin::new(&mut recv).poll(&mut cx),
+        Poll::Ready(Err(RecvError::Closed))
+    );
+}
+
+#[test]
+fn send_without_receivers() {
+    let (tx, rx) = broadcast::channel(1);
+    drop(rx);
+    assert_eq!(tx.send(5).unwrap_err().0, 5);
+}
+
+#[cfg(not(target_family = "wasm"))]
+#[test]
+fn recv_blocking() {
+    let (tx, mut rx) = broadcast::channel(8);
+
+    let handle = std::thread::spawn(move || {
+        let mut received = Vec::new();
+        while let Ok(value) = rx.recv_blocking() {
+            received.push(value);
+        }
+        received
+    });
+
+    for i in 0..8 {
+        tx.send(i).unwrap();
+    }
+    drop(tx);
+
+    assert_eq!(handle.join().unwrap(), (0..8).collect::<Vec<_>>());
+}
@@ -0,0 +1,100 @@
+#![cfg(feature = "std")]
+
+use std::future::Future;
+use std::pin::Pin;
+use std::sync::Mutex;
+use std::task::Context;
+
+use event_listener::condvar::Condvar;
+use waker_fn::waker_fn;
+
+#[cfg(target_family = "wasm")]
+use wasm_bindgen_test::wasm_bindgen_test as test;
+
+#[test]
+fn wait_async_registers_before_unlocking() {
+    let mutex = Mutex::new(0);
+    let condvar = Condvar::new();
+    let waker = waker_fn(|| ());
+    let mut cx = Context::from_waker(&waker);
+
+    let mut wait = condvar.wait_async(&mutex, mutex.lock().unwrap());
+    assert!(Pin::new(&mut wait).poll(&mut cx).is_pending());
+
+    *mutex.lock().unwrap() = 1;
+    condvar.notify_one();
+    match Pin::new(&mut wait).poll(&mut cx) {
+        std::task::Poll::Ready(guard) => assert_eq!(*guard, 1),
+        std::task::Poll::Pending => panic!("the waiter was not notified"),
+    };
+}
+
+#[test]
+fn notify_one_wakes_another_waiter_each_time() {
+    let mutex = Mutex::new(());
+    let condvar = Condvar::new();
+    let waker = waker_fn(|| ());
+    let mut cx = Context::from_waker(&waker);
+
+    let mut w1 = condvar.wait_async(&mutex, mutex.lock().unwrap());
+    let mut w2 = condvar.wait_async(&mutex, mutex.lock().unwrap());
+    let mut w3 = condvar.wait_async(&mutex, mutex.lock().unwrap());
+
+    condvar.notify_one();
+    condvar.notify_one();
+    assert!(Pin::new(&mut w1).poll(&mut cx).is_ready());
+    assert!(Pin::new(&mut w2).poll(&mut cx).is_ready());
+    assert!(Pin::new(&mut w3).poll(&mut cx).is_pending());
+
+    condvar.notify_all();
+    assert!(Pin::new(&mut w3).poll(&mut cx).is_ready());
+}
+
+#[test]
+fn wait_while_async() {
+    let mutex = Mutex::new(0);
+    let condvar = Condvar::new();
+    let waker = waker_fn(|| ());
+    let mut cx = Context::from_waker(&waker);
+
+    let mut wait = condvar.wait_while_async(&mutex, mutex.lock().unwrap(), |n| *n < 2);
+    // The guard is unlocked right away, not on the first poll.
+    assert!(mutex.try_lock().is_ok());
+    for n in 1..=2 {
+        assert!(Pin::new(&mut wait).poll(&mut cx).is_pending());
+        *mutex.lock().unwrap() = n;
+        condvar.notify_all();
+    }
+
+    match Pin::new(&mut wait).poll(&mut cx) {
+        std::task::Poll::Ready(guard) => assert_eq!(*guard, 2),
+        std::task::Poll::Pending => panic!("the condition was not checked again"),
+    };
+}
+
+#[cfg(not(target_family = "wasm"))]
+#[test]
+fn wait_while_threads() {
+    use std::sync::Arc;
+    use std::thread;
+
+    let shared = Arc::new((Mutex::new(0), Condvar::new()));
+
+    let handles: Vec<_> = (0..4)
+        .map(|_| {
+            let shared = shared.clone();
+            thread::spawn(move || {
+                let (mutex, condvar) = &*shared;
+                let mut guard = mutex.lock().unwrap();
+                *guard += 1;
+                condvar.notify_all();
+                let guard = condvar.wait_while(mutex, guard, |n| *n < 4);
+                assert_eq!(*guard, 4);
+            })
+        })
+        .collect();
+
+    for handle in handles {
+        handle.join().unwrap();
+    }
+}
@@ -0,0 +1,68 @@
+#![cfg(all(feature = "std", not(target_family = "wasm")))]
+
+use std::sync::atomic::{AtomicUsize, Ordering};
+use std::sync::Arc;
+use std::thread;
+
+use event_listener::{fence, Event, IntoNotification, Listener};
+
+#[test]
+fn relaxed_notify_after_full_fence() {
+    struct Shared {
+        value: AtomicUsize,
+        event: Event,
+    }
+
+    let shared = Arc::new(Shared {
+        value: AtomicUsize::new(0),
+        event: Event::new(),
+    });
+
+    let waiter = {
+        let shared = shared.clone();
+        thread::spawn(move || loop {
+            let value = shared.value.load(Ordering::Relaxed);
+            if value != 0 {
+                return value;
+            }
+
+            let listener = shared.event.listen();
+            fence::full_fence();
+            let value = shared.value.load(Ordering::Relaxed);
+            if value != 0 {
+                return value;
+            }
+            listener.wait();
+        })
+    };
+
+    // The fence takes the place of the one that a relaxed notification leaves out.
+    shared.value.store(5, Ordering::Relaxed);
+    fence::full_fence();
+    shared.event.notify(1.relaxed());
+
+    assert_eq!(waiter.join().unwrap(), 5);
+}
+
+#[test]
+fn acquire_release_fences() {
+    let data = Arc::new(AtomicUsize::new(0));
+    let ready = Arc::new(AtomicUsize::new(0));
+
+    let reader = {
+        let (data, ready) = (data.clone(), ready.clone());
+        thread::spawn(move || {
+            while ready.load(Ordering::Relaxed) == 0 {
+                thread::yield_now();
+            }
+            fence::acquire_fence();
+            data.load(Ordering::Relaxed)
+        })
+    };
+
+    data.store(7, Ordering::Relaxed);
+    fence::release_fence();
+    ready.store(1, Ordering::Relaxed);
+
+    assert_eq!(reader.join().unwrap(), 7);
+}
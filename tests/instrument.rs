@@ -0,0 +1,59 @@
+#![cfg(all(feature = "instrument", not(target_family = "wasm")))]
+
+use std::future::Future;
+use std::pin::Pin;
+use std::task::Context;
+use std::thread;
+use std::time::Duration;
+
+use event_listener::{Event, Listener};
+use waker_fn::waker_fn;
+
+#[test]
+fn records_both_latencies() {
+    let event = Event::new();
+    let listener = event.listen();
+
+    thread::sleep(Duration::from_millis(20));
+    event.notify(1);
+    thread::sleep(Duration::from_millis(20));
+    listener.wait();
+
+    let snapshot = event.snapshot();
+    let listen_to_wake = snapshot.listen_to_wake();
+    let notify_to_poll = snapshot.notify_to_poll();
+    assert_eq!(listen_to_wake.count(), 1);
+    assert_eq!(notify_to_poll.count(), 1);
+    assert!(listen_to_wake.max().unwrap() >= Duration::from_millis(20));
+    assert!(notify_to_poll.max().unwrap() >= Duration::from_millis(20));
+    assert_eq!(listen_to_wake.mean(), listen_to_wake.max());
+    assert_eq!(listen_to_wake.quantile(0.5), listen_to_wake.max());
+    assert_eq!(listen_to_wake.buckets().map(|(_, n)| n).sum::<u64>(), 1);
+}
+
+#[test]
+fn dropped_listeners_are_not_polled() {
+    let event = Event::new();
+    let mut polled = event.listen();
+    let dropped = event.listen();
+
+    event.notify(2);
+    drop(dropped);
+
+    let waker = waker_fn(|| ());
+    assert!(Pin::new(&mut polled)
+        .poll(&mut Context::from_waker(&waker))
+        .is_ready());
+
+    let snapshot = event.snapshot();
+    assert_eq!(snapshot.listen_to_wake().count(), 2);
+    assert_eq!(snapshot.notify_to_poll().count(), 1);
+}
+
+#[test]
+fn unused_event_is_empty() {
+    let snapshot = Event::new().snapshot();
+    assert_eq!(snapshot.listen_to_wake().count(), 0);
+    assert_eq!(snapshot.listen_to_wake().quantile(0.99), None);
+    assert_eq!(snapshot.notify_to_poll().mean(), None);
+}
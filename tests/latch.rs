@@ -0,0 +1,88 @@
+#![cfg(feature = "std")]
+
+use std::future::Future;
+use std::pin::Pin;
+use std::task::{Context, Poll};
+
+use event_listener::latch::CountDownLatch;
+use waker_fn::waker_fn;
+
+#[cfg(target_family = "wasm")]
+use wasm_bindgen_test::wasm_bindgen_test as test;
+
+#[test]
+fn opens_after_count_downs() {
+    let latch = CountDownLatch::new(2);
+    let waker = waker_fn(|| ());
+    let mut cx = Context::from_waker(&waker);
+
+    let mut wait = latch.wait();
+    assert_eq!(Pin::new(&mut wait).poll(&mut cx), Poll::Pending);
+
+    latch.count_down();
+    assert_eq!(latch.count(), 1);
+    assert_eq!(Pin::new(&mut wait).poll(&mut cx), Poll::Pending);
+
+    latch.count_down();
+    assert!(latch.is_open());
+    assert_eq!(Pin::new(&mut wait).poll(&mut cx), Poll::Ready(()));
+}
+
+#[test]
+fn stays_open() {
+    let latch = CountDownLatch::new(1);
+    let waker = waker_fn(|| ());
+    let mut cx = Context::from_waker(&waker);
+
+    latch.count_down();
+    latch.count_down();
+    assert_eq!(latch.count(), 0);
+    assert_eq!(Pin::new(&mut latch.wait()).poll(&mut cx), Poll::Ready(()));
+    assert_eq!(Pin::new(&mut latch.wait()).poll(&mut cx), Poll::Ready(()));
+}
+
+#[test]
+fn open_wakes_every_waiter() {
+    let latch = CountDownLatch::new(10);
+    let waker = waker_fn(|| ());
+    let mut cx = Context::from_waker(&waker);
+
+    let mut waits = vec![latch.wait(), latch.wait(), latch.wait()];
+    for wait in &mut waits {
+        assert_eq!(Pin::new(wait).poll(&mut cx), Poll::Pending);
+    }
+
+    latch.open();
+    for wait in &mut waits {
+        assert_eq!(Pin::new(wait).poll(&mut cx), Poll::Ready(()));
+    }
+}
+
+#[test]
+fn zero_count_is_open() {
+    let latch = CountDownLatch::new(0);
+    assert!(latch.is_open());
+}
+
+#[cfg(not(target_family = "wasm"))]
+#[test]
+fn wait_blocking_across_threads() {
+    use std::sync::Arc;
+    use std::thread;
+
+    let latch = Arc::new(CountDownLatch::new(4));
+
+    let handles = (0..4)
+        .map(|_| {
+            let latch = latch.clone();
+            thread::spawn(move || latch.count_down())
+        })
+        .collect::<Vec<_>>();
+
+    latch.wait_blocking();
+    assert!(latch.is_open());
+
+    for handle in handles {
+        handle.join().unwrap();
+    }
+}
@@ -0,0 +1,211 @@
+#![cfg(feature = "locks")]
+
+use std::future::Future;
+use std::pin::Pin;
+use std::task::{Context, Poll};
+
+use event_listener::locks::{Mutex, RwLock};
+use waker_fn::waker_fn;
+
+#[cfg(target_family = "wasm")]
+use wasm_bindgen_test::wasm_bindgen_test as test;
+
+#[test]
+fn mutex_is_fair() {
+    let mutex = Mutex::new(Vec::new());
+    let waker = waker_fn(|| ());
+    let mut cx = Context::from_waker(&waker);
+
+    let guard = mutex.try_lock().unwrap();
+    let mut first = mutex.lock();
+    let mut second = mutex.lock();
+    assert!(Pin::new(&mut first).poll(&mut cx).is_pending());
+    assert!(Pin::new(&mut second).poll(&mut cx).is_pending());
+    drop(guard);
+
+    // The lock was handed to the first waiter, so it cannot be taken over.
+    assert!(mutex.try_lock().is_none());
+    assert!(Pin::new(&mut second).poll(&mut cx).is_pending());
+
+    match Pin::new(&mut first).poll(&mut cx) {
+        Poll::Ready(mut guard) => guard.push(1),
+        Poll::Pending => panic!("the lock was not handed over"),
+    }
+    match Pin::new(&mut second).poll(&mut cx) {
+        Poll::Ready(mut guard) => guard.push(2),
+        Poll::Pending => panic!("the lock was not handed over"),
+    }
+
+    assert_eq!(*mutex.try_lock().unwrap(), [1, 2]);
+}
+
+#[test]
+fn mutex_cancelled_waiter_passes_the_lock_on() {
+    let mutex = Mutex::new(());
+    let waker = waker_fn(|| ());
+    let mut cx = Context::from_waker(&waker);
+
+    let guard = mutex.try_lock().unwrap();
+    let mut first = mutex.lock();
+    let mut second = mutex.lock();
+    let mut third = mutex.lock();
+    assert!(Pin::new(&mut first).poll(&mut cx).is_pending());
+    assert!(Pin::new(&mut second).poll(&mut cx).is_pending());
+    assert!(Pin::new(&mut third).poll(&mut cx).is_pending());
+
+    // A waiter that leaves the queue before its turn does not take the lock with it.
+    drop(second);
+    drop(guard);
+
+    // A waiter that was handed the lock passes it on.
+    drop(first);
+    assert!(Pin::new(&mut third).poll(&mut cx).is_ready());
+    drop(third);
+
+    assert!(mutex.try_lock().is_some());
+}
+
+#[test]
+fn mutex_with_condvar() {
+    use event_listener::condvar::Condvar;
+
+    let mutex = Mutex::new(0);
+    let condvar = Condvar::new();
+
+    futures_lite::future::block_on(async {
+        let wait = condvar.wait_while_async(&mutex, mutex.lock().await, |n| *n == 0);
+        *mutex.lock().await = 1;
+        condvar.notify_one();
+        assert_eq!(*wait.await, 1);
+    });
+}
+
+#[cfg(not(target_family = "wasm"))]
+#[test]
+fn mutex_blocking_lock_threads() {
+    use std::sync::Arc;
+    use std::thread;
+
+    let mutex = Arc::new(Mutex::new(0));
+    let handles: Vec<_> = (0..8)
+        .map(|i| {
+            let mutex = mutex.clone();
+            thread::spawn(move || {
+                for _ in 0..100 {
+                    if i % 2 == 0 {
+                        *mutex.blocking_lock() += 1;
+                    } else {
+                        *futures_lite::future::block_on(mutex.lock()) += 1;
+                    }
+                }
+            })
+        })
+        .collect();
+
+    for handle in handles {
+        handle.join().unwrap();
+    }
+    assert_eq!(*mutex.blocking_lock(), 800);
+}
+
+#[test]
+fn rwlock_prefers_writers() {
+    let lock = RwLock::new(0);
+    let waker = waker_fn(|| ());
+    let mut cx = Context::from_waker(&waker);
+
+    let reader = lock.try_read().unwrap();
+    let mut writer = lock.write();
+    assert!(Pin::new(&mut writer).poll(&mut cx).is_pending());
+
+    // New readers queue up behind the writer.
+    assert!(lock.try_read().is_none());
+    let mut late_reader = lock.read();
+    assert!(Pin::new(&mut late_reader).poll(&mut cx).is_pending());
+
+    drop(reader);
+    match Pin::new(&mut writer).poll(&mut cx) {
+        Poll::Ready(mut guard) => *guard = 1,
+        Poll::Pending => panic!("the lock was not handed to the writer"),
+    }
+    match Pin::new(&mut late_reader).poll(&mut cx) {
+        Poll::Ready(guard) => assert_eq!(*guard, 1),
+        Poll::Pending => panic!("the lock was not handed to the reader"),
+    };
+}
+
+#[test]
+fn rwlock_prefers_readers() {
+    let lock = RwLock::with_writer_preference((), false);
+    let waker = waker_fn(|| ());
+    let mut cx = Context::from_waker(&waker);
+
+    let writer = lock.try_write().unwrap();
+    let mut next_writer = lock.write();
+    let mut r1 = lock.read();
+    let mut r2 = lock.read();
+    assert!(Pin::new(&mut next_writer).poll(&mut cx).is_pending());
+    assert!(Pin::new(&mut r1).poll(&mut cx).is_pending());
+    assert!(Pin::new(&mut r2).poll(&mut cx).is_pending());
+
+    // All queued readers go in before the queued writer.
+    drop(writer);
+    let g1 = match Pin::new(&mut r1).poll(&mut cx) {
+        Poll::Ready(guard) => guard,
+        Poll::Pending => panic!("the first reader was not let in"),
+    };
+    assert!(Pin::new(&mut r2).poll(&mut cx).is_ready());
+    assert!(Pin::new(&mut next_writer).poll(&mut cx).is_pending());
+
+    // Readers do not wait for the queued writer either.
+    assert!(lock.try_read().is_some());
+
+    drop(g1);
+    assert!(Pin::new(&mut next_writer).poll(&mut cx).is_ready());
+}
+
+#[test]
+fn rwlock_cancelled_writer_lets_readers_in() {
+    let lock = RwLock::new(());
+    let waker = waker_fn(|| ());
+    let mut cx = Context::from_waker(&waker);
+
+    let reader = lock.try_read().unwrap();
+    let mut writer = lock.write();
+    let mut late_reader = lock.read();
+    assert!(Pin::new(&mut writer).poll(&mut cx).is_pending());
+    assert!(Pin::new(&mut late_reader).poll(&mut cx).is_pending());
+
+    drop(writer);
+    assert!(Pin::new(&mut late_reader).poll(&mut cx).is_ready());
+    drop(reader);
+}
+
+#[cfg(not(target_family = "wasm"))]
+#[test]
+fn rwlock_threads() {
+    use std::sync::Arc;
+    use std::thread;
+
+    let lock = Arc::new(RwLock::new(0));
+    let handles: Vec<_> = (0..8)
+        .map(|i| {
+            let lock = lock.clone();
+            thread::spawn(move || {
+                for _ in 0..100 {
+                    if i % 2 == 0 {
+                        *lock.blocking_write() += 1;
+                    } else {
+                        let value = *futures_lite::future::block_on(lock.read());
+                        assert!(value <= 400);
+                    }
+                }
+            })
+        })
+        .collect();
+
+    for handle in handles {
+        handle.join().unwrap();
+    }
+    assert_eq!(*lock.blocking_read(), 400);
+}
@@ -36,6 +36,24 @@ fn notify() {
     assert!(!is_notified(&mut l3));
 }
 
+#[test]
+fn notify_bool() {
+    use event_listener::IntoNotification;
+
+    let event = Event::new();
+
+    let mut l1 = event.listen();
+    let mut l2 = event.listen();
+
+    assert_eq!(event.notify(false), 0);
+    assert_eq!(event.notify(true), 1);
+    assert_eq!(event.notify(true), 0);
+    assert_eq!(event.notify(true.additional()), 1);
+
+    assert!(is_notified(&mut l1));
+    assert!(is_notified(&mut l2));
+}
+
 #[test]
 fn notify_additional() {
     let event = Event::new();
@@ -190,3 +208,1343 @@ fn notify_all_fair() {
         .poll(&mut Context::from_waker(&waker3))
         .is_ready());
 }
+
+#[cfg(feature = "std")]
+#[test]
+fn notify_weighted() {
+    use event_listener::IntoNotification;
+
+    let event = Event::new();
+
+    let mut l1 = event.listen_weighted(2);
+    let mut l2 = event.listen();
+    let mut l3 = event.listen_weighted(3);
+
+    // Not enough units for the first listener.
+    assert_eq!(event.notify(1), 0);
+    assert!(!is_notified(&mut l1));
+
+    // Listeners are woken in order until the units run out.
+    assert_eq!(event.notify(4), 2);
+    assert!(!is_notified(&mut l3));
+    assert!(is_notified(&mut l1));
+    assert!(is_notified(&mut l2));
+
+    assert_eq!(event.notify(3.additional()), 1);
+    assert!(is_notified(&mut l3));
+}
+
+#[cfg(feature = "std")]
+#[test]
+fn notify_all_heavy() {
+    let event = Event::new();
+    let heavy = usize::MAX / 2 + 1;
+
+    let _l1 = event.listen_weighted(heavy);
+    let _l2 = event.listen_weighted(heavy);
+    let mut l3 = event.listen();
+
+    // The weights add up to more than `usize::MAX`.
+    assert_eq!(event.notify_all(), 3);
+    assert_eq!(event.notify(usize::MAX), 0);
+    assert!(is_notified(&mut l3));
+}
+
+#[cfg(feature = "std")]
+#[test]
+fn drop_notified_weighted() {
+    let event = Event::new();
+
+    let l1 = event.listen_weighted(2);
+    let mut l2 = event.listen();
+    let mut l3 = event.listen();
+    let mut l4 = event.listen();
+
+    assert_eq!(event.notify(2), 1);
+
+    // Both units are forwarded to the next listeners.
+    drop(l1);
+    assert!(is_notified(&mut l2));
+    assert!(is_notified(&mut l3));
+    assert!(!is_notified(&mut l4));
+}
+
+#[cfg(feature = "std")]
+#[test]
+fn notify_grouped() {
+    use event_listener::IntoNotification;
+
+    let event = Event::new();
+
+    let mut a1 = event.listen_grouped(1);
+    let mut a2 = event.listen_grouped(1);
+    let mut a3 = event.listen_grouped(1);
+    let mut b1 = event.listen_grouped(2);
+
+    // Notifications alternate between the groups.
+    assert_eq!(event.notify(1.additional()), 1);
+    assert!(is_notified(&mut a1));
+    assert_eq!(event.notify(1.additional()), 1);
+    assert!(is_notified(&mut b1));
+    assert!(!is_notified(&mut a2));
+
+    // Once a group runs dry, the rest go to the remaining groups.
+    assert_eq!(event.notify(2.additional()), 2);
+    assert!(is_notified(&mut a2));
+    assert!(is_notified(&mut a3));
+}
+
+#[cfg(feature = "std")]
+#[test]
+fn drop_notified_grouped() {
+    use event_listener::IntoNotification;
+
+    let event = Event::new();
+
+    let a1 = event.listen_grouped(1);
+    let mut a2 = event.listen_grouped(1);
+    let mut b1 = event.listen_grouped(2);
+    let mut b2 = event.listen_grouped(2);
+
+    assert_eq!(event.notify(1.additional()), 1);
+
+    // The forwarded notification goes to the next group in line.
+    drop(a1);
+    assert!(is_notified(&mut b1));
+    assert!(!is_notified(&mut a2));
+    assert!(!is_notified(&mut b2));
+}
+
+#[cfg(feature = "std")]
+#[test]
+fn notify_report() {
+    use event_listener::{IntoNotification, Listener};
+
+    let event = Event::<usize>::with_tag();
+
+    let l1 = event.listen();
+    let l2 = event.listen();
+    let l3 = event.listen();
+
+    // Already notified listeners count towards non-additional notifications.
+    assert_eq!(event.notify(1.tag(0)), 1);
+    let report = event.notify_report(4.tag_iter(1..));
+    assert_eq!(report.notified(), 2);
+    assert_eq!(report.undelivered(), 1);
+    assert_eq!(report.into_undelivered().next(), Some(3));
+
+    assert_eq!(l1.wait(), 0);
+    assert_eq!(l2.wait(), 1);
+    assert_eq!(l3.wait(), 2);
+
+    let report = event.notify_report(1.additional().tag(5));
+    assert_eq!(report.notified(), 0);
+    assert_eq!(report.undelivered(), 1);
+    assert_eq!(report.into_tag(), 5);
+}
+
+#[cfg(feature = "std")]
+#[test]
+fn notify_tag_iter_runs_out() {
+    use event_listener::{IntoNotification, Listener};
+
+    let event = Event::<i32>::with_tag();
+    let l1 = event.listen();
+    let l2 = event.listen();
+    let l3 = event.listen();
+
+    // The upper bound of the size hint is three, but only one tag is produced.
+    let tags = vec![1, 2, 3].into_iter().filter(|x| *x == 2);
+    let report = event.notify_report(3.additional().tag_iter(tags));
+    assert_eq!(report.notified(), 1);
+    assert_eq!(report.undelivered(), 2);
+
+    assert!(!l2.is_notified());
+    assert!(!l3.is_notified());
+    assert_eq!(l1.wait(), 2);
+}
+
+#[test]
+fn notify_all_method() {
+    let event = Event::new();
+    assert_eq!(event.notify_all(), 0);
+
+    let mut l1 = event.listen();
+    let mut l2 = event.listen();
+    let mut l3 = event.listen();
+
+    assert_eq!(event.notify(1), 1);
+    assert_eq!(event.notify_all(), 2);
+    assert_eq!(event.notify_all_additional(), 0);
+
+    assert!(is_notified(&mut l1));
+    assert!(is_notified(&mut l2));
+    assert!(is_notified(&mut l3));
+}
+
+#[cfg(feature = "std")]
+#[test]
+fn counting_event() {
+    use event_listener::IntoNotification;
+
+    let event = Event::counting();
+    assert!(!event.is_notified());
+
+    // Non-additional notifications only top up the stored count.
+    event.notify(1);
+    event.notify(1);
+    assert!(event.is_notified());
+
+    let mut l1 = event.listen();
+    let mut l2 = event.listen();
+    assert!(is_notified(&mut l1));
+    assert!(!is_notified(&mut l2));
+    assert!(!event.is_notified());
+
+    // Leftover units are stored for the next listener.
+    assert_eq!(event.notify(2.additional()), 1);
+    assert!(is_notified(&mut l2));
+    let mut l3 = event.listen();
+    assert!(is_notified(&mut l3));
+}
+
+#[cfg(feature = "std")]
+#[test]
+fn counting_event_drop_notified() {
+    let event = Event::counting();
+    event.notify(1);
+
+    // A dropped listener gives its stored notification back.
+    drop(event.listen());
+    assert!(event.is_notified());
+    let mut listener = event.listen();
+    assert!(is_notified(&mut listener));
+}
+
+#[cfg(feature = "std")]
+#[test]
+fn pending_notifications() {
+    use event_listener::IntoNotification;
+
+    let event = Event::new();
+    event.notify(1.additional());
+    assert_eq!(event.pending_notifications(), 0);
+
+    let event = Event::counting();
+    event.notify(2.additional());
+    assert_eq!(event.pending_notifications(), 2);
+    let mut listener = event.listen();
+    assert!(is_notified(&mut listener));
+    assert_eq!(event.pending_notifications(), 1);
+    event.reset();
+    assert_eq!(event.pending_notifications(), 0);
+
+    let event = Event::latched();
+    assert_eq!(event.pending_notifications(), 0);
+    event.notify(1);
+    assert_eq!(event.pending_notifications(), 1);
+}
+
+#[cfg(feature = "std")]
+#[test]
+fn counting_event_fifo() {
+    use event_listener::IntoNotification;
+
+    let event = Event::counting();
+    let mut heavy = event.listen_weighted(3);
+
+    // The units are not enough for the oldest listener, so they are stored...
+    assert_eq!(event.notify(2.additional()), 0);
+    assert!(!is_notified(&mut heavy));
+
+    // ...but a newer listener cannot take them away from it.
+    let mut light = event.listen();
+    assert!(!is_notified(&mut light));
+
+    // The stored units count towards the next notification.
+    assert_eq!(event.notify(1.additional()), 1);
+    assert!(is_notified(&mut heavy));
+    assert!(!is_notified(&mut light));
+}
+
+#[cfg(feature = "std")]
+#[test]
+fn latched_event() {
+    let event = Event::latched();
+
+    let mut l1 = event.listen();
+    let mut l2 = event.listen();
+    assert!(!event.is_notified());
+
+    // Any notification opens the event for everyone.
+    assert_eq!(event.notify(1), 2);
+    assert!(is_notified(&mut l1));
+    assert!(is_notified(&mut l2));
+
+    let mut l3 = event.listen();
+    assert!(is_notified(&mut l3));
+    assert!(event.is_notified());
+
+    event.reset();
+    assert!(!event.is_notified());
+    let mut l4 = event.listen();
+    assert!(!is_notified(&mut l4));
+
+    assert_eq!(event.notify_all(), 1);
+    assert!(is_notified(&mut l4));
+    assert!(event.is_notified());
+}
+
+#[test]
+fn listener_state() {
+    use event_listener::Listener;
+
+    let event = Event::new();
+    let mut listener = event.listen();
+    assert!(listener.is_listening());
+    #[cfg(feature = "std")]
+    assert!(!listener.is_notified());
+
+    assert_eq!(event.notify(1), 1);
+    assert!(listener.is_listening());
+    #[cfg(feature = "std")]
+    assert!(listener.is_notified());
+
+    assert!(is_notified(&mut listener));
+    assert!(!listener.is_listening());
+    #[cfg(feature = "std")]
+    assert!(!listener.is_notified());
+}
+
+#[test]
+fn notify_constructed() {
+    use event_listener::{Additional, Notify, Relaxed};
+
+    let event = Event::new();
+    let mut l1 = event.listen();
+    let mut l2 = event.listen();
+
+    assert_eq!(event.notify(Relaxed::new(Notify::new(1))), 1);
+    assert!(is_notified(&mut l1));
+
+    let notify = Additional::new(Notify::new(1));
+    assert_eq!(notify.inner().count(), 1);
+    assert_eq!(event.notify(notify), 1);
+    assert!(is_notified(&mut l2));
+}
+
+#[cfg(feature = "std")]
+#[test]
+fn notify_constructed_tag() {
+    use event_listener::{Listener, Notify, Tag, TagWith};
+
+    let event = Event::<i32>::with_tag();
+    let l1 = event.listen();
+    let l2 = event.listen();
+
+    let (tag, notify) = Tag::new(1, Notify::new(1)).into_parts();
+    assert_eq!(tag, 1);
+    assert_eq!(event.notify(Tag::new(tag, notify)), 1);
+    assert_eq!(event.notify(TagWith::new(|| 2, Notify::new(2))), 1);
+
+    assert_eq!(l1.wait(), 1);
+    assert_eq!(l2.wait(), 2);
+}
+
+#[cfg(feature = "std")]
+#[test]
+fn notify_with_context() {
+    use event_listener::{IntoNotification, NotifyWith};
+
+    let event = Event::new();
+    let mut listeners: Vec<_> = (0..5).map(|_| event.listen()).collect();
+
+    // Top up to three outstanding wakes.
+    let top_up = || NotifyWith::new(|cx| 3usize.saturating_sub(cx.notified())).additional();
+
+    assert_eq!(event.notify(top_up()), 3);
+    assert_eq!(event.notify(top_up()), 0);
+
+    assert!(is_notified(&mut listeners[0]));
+    listeners.remove(0);
+    assert_eq!(event.notify(top_up()), 1);
+
+    assert_eq!(
+        event.notify(NotifyWith::new(|cx| {
+            assert_eq!(cx.waiting(), 1);
+            assert_eq!(cx.notified(), 3);
+            assert_eq!(cx.registered(), 4);
+            cx.registered()
+        })),
+        1
+    );
+}
+
+#[cfg(feature = "std")]
+#[test]
+fn notify_map_tag() {
+    use event_listener::{IntoNotification, Listener};
+
+    let event = Event::<Option<char>>::with_tag();
+    let l1 = event.listen();
+    let l2 = event.listen();
+
+    let mut calls = 0;
+    event.notify(
+        1.additional()
+            .tag('a')
+            .map_tag(|c| {
+                calls += 1;
+                Some(c)
+            })
+            .relaxed(),
+    );
+    assert_eq!(calls, 1);
+    event.notify(1.additional().map_tag(|()| None));
+
+    assert_eq!(l1.wait(), Some('a'));
+    assert_eq!(l2.wait(), None);
+}
+
+#[cfg(feature = "std")]
+#[test]
+fn notify_filtered() {
+    use event_listener::{IntoNotification, Listener};
+
+    let event = Event::<&'static str>::with_tag();
+    let a = event.listen_filter(|topic| *topic == "a");
+    let b1 = event.listen_filter(|topic| *topic == "b");
+    let b2 = event.listen_filter(|topic| *topic == "b");
+
+    // Nobody accepts this topic.
+    assert_eq!(event.notify(1.additional().tag("c")), 0);
+
+    // The notification skips the listener for "a".
+    assert_eq!(event.notify(1.additional().tag("b")), 1);
+    assert!(!a.is_notified());
+    assert!(b1.is_notified());
+
+    // A dropped notification is passed on to a listener that accepts it.
+    drop(b1);
+    assert!(b2.is_notified());
+    assert!(!a.is_notified());
+
+    assert_eq!(event.notify(1.additional().tag("a")), 1);
+    assert!(a.is_notified());
+    assert_eq!(a.wait(), "a");
+    assert_eq!(b2.wait(), "b");
+
+    let b1 = event.listen_filter(|topic| *topic == "b");
+    let a = event.listen();
+    assert_eq!(event.notify(2.additional().tag("b")), 2);
+    assert_eq!(a.wait(), "b");
+    assert_eq!(b1.wait(), "b");
+}
+
+#[cfg(feature = "std")]
+#[test]
+fn notify_filtered_rejected_tags() {
+    use event_listener::{IntoNotification, Listener};
+
+    let event = Event::<i32>::with_tag();
+    let odd = event.listen_filter(|n| n % 2 == 1);
+
+    // The rejected tag is handed back, and the next one is tried.
+    let report = event.notify_report(3.additional().tag_iter(vec![2, 3, 5]));
+    assert_eq!(report.notified(), 1);
+    assert_eq!(report.undelivered(), 2);
+    assert_eq!(report.into_undelivered().collect::<Vec<_>>(), [2, 5]);
+    assert_eq!(odd.wait(), 3);
+
+    // A rejecting listener does not make an endless tag source spin.
+    let odd = event.listen_filter(|n| n % 2 == 1);
+    assert_eq!(event.notify(usize::MAX.additional().tag(4)), 0);
+    assert!(!odd.is_notified());
+    drop(odd);
+
+    // Neither does a tag source that may vary, but is always rejected.
+    let never = event.listen_filter(|_| false);
+    let report = event.notify_report(usize::MAX.additional().tag_with(|| 1));
+    assert_eq!(report.notified(), 0);
+    assert_eq!(report.undelivered(), usize::MAX);
+    assert!(!never.is_notified());
+    drop(never);
+
+    // A tag taken for a listener that is too heavy is not lost either.
+    let heavy = event.listen_weighted(2);
+    let odd = event.listen_filter(|n| n % 2 == 1);
+    let report = event.notify_report(1.additional().tag_iter(vec![7]));
+    assert_eq!(report.notified(), 0);
+    assert_eq!(report.into_undelivered().collect::<Vec<_>>(), [7]);
+    assert!(!heavy.is_notified());
+    assert!(!odd.is_notified());
+}
+
+#[test]
+fn map_listener() {
+    let event = Event::new();
+    let mapped = event.listen().map(|()| 5);
+    let mut l2 = event.listen();
+
+    assert_eq!(event.notify(1), 1);
+    assert!(mapped.is_listening());
+
+    // Dropping the mapped listener passes the notification on.
+    drop(mapped);
+    assert!(is_notified(&mut l2));
+
+    let mut mapped = event.listen().map(|()| 7);
+    event.notify(1);
+
+    let waker = waker_fn(|| ());
+    let mut cx = Context::from_waker(&waker);
+    assert_eq!(
+        Pin::new(&mut mapped).poll(&mut cx),
+        std::task::Poll::Ready(7)
+    );
+}
+
+#[cfg(feature = "std")]
+#[test]
+fn notify_parent() {
+    use event_listener::IntoNotification;
+    use std::task::Poll;
+
+    let waker = waker_fn(|| ());
+    let mut cx = Context::from_waker(&waker);
+
+    let root = Arc::new(Event::<&str>::with_tag());
+    let branch = Arc::new(Event::new());
+    let leaf = Event::new();
+    branch.set_parent_with(root.clone(), || "branch");
+    leaf.set_parent(branch.clone());
+
+    let mut leaf_listener = leaf.listen();
+    let mut branch_listener = branch.listen();
+    let mut root_listener = root.listen();
+
+    // The notification travels up the tree.
+    assert_eq!(leaf.notify(1.additional()), 1);
+    assert!(is_notified(&mut leaf_listener));
+    assert!(is_notified(&mut branch_listener));
+    assert_eq!(
+        Pin::new(&mut root_listener).poll(&mut cx),
+        Poll::Ready("branch")
+    );
+
+    // Events without listeners still pass the notification on.
+    let mut root_listener = root.listen();
+    assert_eq!(leaf.notify_all(), 0);
+    assert_eq!(
+        Pin::new(&mut root_listener).poll(&mut cx),
+        Poll::Ready("branch")
+    );
+
+    leaf.clear_parent();
+    let mut branch_listener = branch.listen();
+    leaf.notify(1);
+    assert!(!is_notified(&mut branch_listener));
+}
+
+#[test]
+fn borrowed_listener() {
+    use event_listener::Listener;
+
+    let waker = waker_fn(|| ());
+    let mut cx = Context::from_waker(&waker);
+
+    let event = Event::new();
+    let first = event.listen_borrowed();
+    let mut second = event.listen_borrowed();
+    assert!(first.listens_to(&event));
+    assert!(first.same_event(&second));
+
+    // Dropping a notified listener passes the notification on.
+    assert_eq!(event.notify(1), 1);
+    drop(first);
+    assert!(Pin::new(&mut second).poll(&mut cx).is_ready());
+}
+
+#[test]
+fn clone_event() {
+    use event_listener::Listener;
+
+    let event = Event::new();
+    let handle = event.clone();
+
+    let mut listener = event.listen();
+    assert!(listener.listens_to(&handle));
+
+    // Notifying either handle notifies the same listeners.
+    assert_eq!(handle.notify(1), 1);
+    assert!(is_notified(&mut listener));
+
+    // The clones keep the event alive.
+    let listener = handle.listen();
+    drop(handle);
+    assert_eq!(event.notify(1), 1);
+    assert!(listener.discard());
+}
+
+#[cfg(all(feature = "std", not(target_family = "wasm")))]
+#[test]
+fn try_notify_busy() {
+    use event_listener::{IntoNotification, Listener, WouldBlock};
+    use std::sync::mpsc;
+    use std::thread;
+
+    let event = Arc::new(Event::<u32>::with_tag());
+    let (locked_tx, locked_rx) = mpsc::channel();
+    let (release_tx, release_rx) = mpsc::channel();
+
+    // The filter runs with the event locked, so it can hold the lock until it is released.
+    let locked = Mutex::new((locked_tx, release_rx));
+    let listener = event.listen_filter(move |_| {
+        let locked = locked.lock().unwrap();
+        locked.0.send(()).unwrap();
+        locked.1.recv().unwrap();
+        true
+    });
+
+    let handle = thread::spawn({
+        let event = event.clone();
+        move || event.notify(1.tag(1))
+    });
+
+    locked_rx.recv().unwrap();
+    assert_eq!(event.try_notify(1.tag(2)), Err(WouldBlock));
+    release_tx.send(()).unwrap();
+    assert_eq!(handle.join().unwrap(), 1);
+
+    assert_eq!(event.try_notify(1.tag(2)), Ok(0));
+    assert!(listener.is_notified());
+}
+
+#[cfg(all(feature = "std", not(target_family = "wasm")))]
+#[test]
+fn unlisten() {
+    use event_listener::{Listener, WaitError};
+    use std::thread;
+
+    let event = Arc::new(Event::new());
+
+    // A blocked listener is woken up by its removal.
+    let listener = event.listen();
+    let id = listener.id().unwrap();
+    let handle = thread::spawn(move || listener.checked().wait());
+    assert!(event.unlisten(id));
+    assert_eq!(handle.join().unwrap(), Err(WaitError::Cancelled));
+    assert_eq!(event.total_listeners(), 0);
+    assert!(!event.unlisten(id));
+
+    // Notified listeners keep their notification.
+    let listener = event.listen();
+    let id = listener.id().unwrap();
+    assert_eq!(event.notify(1), 1);
+    assert!(!event.unlisten(id));
+    assert_eq!(listener.checked().wait(), Ok(()));
+}
+
+#[cfg(feature = "std")]
+#[test]
+fn listener_debug() {
+    use event_listener::IntoNotification;
+
+    let waker = waker_fn(|| ());
+    let mut cx = Context::from_waker(&waker);
+
+    let event = Event::<u32>::with_tag();
+    let mut listener = event.listen();
+    assert!(format!("{:?}", listener).contains("state: Created"));
+
+    assert!(Pin::new(&mut listener).poll(&mut cx).is_pending());
+    assert!(format!("{:?}", listener).contains("state: Waiting"));
+
+    event.notify(1.additional().tag(3));
+    assert!(
+        format!("{:?}", listener).contains(r#"state: Notified { additional: true, tag: "u32" }"#)
+    );
+
+    // Listeners of the same event print the same event.
+    let other = event.listen();
+    let event_field = |debug: String| debug.split(", state").next().unwrap().to_owned();
+    assert_eq!(
+        event_field(format!("{:?}", listener)),
+        event_field(format!("{:?}", other))
+    );
+}
+
+#[cfg(feature = "std")]
+#[test]
+fn notify_sync() {
+    let waker = waker_fn(|| ());
+    let mut cx = Context::from_waker(&waker);
+
+    let event = Event::new();
+    let l1 = event.listen();
+    let l2 = event.listen();
+    let l3 = event.listen();
+
+    let mut ack = event.notify_sync(2);
+    assert_eq!(ack.notified(), 2);
+    assert!(Pin::new(&mut ack).poll(&mut cx).is_pending());
+
+    // The third listener was not notified by this call.
+    drop(l3);
+    assert!(Pin::new(&mut ack).poll(&mut cx).is_pending());
+
+    drop(l1);
+    assert!(Pin::new(&mut ack).poll(&mut cx).is_pending());
+    drop(l2);
+    assert!(Pin::new(&mut ack).poll(&mut cx).is_ready());
+
+    // Nothing to wait for without listeners.
+    let mut ack = event.notify_sync(1);
+    assert_eq!(ack.notified(), 0);
+    assert!(Pin::new(&mut ack).poll(&mut cx).is_ready());
+}
+
+#[cfg(feature = "std")]
+#[test]
+fn notify_and_wait() {
+    use event_listener::Listener;
+    use std::task::Poll;
+
+    let waker = waker_fn(|| ());
+    let mut cx = Context::from_waker(&waker);
+
+    let event = Event::new();
+    let l1 = event.listen();
+    let mut l2 = event.listen();
+
+    let mut rendezvous = event.notify_and_wait(2);
+    assert_eq!(rendezvous.notified(), 2);
+    assert_eq!(Pin::new(&mut rendezvous).poll(&mut cx), Poll::Pending);
+
+    // Discarding the notification does not complete the rendezvous.
+    assert!(l1.discard());
+    assert_eq!(Pin::new(&mut rendezvous).poll(&mut cx), Poll::Pending);
+
+    assert!(is_notified(&mut l2));
+    assert_eq!(Pin::new(&mut rendezvous).poll(&mut cx), Poll::Ready(true));
+
+    // Nobody received the notification.
+    let listener = event.listen();
+    let mut rendezvous = event.notify_and_wait(1);
+    drop(listener);
+    assert_eq!(Pin::new(&mut rendezvous).poll(&mut cx), Poll::Ready(false));
+}
+
+#[cfg(feature = "std")]
+#[test]
+fn notify_class() {
+    use event_listener::{IntoNotification, ListenerClass};
+
+    let event = Event::new();
+    let mut r1 = event.listen_class(ListenerClass::A);
+    let mut w1 = event.listen_class(ListenerClass::B);
+    let mut r2 = event.listen();
+    let mut w2 = event.listen_class(ListenerClass::B);
+
+    // Only writers are notified, oldest first.
+    assert_eq!(event.notify_class(ListenerClass::B, 1), 1);
+    assert!(!is_notified(&mut r1));
+    assert!(is_notified(&mut w1));
+    assert!(!is_notified(&mut w2));
+
+    // Regular notifications still go to the oldest listener.
+    assert_eq!(event.notify(1.additional()), 1);
+    assert!(is_notified(&mut r1));
+
+    // A writer is waiting, so no reader is notified.
+    let mut r3 = event.listen();
+    assert_eq!(
+        event.notify_one_or_all(ListenerClass::B, ListenerClass::A),
+        1
+    );
+    assert!(is_notified(&mut w2));
+    assert!(!is_notified(&mut r2));
+
+    // Without writers, all readers are notified.
+    assert_eq!(
+        event.notify_one_or_all(ListenerClass::B, ListenerClass::A),
+        2
+    );
+    assert!(is_notified(&mut r2));
+    assert!(is_notified(&mut r3));
+}
+
+#[test]
+fn notify_waves() {
+    let event = Event::new();
+    let mut listeners = (0..5).map(|_| event.listen()).collect::<Vec<_>>();
+
+    let mut waves = event.notify_waves(3);
+    assert_eq!(waves.next(), Some(3));
+    assert!(listeners[..3].iter_mut().all(is_notified));
+    assert!(!is_notified(&mut listeners[3]));
+
+    // A listener that joins between waves is notified by a later one.
+    let mut late = event.listen();
+    assert_eq!(waves.next(), Some(3));
+    assert!(is_notified(&mut late));
+    assert_eq!(waves.next(), None);
+}
+
+#[cfg(feature = "std")]
+#[test]
+fn wake_outside_lock() {
+    use event_listener::WouldBlock;
+
+    let event = Arc::new(Event::new());
+    let mut listener = event.listen();
+
+    // The waker runs after the notifier released the lock, so it can use the event.
+    let result = Arc::new(Mutex::new(None));
+    let waker = waker_fn({
+        let event = event.clone();
+        let result = result.clone();
+        move || *result.lock().unwrap() = Some(event.try_notify(1))
+    });
+    assert!(Pin::new(&mut listener)
+        .poll(&mut Context::from_waker(&waker))
+        .is_pending());
+
+    assert_eq!(event.notify(1), 1);
+    assert_eq!(*result.lock().unwrap(), Some(Ok::<_, WouldBlock>(0)));
+}
+
+#[test]
+fn spin_wait() {
+    use event_listener::Listener;
+
+    let event = Event::new();
+    let listener = event.listen();
+    event.notify(1);
+    listener.spin_wait();
+}
+
+#[cfg(all(feature = "std", not(target_family = "wasm")))]
+#[test]
+fn spin_wait_threaded() {
+    use event_listener::Listener;
+    use std::thread;
+
+    let event = Arc::new(Event::new());
+    let listener = event.listen();
+
+    let handle = thread::spawn(move || listener.spin_wait());
+    event.notify(1);
+    handle.join().unwrap();
+}
+
+#[cfg(feature = "std")]
+#[test]
+fn undelivered_tags() {
+    use event_listener::{IntoNotification, Listener};
+
+    let event = Event::<u32>::with_tag();
+    let dropped = Arc::new(Mutex::new(Vec::new()));
+    event.on_undelivered({
+        let dropped = dropped.clone();
+        move |tag| dropped.lock().unwrap().push(tag)
+    });
+
+    // A notification that is passed on is not reported.
+    let first = event.listen();
+    let second = event.listen();
+    event.notify(1.tag(1));
+    drop(first);
+    assert!(dropped.lock().unwrap().is_empty());
+
+    // Nobody is left to take it over.
+    drop(second);
+    assert_eq!(*dropped.lock().unwrap(), [1]);
+
+    let listener = event.listen();
+    event.notify(1.tag(2));
+    assert!(listener.discard());
+    assert_eq!(*dropped.lock().unwrap(), [1, 2]);
+
+    // Rejected tags are reported, unless the report returns them.
+    let _odd = event.listen_filter(|n| n % 2 == 1);
+    assert_eq!(event.notify(1.tag(4)), 0);
+    let report = event.notify_report(1.tag_iter(vec![6]));
+    assert_eq!(report.into_undelivered().collect::<Vec<_>>(), [6]);
+    assert_eq!(*dropped.lock().unwrap(), [1, 2, 4]);
+
+    event.clear_undelivered();
+    assert_eq!(event.notify(1.tag(8)), 0);
+    assert_eq!(*dropped.lock().unwrap(), [1, 2, 4]);
+}
+
+#[cfg(feature = "std")]
+#[test]
+fn undelivered_unused_tags() {
+    use event_listener::{IntoNotification, Listener};
+
+    let event = Event::<u32>::with_tag();
+    let dropped = Arc::new(Mutex::new(Vec::new()));
+    event.on_undelivered({
+        let dropped = dropped.clone();
+        move |tag| dropped.lock().unwrap().push(tag)
+    });
+
+    // Nobody is listening.
+    assert_eq!(event.notify(1.tag(1)), 0);
+    assert_eq!(*dropped.lock().unwrap(), [1]);
+
+    // The listener is already notified.
+    let listener = event.listen();
+    event.notify(1.tag(2));
+    assert_eq!(event.notify(1.tag(3).map_tag(|n| n * 10)), 0);
+    assert_eq!(*dropped.lock().unwrap(), [1, 30]);
+
+    // A tag that reaches a listener is not reported, and tags that are produced on demand are
+    // never produced for missing listeners.
+    let second = event.listen();
+    assert_eq!(event.notify(1.additional().tag(4)), 1);
+    assert_eq!(event.notify(3.tag_with(|| 5)), 0);
+    assert_eq!(listener.wait(), 2);
+    assert_eq!(second.wait(), 4);
+    assert_eq!(*dropped.lock().unwrap(), [1, 30]);
+}
+
+#[cfg(all(feature = "std", not(target_family = "wasm")))]
+#[test]
+fn tags_in_registration_order() {
+    use event_listener::{IntoNotification, Listener};
+    use std::sync::mpsc;
+    use std::thread;
+
+    let event = Arc::new(Event::<usize>::with_tag());
+    let (tx, rx) = mpsc::channel();
+
+    // Register listeners from several threads at once.
+    let handles = (0..4)
+        .map(|_| {
+            let event = event.clone();
+            let tx = tx.clone();
+            thread::spawn(move || {
+                for _ in 0..50 {
+                    let listener = event.listen();
+                    tx.send((listener.id().unwrap(), listener)).unwrap();
+                }
+            })
+        })
+        .collect::<Vec<_>>();
+    drop(tx);
+
+    // Notify some of them while the others are still registering.
+    let mut listeners = Vec::new();
+    while listeners.len() < 20 {
+        listeners.push(rx.recv().unwrap());
+    }
+    let first = event.notify(usize::MAX.additional().tag_iter(0..));
+
+    for handle in handles {
+        handle.join().unwrap();
+    }
+    listeners.extend(rx);
+    let second = event.notify(usize::MAX.additional().tag_iter(first..));
+    assert_eq!(first + second, 200);
+
+    // Listener IDs increase in registration order.
+    listeners.sort_by_key(|(id, _)| *id);
+    let tags = listeners
+        .into_iter()
+        .map(|(_, listener)| listener.wait())
+        .collect::<Vec<_>>();
+    assert_eq!(tags, (0..200).collect::<Vec<_>>());
+}
+
+#[cfg(feature = "std")]
+#[test]
+fn panicking_tag() {
+    use event_listener::{IntoNotification, Listener};
+    use std::panic::{self, AssertUnwindSafe};
+
+    let event = Event::<u32>::with_tag();
+    let mut listeners = (0..3).map(|_| event.listen()).collect::<Vec<_>>();
+
+    // The notification stops at the panicking tag, and the panic reaches the notifier.
+    let mut calls = 0;
+    let result = panic::catch_unwind(AssertUnwindSafe(|| {
+        event.notify(3.additional().tag_with(|| {
+            calls += 1;
+            if calls == 2 {
+                panic!("tag failed");
+            }
+            calls
+        }))
+    }));
+    assert!(result.is_err());
+
+    // The listeners that were not notified yet are still waiting.
+    let rest = listeners.split_off(1);
+    assert_eq!(event.notify(usize::MAX.additional().tag(7)), 2);
+    assert_eq!(listeners.pop().unwrap().wait(), 1);
+    for listener in rest {
+        assert_eq!(listener.wait(), 7);
+    }
+}
+
+#[cfg(feature = "std")]
+#[test]
+fn panicking_tag_clone() {
+    use event_listener::{IntoNotification, Listener};
+    use std::panic::{self, AssertUnwindSafe};
+    use std::sync::atomic::{AtomicUsize, Ordering};
+
+    static CLONES: AtomicUsize = AtomicUsize::new(0);
+
+    #[derive(Debug, PartialEq)]
+    struct Fragile;
+
+    impl Clone for Fragile {
+        fn clone(&self) -> Self {
+            if CLONES.fetch_add(1, Ordering::SeqCst) == 1 {
+                panic!("clone failed");
+            }
+            Fragile
+        }
+    }
+
+    let event = Event::<Fragile>::with_tag();
+    let first = event.listen();
+    let second = event.listen();
+
+    let result = panic::catch_unwind(AssertUnwindSafe(|| event.notify(2.tag(Fragile))));
+    assert!(result.is_err());
+    assert_eq!(event.total_listeners(), 2);
+
+    assert_eq!(first.wait(), Fragile);
+    assert_eq!(event.notify(1.additional().tag(Fragile)), 1);
+    assert_eq!(second.wait(), Fragile);
+}
+
+#[cfg(feature = "std")]
+#[test]
+fn panicking_tag_keeps_stored_units() {
+    use event_listener::IntoNotification;
+    use std::panic::{self, AssertUnwindSafe};
+
+    let event = Event::counting();
+    event.notify(2);
+    let mut heavy = event.listen_weighted(3);
+    assert!(!is_notified(&mut heavy));
+
+    let result = panic::catch_unwind(AssertUnwindSafe(|| {
+        event.notify(1.additional().tag_with(|| panic!("tag failed")))
+    }));
+    assert!(result.is_err());
+
+    // The stored units are not lost, and the new unit is kept along with them.
+    assert_eq!(event.pending_notifications(), 3);
+}
+
+#[cfg(feature = "std")]
+#[test]
+fn notify_all_and_close() {
+    use event_listener::WaitError;
+
+    let event = Event::<u32>::with_tag();
+    let first = event.listen().checked();
+    let second = event.listen().checked();
+    let picky = event.listen_filter(|tag| *tag != 5).checked();
+
+    assert!(!event.is_closed());
+    assert_eq!(event.notify_all_and_close(5), 2);
+    assert!(event.is_closed());
+
+    assert_eq!(first.wait(), Ok(5));
+    assert_eq!(second.wait(), Ok(5));
+    assert_eq!(picky.wait(), Err(WaitError::Closed));
+    assert_eq!(event.listen().checked().wait(), Err(WaitError::Closed));
+    assert_eq!(event.total_listeners(), 0);
+}
+
+#[cfg(feature = "std")]
+#[test]
+fn notify_all_and_close_panicking_clone() {
+    use event_listener::WaitError;
+    use std::panic::{self, AssertUnwindSafe};
+    use std::sync::atomic::{AtomicUsize, Ordering};
+
+    static CLONES: AtomicUsize = AtomicUsize::new(0);
+
+    #[derive(Debug, PartialEq)]
+    struct Fragile;
+
+    impl Clone for Fragile {
+        fn clone(&self) -> Self {
+            if CLONES.fetch_add(1, Ordering::SeqCst) == 1 {
+                panic!("clone failed");
+            }
+            Fragile
+        }
+    }
+
+    let event = Event::<Fragile>::with_tag();
+    let first = event.listen().checked();
+    let second = event.listen().checked();
+
+    // The panic reaches the caller, and the event is closed anyway.
+    let result = panic::catch_unwind(AssertUnwindSafe(|| event.notify_all_and_close(Fragile)));
+    assert!(result.is_err());
+    assert!(event.is_closed());
+
+    assert_eq!(first.wait(), Ok(Fragile));
+    assert_eq!(second.wait(), Err(WaitError::Closed));
+}
+
+#[cfg(all(feature = "std", not(target_family = "wasm")))]
+#[test]
+fn notify_all_and_close_concurrent() {
+    use std::thread;
+
+    let event = Arc::new(Event::new());
+
+    // Every listener completes, whether it registered before or after the event was closed.
+    let handles = (0..4)
+        .map(|_| {
+            let event = event.clone();
+            thread::spawn(move || {
+                for _ in 0..100 {
+                    let _ = event.listen().checked().wait();
+                }
+            })
+        })
+        .collect::<Vec<_>>();
+
+    event.notify_all_and_close(());
+    for handle in handles {
+        handle.join().unwrap();
+    }
+}
+
+#[cfg(feature = "std")]
+#[test]
+fn poll_wait() {
+    use event_listener::IntoNotification;
+
+    let event = Event::<usize>::with_tag();
+    let mut listener = event.listen();
+    let waker = waker_fn(|| ());
+    let mut cx = Context::from_waker(&waker);
+
+    assert!(listener.poll_wait(&mut cx).is_pending());
+    event.notify(1.tag(3));
+    assert_eq!(listener.poll_wait(&mut cx), std::task::Poll::Ready(3));
+}
+
+#[cfg(all(feature = "std", not(target_family = "wasm")))]
+#[test]
+fn wait_canceller() {
+    use event_listener::{Listener, WaitError};
+    use std::thread;
+    use std::time::Duration;
+
+    let event = Event::new();
+    let listener = event.listen();
+    let canceller = listener.canceller().unwrap();
+    assert_eq!(Some(canceller.id()), listener.id());
+
+    let handle = thread::spawn(move || listener.checked().wait());
+    thread::sleep(Duration::from_millis(50));
+
+    assert!(canceller.cancel());
+    assert_eq!(handle.join().unwrap(), Err(WaitError::Cancelled));
+    assert!(!canceller.cancel());
+
+    // A notified listener keeps its notification.
+    let listener = event.listen();
+    let canceller = listener.canceller().unwrap();
+    event.notify(1);
+    assert!(!canceller.cancel());
+    listener.wait();
+}
+
+#[cfg(feature = "std")]
+#[test]
+fn try_drop() {
+    use event_listener::IntoNotification;
+
+    let event = Arc::new(Event::<i32>::with_tag());
+    let slot = Arc::new(Mutex::new(None::<EventListener<i32>>));
+    let result = Arc::new(Mutex::new(None));
+
+    // Filters run while the listener list is locked.
+    let filtered = event.listen_filter({
+        let slot = slot.clone();
+        let result = result.clone();
+        move |_| {
+            if let Some(listener) = slot.lock().unwrap().take() {
+                *result.lock().unwrap() = Some(listener.try_drop());
+            }
+            true
+        }
+    });
+    *slot.lock().unwrap() = Some(event.listen());
+
+    event.notify(1.tag(0));
+    let listener = match result.lock().unwrap().take() {
+        Some(Err(listener)) => listener,
+        _ => panic!("expected the list to be locked"),
+    };
+    drop(filtered);
+
+    assert_eq!(event.total_listeners(), 1);
+    assert!(listener.try_drop().is_ok());
+    assert_eq!(event.total_listeners(), 0);
+}
+
+#[cfg(feature = "std")]
+#[test]
+fn event_builder() {
+    use event_listener::{EventBuilder, IntoNotification, Listener};
+
+    let root = Arc::new(Event::new());
+    let event = Event::builder().counting(true).parent(root.clone()).build();
+    let root_listener = root.listen();
+
+    event.notify(2.additional());
+    event.listen().wait();
+    event.listen().wait();
+    root_listener.wait();
+
+    // Turning counting off again gives a plain event.
+    let event = Event::builder().counting(true).counting(false).build();
+    event.notify(1.additional());
+    assert!(!is_notified(&mut event.listen()));
+
+    let dropped = Arc::new(Mutex::new(Vec::new()));
+    let event = EventBuilder::<i32>::new()
+        .on_undelivered({
+            let dropped = dropped.clone();
+            move |tag| dropped.lock().unwrap().push(tag)
+        })
+        .build();
+    let listener = event.listen();
+    event.notify(1.tag(5));
+    drop(listener);
+    assert_eq!(*dropped.lock().unwrap(), [5]);
+}
+
+#[test]
+fn notify_from_isr() {
+    use event_listener::IntoNotification;
+
+    let event = Event::new();
+    let mut l1 = event.listen();
+    let mut l2 = event.listen();
+    let mut l3 = event.listen();
+
+    event.notify_from_isr(1);
+    event.notify_from_isr(1);
+    assert!(is_notified(&mut l1));
+    assert!(!is_notified(&mut l2));
+
+    event.notify_from_isr(2.additional());
+    assert!(is_notified(&mut l2));
+    assert!(is_notified(&mut l3));
+}
+
+#[cfg(feature = "std")]
+#[test]
+fn notify_from_isr_while_locked() {
+    use event_listener::IntoNotification;
+    use std::sync::atomic::{AtomicBool, Ordering};
+
+    let event = Arc::new(Event::new());
+    let interrupted = Arc::new(AtomicBool::new(false));
+
+    // Filters run while the listener list is locked, like a thread that is interrupted while
+    // holding it.
+    let mut filtered = event.listen_filter({
+        let event = event.clone();
+        let interrupted = interrupted.clone();
+        move |_| {
+            if !interrupted.swap(true, Ordering::SeqCst) {
+                event.notify_from_isr(1.additional());
+            }
+            true
+        }
+    });
+    let mut listener = event.listen();
+
+    // The interrupt's notification is delivered before the list is unlocked.
+    event.notify(1);
+    assert!(interrupted.load(Ordering::SeqCst));
+    assert!(is_notified(&mut filtered));
+    assert!(is_notified(&mut listener));
+}
+
+#[cfg(all(feature = "std", not(target_family = "wasm")))]
+#[test]
+fn drain() {
+    use event_listener::WaitError;
+    use std::thread;
+
+    let event = Arc::new(Event::new());
+    assert_eq!(event.drain(), 0);
+
+    // Notified listeners keep their notification.
+    let notified = event.listen();
+    assert_eq!(event.notify(1), 1);
+
+    // Blocked listeners are woken up by the drain.
+    let handles = (0..3)
+        .map(|_| {
+            let listener = event.listen();
+            thread::spawn(move || listener.checked().wait())
+        })
+        .collect::<Vec<_>>();
+
+    assert_eq!(event.drain(), 3);
+    for handle in handles {
+        assert_eq!(handle.join().unwrap(), Err(WaitError::Drained));
+    }
+    assert_eq!(notified.checked().wait(), Ok(()));
+    assert_eq!(event.total_listeners(), 0);
+
+    // The event is not closed.
+    assert!(!event.is_closed());
+    let mut listener = event.listen();
+    assert_eq!(event.notify(1), 1);
+    assert!(is_notified(&mut listener));
+}
+
+#[cfg(all(feature = "std", not(target_family = "wasm")))]
+#[test]
+fn notify_hands_off_to_waiter() {
+    use event_listener::{IntoNotification, Listener};
+    use std::sync::mpsc;
+    use std::thread;
+
+    let event = Arc::new(Event::<i32>::with_tag());
+    let (tx, rx) = mpsc::channel();
+
+    let waiter = {
+        let listener = event.listen();
+        thread::spawn(move || {
+            tx.send(()).unwrap();
+            listener.wait()
+        })
+    };
+    rx.recv().unwrap();
+
+    // A listener that registers after the notification, but before the waiter wakes up, cannot
+    // take the notification away from it.
+    assert_eq!(event.notify(1.tag(7)), 1);
+    let mut late = event.listen();
+    let waker = waker_fn(|| ());
+    assert!(Pin::new(&mut late)
+        .poll(&mut Context::from_waker(&waker))
+        .is_pending());
+
+    assert_eq!(waiter.join().unwrap(), 7);
+    assert!(Pin::new(&mut late)
+        .poll(&mut Context::from_waker(&waker))
+        .is_pending());
+}
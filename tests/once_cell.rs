@@ -0,0 +1,113 @@
+#![cfg(feature = "std")]
+
+use std::future::Future;
+use std::task::{Context, Poll};
+
+use event_listener::once_cell::OnceCell;
+use futures_lite::future;
+use waker_fn::waker_fn;
+
+#[cfg(target_family = "wasm")]
+use wasm_bindgen_test::wasm_bindgen_test as test;
+
+#[test]
+fn set_and_get() {
+    let mut cell = OnceCell::new();
+    assert_eq!(cell.get(), None);
+    assert_eq!(cell.set(1), Ok(&1));
+    assert_eq!(cell.set(2), Err(2));
+    assert_eq!(cell.get(), Some(&1));
+
+    assert_eq!(cell.take(), Some(1));
+    assert_eq!(cell.get(), None);
+    assert_eq!(OnceCell::from(3).into_inner(), Some(3));
+}
+
+#[test]
+fn waiters_complete_when_initializer_finishes() {
+    let cell = OnceCell::new();
+    let waker = waker_fn(|| ());
+    let mut cx = Context::from_waker(&waker);
+
+    let (tx, rx) = std::sync::mpsc::channel::<()>();
+    let mut init = Box::pin(cell.get_or_init(|| async move {
+        // Stay pending until the test says so.
+        future::poll_fn(|_| match rx.try_recv() {
+            Ok(()) => Poll::Ready(()),
+            Err(_) => Poll::Pending,
+        })
+        .await;
+        7
+    }));
+    let mut waiter = Box::pin(cell.get_or_init(|| async { panic!("the waiter must not run") }));
+
+    assert!(init.as_mut().poll(&mut cx).is_pending());
+    assert!(waiter.as_mut().poll(&mut cx).is_pending());
+
+    tx.send(()).unwrap();
+    assert_eq!(init.as_mut().poll(&mut cx), Poll::Ready(&7));
+    assert_eq!(waiter.as_mut().poll(&mut cx), Poll::Ready(&7));
+}
+
+#[test]
+fn cancelled_initializer_rearms_waiters() {
+    let cell = OnceCell::new();
+    let waker = waker_fn(|| ());
+    let mut cx = Context::from_waker(&waker);
+
+    let mut init = Box::pin(cell.get_or_init(future::pending::<i32>));
+    let mut waiter = Box::pin(cell.get_or_try_init(|| async { Ok::<_, ()>(2) }));
+
+    assert!(init.as_mut().poll(&mut cx).is_pending());
+    assert!(waiter.as_mut().poll(&mut cx).is_pending());
+
+    drop(init);
+    assert_eq!(waiter.as_mut().poll(&mut cx), Poll::Ready(Ok(&2)));
+}
+
+#[test]
+fn failed_initializer_leaves_the_cell_empty() {
+    let cell = OnceCell::<i32>::new();
+
+    future::block_on(async {
+        assert_eq!(
+            cell.get_or_try_init(|| async { Err("no") }).await,
+            Err("no")
+        );
+        assert_eq!(cell.get(), None);
+        assert_eq!(
+            cell.get_or_try_init(|| async { Ok::<_, ()>(5) }).await,
+            Ok(&5)
+        );
+    });
+}
+
+#[cfg(not(target_family = "wasm"))]
+#[test]
+fn initializer_runs_once_across_threads() {
+    use std::sync::atomic::{AtomicUsize, Ordering};
+    use std::sync::Arc;
+    use std::thread;
+    use std::time::Duration;
+
+    let cell = Arc::new(OnceCell::new());
+    let runs = Arc::new(AtomicUsize::new(0));
+
+    let handles: Vec<_> = (0..8)
+        .map(|i| {
+            let (cell, runs) = (cell.clone(), runs.clone());
+            thread::spawn(move || {
+                let value = cell.get_or_init_blocking(|| {
+                    runs.fetch_add(1, Ordering::SeqCst);
+                    thread::sleep(Duration::from_millis(20));
+                    i
+                });
+                *value
+            })
+        })
+        .collect();
+
+    let values: Vec<_> = handles.into_iter().map(|h| h.join().unwrap()).collect();
+    assert_eq!(runs.load(Ordering::SeqCst), 1);
+    assert!(values.iter().all(|v| *v == values[0]));
+}
@@ -0,0 +1,54 @@
+#![cfg(feature = "std")]
+
+use std::future::Future;
+use std::pin::Pin;
+use std::task::{Context, Poll};
+
+use event_listener::oneshot::{self, RecvError};
+use waker_fn::waker_fn;
+
+#[cfg(target_family = "wasm")]
+use wasm_bindgen_test::wasm_bindgen_test as test;
+
+#[test]
+fn send_then_receive() {
+    let (tx, mut rx) = oneshot::channel();
+    let waker = waker_fn(|| ());
+    let mut cx = Context::from_waker(&waker);
+
+    assert_eq!(Pin::new(&mut rx).poll(&mut cx), Poll::Pending);
+    assert_eq!(tx.send(String::from("hello")), Ok(()));
+    assert_eq!(
+        Pin::new(&mut rx).poll(&mut cx),
+        Poll::Ready(Ok(String::from("hello")))
+    );
+}
+
+#[test]
+fn dropped_sender_closes_the_channel() {
+    let (tx, mut rx) = oneshot::channel::<i32>();
+    let waker = waker_fn(|| ());
+    let mut cx = Context::from_waker(&waker);
+
+    assert_eq!(Pin::new(&mut rx).poll(&mut cx), Poll::Pending);
+    drop(tx);
+    assert_eq!(Pin::new(&mut rx).poll(&mut cx), Poll::Ready(Err(RecvError)));
+}
+
+#[test]
+fn dropped_receiver_returns_the_message() {
+    let (tx, rx) = oneshot::channel();
+    drop(rx);
+    assert_eq!(tx.send(vec![1, 2]), Err(vec![1, 2]));
+}
+
+#[cfg(not(target_family = "wasm"))]
+#[test]
+fn receive_across_threads() {
+    use std::thread;
+
+    let (tx, rx) = oneshot::channel();
+    let handle = thread::spawn(move || rx.recv_blocking());
+    tx.send(5).unwrap();
+    assert_eq!(handle.join().unwrap(), Ok(5));
+}
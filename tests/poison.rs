@@ -0,0 +1,71 @@
+#![cfg(feature = "std")]
+
+use std::panic::{self, AssertUnwindSafe};
+
+use event_listener::{Event, WaitError};
+use futures_lite::future::block_on;
+
+#[cfg(target_family = "wasm")]
+use wasm_bindgen_test::wasm_bindgen_test as test;
+
+fn poison(event: &Event) {
+    let result = panic::catch_unwind(AssertUnwindSafe(|| {
+        let _guard = event.poison_guard();
+        panic!("notifier panicked");
+    }));
+    assert!(result.is_err());
+}
+
+#[test]
+fn poison_waiters() {
+    let event = Event::new();
+
+    let notified = event.listen().checked();
+    let waiting = event.listen().checked();
+    assert_eq!(event.notify(1), 1);
+
+    poison(&event);
+    assert!(event.is_poisoned());
+
+    // Notified listeners keep their notification.
+    assert_eq!(block_on(notified), Ok(()));
+    assert_eq!(block_on(waiting), Err(WaitError::Poisoned));
+
+    // New listeners complete right away.
+    assert_eq!(block_on(event.listen().checked()), Err(WaitError::Poisoned));
+    assert_eq!(event.total_listeners(), 0);
+}
+
+#[test]
+fn guard_without_panic() {
+    let event = Event::new();
+    let listener = event.listen().checked();
+
+    drop(event.poison_guard());
+    assert!(!event.is_poisoned());
+
+    assert_eq!(event.notify(1), 1);
+    assert_eq!(block_on(listener), Ok(()));
+}
+
+#[test]
+fn clear_poison() {
+    let event = Event::new();
+    poison(&event);
+
+    event.clear_poison();
+    assert!(!event.is_poisoned());
+
+    let listener = event.listen().checked();
+    assert_eq!(event.notify(1), 1);
+    assert_eq!(block_on(listener), Ok(()));
+}
+
+#[test]
+#[should_panic = "a notifier panicked while holding a poison guard"]
+fn unchecked_listener_panics() {
+    let event = Event::new();
+    let listener = event.listen();
+    poison(&event);
+    block_on(listener);
+}
@@ -0,0 +1,79 @@
+#![cfg(all(feature = "priority-inheritance", not(target_family = "wasm")))]
+
+use std::sync::atomic::{AtomicUsize, Ordering};
+use std::sync::Arc;
+use std::thread;
+use std::time::Duration;
+
+use event_listener::{Event, Listener, PriorityInheritance};
+
+#[derive(Default)]
+struct Hook {
+    /// The number of boosts that are active.
+    active: AtomicUsize,
+
+    /// The total number of boosts.
+    total: AtomicUsize,
+}
+
+impl PriorityInheritance for Hook {
+    fn boost(&self) {
+        self.active.fetch_add(1, Ordering::SeqCst);
+        self.total.fetch_add(1, Ordering::SeqCst);
+    }
+
+    fn restore(&self) {
+        self.active.fetch_sub(1, Ordering::SeqCst);
+    }
+}
+
+#[test]
+fn blocked_wait_boosts_the_producer() {
+    let hook = Arc::new(Hook::default());
+    let event = Arc::new(Event::new());
+    event.set_priority_inheritance(hook.clone());
+
+    let listener = event.listen();
+    let producer = thread::spawn({
+        let event = event.clone();
+        let hook = hook.clone();
+        move || {
+            // Wait until the waiter is blocked.
+            while hook.active.load(Ordering::SeqCst) == 0 {
+                thread::yield_now();
+            }
+            event.notify(1);
+        }
+    });
+
+    listener.wait();
+    producer.join().unwrap();
+    assert_eq!(hook.total.load(Ordering::SeqCst), 1);
+    assert_eq!(hook.active.load(Ordering::SeqCst), 0);
+}
+
+#[test]
+fn notified_wait_does_not_boost() {
+    let hook = Arc::new(Hook::default());
+    let event = Event::builder().priority_inheritance(hook.clone()).build();
+
+    let listener = event.listen();
+    event.notify(1);
+    listener.wait();
+    assert_eq!(hook.total.load(Ordering::SeqCst), 0);
+}
+
+#[test]
+fn timeout_restores() {
+    let hook = Arc::new(Hook::default());
+    let event = Event::new();
+    event.set_priority_inheritance(hook.clone());
+
+    assert_eq!(event.listen().wait_timeout(Duration::from_millis(10)), None);
+    assert_eq!(hook.total.load(Ordering::SeqCst), 1);
+    assert_eq!(hook.active.load(Ordering::SeqCst), 0);
+
+    event.clear_priority_inheritance();
+    assert_eq!(event.listen().wait_timeout(Duration::from_millis(10)), None);
+    assert_eq!(hook.total.load(Ordering::SeqCst), 1);
+}
@@ -0,0 +1,17 @@
+#![cfg(feature = "registry")]
+
+use event_listener::{Event, Listener};
+
+#[test]
+fn named_events_are_shared() {
+    let listener = Event::named("registry.shared").listen();
+    assert_eq!(Event::named("registry.shared").notify(1), 1);
+    listener.wait();
+}
+
+#[test]
+fn names_are_distinct() {
+    let _listener = Event::named("registry.first").listen();
+    assert_eq!(Event::named("registry.second").notify(1), 0);
+    assert_eq!(Event::named("registry.first").total_listeners(), 1);
+}
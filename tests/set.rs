@@ -0,0 +1,157 @@
+use std::task::{Context, Poll};
+
+use event_listener::{Event, ListenerSet};
+use waker_fn::waker_fn;
+
+#[cfg(target_family = "wasm")]
+use wasm_bindgen_test::wasm_bindgen_test as test;
+
+fn poll_next<T>(set: &mut ListenerSet<T>) -> Poll<Option<(usize, T)>> {
+    let waker = waker_fn(|| ());
+    set.poll_next(&mut Context::from_waker(&waker))
+}
+
+#[cfg(feature = "std")]
+#[test]
+fn set_yields_notified() {
+    use event_listener::IntoNotification;
+
+    let event1 = Event::<usize>::with_tag();
+    let event2 = Event::<usize>::with_tag();
+
+    let mut set = ListenerSet::new();
+    let key1 = set.insert(event1.listen());
+    let key2 = set.insert(event2.listen());
+    let key3 = set.insert(event2.listen());
+    assert_eq!(set.len(), 3);
+    assert!(poll_next(&mut set).is_pending());
+
+    event2.notify(1.tag(7));
+    assert_eq!(poll_next(&mut set), Poll::Ready(Some((key2, 7))));
+    assert!(!set.contains(key2));
+    assert!(poll_next(&mut set).is_pending());
+
+    event1.notify(1.tag(1));
+    event2.notify(1.tag(2));
+    let mut completed = vec![];
+    while let Poll::Ready(Some(completion)) = poll_next(&mut set) {
+        completed.push(completion);
+    }
+    completed.sort_unstable();
+    assert_eq!(completed, [(key1, 1), (key3, 2)]);
+
+    assert!(set.is_empty());
+    assert_eq!(poll_next(&mut set), Poll::Ready(None));
+}
+
+#[test]
+fn set_reuses_keys() {
+    let event = Event::new();
+
+    let mut set = ListenerSet::new();
+    let key1 = set.insert(event.listen());
+    let key2 = set.insert(event.listen());
+
+    assert!(set.remove(key1).is_some());
+    assert!(set.remove(key1).is_none());
+    assert_eq!(set.insert(event.listen()), key1);
+
+    assert_eq!(event.notify(1), 1);
+    assert!(set.get(key2).is_some());
+    assert_eq!(poll_next(&mut set), Poll::Ready(Some((key2, ()))));
+}
+
+#[cfg(feature = "std")]
+#[test]
+fn group_stays_attached() {
+    use event_listener::{EventGroup, Listener};
+    use std::sync::Arc;
+
+    let waker = waker_fn(|| ());
+    let mut cx = Context::from_waker(&waker);
+
+    let event1 = Arc::new(Event::new());
+    let event2 = Arc::new(Event::new());
+
+    let mut group = EventGroup::new();
+    assert_eq!(group.poll_next(&mut cx), Poll::Ready(None));
+
+    let key1 = group.attach(event1.clone());
+    let key2 = group.attach(event2.clone());
+    assert_eq!(group.poll_next(&mut cx), Poll::Pending);
+
+    // Notifications are captured even when nobody is polling.
+    event1.notify(1);
+    event2.notify(1);
+    let mut fired = vec![
+        group.poll_next(&mut cx).map(Option::unwrap),
+        group.poll_next(&mut cx).map(Option::unwrap),
+    ];
+    fired.sort_by_key(|poll| match poll {
+        Poll::Ready((key, ())) => *key,
+        Poll::Pending => usize::MAX,
+    });
+    assert_eq!(fired, [Poll::Ready((key1, ())), Poll::Ready((key2, ()))]);
+    assert_eq!(group.poll_next(&mut cx), Poll::Pending);
+
+    // The group listens again after each notification.
+    assert_eq!(event1.notify(1), 1);
+    assert_eq!(group.poll_next(&mut cx), Poll::Ready(Some((key1, ()))));
+
+    // A detached event passes its notification on.
+    let other = event2.listen();
+    event2.notify(1);
+    assert!(Arc::ptr_eq(&group.detach(key2).unwrap(), &event2));
+    assert_eq!(group.poll_next(&mut cx), Poll::Pending);
+    assert!(other.discard());
+
+    // Keys are reused.
+    assert_eq!(group.attach(event2.clone()), key2);
+    assert_eq!(group.len(), 2);
+}
+
+#[cfg(all(feature = "std", not(target_family = "wasm")))]
+#[test]
+fn wait_on_many_events() {
+    use std::sync::Arc;
+    use std::thread;
+    use std::time::Duration;
+
+    let events = (0..8).map(|_| Arc::new(Event::new())).collect::<Vec<_>>();
+    let mut set = ListenerSet::new();
+    let keys = events
+        .iter()
+        .map(|event| set.insert(event.listen()))
+        .collect::<Vec<_>>();
+
+    let notifier = thread::spawn({
+        let event = events[5].clone();
+        move || {
+            thread::sleep(Duration::from_millis(10));
+            event.notify(1);
+        }
+    });
+
+    assert_eq!(set.wait(), Some((keys[5], ())));
+    assert_eq!(set.len(), 7);
+    notifier.join().unwrap();
+
+    set.clear();
+    assert_eq!(set.wait(), None);
+}
+
+#[cfg(all(feature = "std", not(target_family = "wasm")))]
+#[test]
+fn group_wait() {
+    use event_listener::EventGroup;
+    use std::sync::Arc;
+
+    let event = Arc::new(Event::new());
+    let mut group = EventGroup::new();
+    let key = group.attach(event.clone());
+
+    event.notify(1);
+    assert_eq!(group.wait(), Some((key, ())));
+    event.notify(1);
+    assert_eq!(group.wait(), Some((key, ())));
+}
@@ -0,0 +1,185 @@
+#![cfg(feature = "std")]
+
+use std::future::Future;
+use std::pin::Pin;
+use std::sync::{Arc, Mutex};
+use std::task::{Context, Poll};
+
+use event_listener::{Event, EventListener, IntoNotification, WaitError};
+use waker_fn::waker_fn;
+
+#[cfg(target_family = "wasm")]
+use wasm_bindgen_test::wasm_bindgen_test as test;
+
+fn is_notified(listener: &mut EventListener) -> bool {
+    let waker = waker_fn(|| ());
+    Pin::new(listener)
+        .poll(&mut Context::from_waker(&waker))
+        .is_ready()
+}
+
+#[test]
+fn subscription_buffers_notifications() {
+    let event = Event::<i32>::with_tag();
+    let mut subscription = event.subscribe();
+
+    let waker = waker_fn(|| ());
+    let mut cx = Context::from_waker(&waker);
+    assert_eq!(subscription.poll_next(&mut cx), Poll::Pending);
+
+    // None of the notifications are lost while the subscription is not polled.
+    for tag in 0..3 {
+        assert_eq!(event.notify(1.tag(tag)), 1);
+    }
+    assert_eq!(subscription.buffered(), 3);
+    assert_eq!(event.total_listeners(), 1);
+
+    for tag in 0..3 {
+        assert_eq!(subscription.poll_next(&mut cx), Poll::Ready(Ok(tag)));
+    }
+    assert_eq!(subscription.poll_next(&mut cx), Poll::Pending);
+}
+
+#[test]
+fn subscription_wakes_task() {
+    let event = Event::new();
+    let mut subscription = event.subscribe();
+
+    let woken = Arc::new(Mutex::new(0));
+    let waker = {
+        let woken = woken.clone();
+        waker_fn(move || *woken.lock().unwrap() += 1)
+    };
+    let mut cx = Context::from_waker(&waker);
+
+    assert_eq!(subscription.poll_next(&mut cx), Poll::Pending);
+    assert_eq!(event.notify(usize::MAX), 1);
+    assert_eq!(*woken.lock().unwrap(), 1);
+    assert_eq!(subscription.poll_next(&mut cx), Poll::Ready(Ok(())));
+
+    // A notification of all listeners only reaches the subscription once.
+    assert_eq!(subscription.poll_next(&mut cx), Poll::Pending);
+    assert_eq!(event.notify(usize::MAX.additional()), 1);
+    assert_eq!(event.notify_all(), 1);
+    assert_eq!(subscription.buffered(), 2);
+}
+
+#[test]
+fn subscription_requeues_behind_waiters() {
+    let event = Event::new();
+    let subscription = event.subscribe();
+    let mut listener = event.listen();
+
+    // After its notification, the subscription waits behind the older listener.
+    assert_eq!(event.notify(1), 1);
+    assert_eq!(subscription.buffered(), 1);
+    assert!(!is_notified(&mut listener));
+
+    assert_eq!(event.notify(1), 1);
+    assert!(is_notified(&mut listener));
+    assert_eq!(subscription.buffered(), 1);
+
+    assert_eq!(event.notify(1.additional()), 1);
+    assert_eq!(subscription.buffered(), 2);
+}
+
+#[test]
+fn subscription_closed() {
+    let event = Event::<i32>::with_tag();
+    let mut subscription = event.subscribe();
+
+    assert_eq!(event.notify(1.tag(1)), 1);
+    assert_eq!(event.notify_all_and_close(2), 1);
+    assert_eq!(event.total_listeners(), 0);
+
+    // The buffered notifications come first.
+    futures_lite::future::block_on(async {
+        assert_eq!(subscription.next().await, Ok(1));
+        assert_eq!(subscription.next().await, Ok(2));
+        assert_eq!(subscription.next().await, Err(WaitError::Closed));
+        assert_eq!(subscription.next().await, Err(WaitError::Closed));
+    });
+}
+
+#[test]
+fn subscription_dropped() {
+    let event = Event::<i32>::with_tag();
+    let undelivered = Arc::new(Mutex::new(Vec::new()));
+    event.on_undelivered({
+        let undelivered = undelivered.clone();
+        move |tag| undelivered.lock().unwrap().push(tag)
+    });
+
+    let subscription = event.subscribe();
+    assert_eq!(event.notify(1.tag(1)), 1);
+    assert_eq!(event.notify(1.tag(2)), 1);
+
+    drop(subscription);
+    assert_eq!(*undelivered.lock().unwrap(), [1, 2]);
+    assert_eq!(event.total_listeners(), 0);
+}
+
+#[cfg(not(target_family = "wasm"))]
+#[test]
+fn subscription_across_threads() {
+    use std::thread;
+
+    let event = Arc::new(Event::<usize>::with_tag());
+    let mut subscription = event.subscribe();
+
+    let producer = {
+        let event = event.clone();
+        thread::spawn(move || {
+            for tag in 0..1000 {
+                assert_eq!(event.notify(1.tag(tag)), 1);
+            }
+        })
+    };
+
+    for tag in 0..1000 {
+        assert_eq!(subscription.next_blocking(), Ok(tag));
+    }
+    producer.join().unwrap();
+}
+
+#[test]
+fn subscription_counting() {
+    let event = Event::counting();
+    assert_eq!(event.notify(3), 0);
+
+    // The subscription takes every stored unit.
+    let mut subscription = event.subscribe();
+    assert_eq!(subscription.buffered(), 3);
+    assert_eq!(event.pending_notifications(), 0);
+
+    let waker = waker_fn(|| ());
+    let mut cx = Context::from_waker(&waker);
+    for _ in 0..3 {
+        assert_eq!(subscription.poll_next(&mut cx), Poll::Ready(Ok(())));
+    }
+    assert_eq!(subscription.poll_next(&mut cx), Poll::Pending);
+
+    // Units that arrive while it is waiting do not stay stored either.
+    assert_eq!(event.notify(2.additional()), 1);
+    assert_eq!(subscription.buffered(), 2);
+    assert_eq!(event.pending_notifications(), 0);
+}
+
+#[test]
+fn subscription_latched() {
+    let event = Event::latched();
+    let mut subscription = event.subscribe();
+
+    let waker = waker_fn(|| ());
+    let mut cx = Context::from_waker(&waker);
+    assert_eq!(subscription.poll_next(&mut cx), Poll::Pending);
+
+    // While the latch is open, every wait completes right away.
+    event.notify(1);
+    for _ in 0..3 {
+        assert_eq!(subscription.poll_next(&mut cx), Poll::Ready(Ok(())));
+    }
+
+    event.reset();
+    assert_eq!(subscription.poll_next(&mut cx), Poll::Pending);
+}
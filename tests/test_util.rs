@@ -0,0 +1,158 @@
+#![cfg(feature = "test-util")]
+
+use std::future::Future;
+use std::pin::Pin;
+use std::task::Context;
+
+use event_listener::test_util::{assert_no_alloc, CountingAllocator, MockEvent};
+use event_listener::{listener, Event, EventListener, IntoNotification};
+use waker_fn::waker_fn;
+
+fn is_notified<T>(listener: &mut EventListener<T>) -> bool {
+    let waker = waker_fn(|| ());
+    Pin::new(listener)
+        .poll(&mut Context::from_waker(&waker))
+        .is_ready()
+}
+
+#[test]
+fn step_delivers_in_order() {
+    let event = MockEvent::<u8>::with_tag();
+
+    let mut l1 = event.listen();
+    let mut l2 = event.listen();
+    event.assert_listeners(2);
+
+    event.notify(1.additional().tag(1));
+    event.notify(1.additional().tag(2));
+    event.assert_pending(2);
+    assert!(!is_notified(&mut l1));
+
+    assert_eq!(event.step(), Some(1));
+    assert!(is_notified(&mut l1));
+    assert!(!is_notified(&mut l2));
+    event.assert_listeners(1);
+
+    assert_eq!(event.run(), 1);
+    assert!(is_notified(&mut l2));
+    assert_eq!(event.step(), None);
+}
+
+#[test]
+#[should_panic = "expected 2 registered listeners, found 1"]
+fn assert_listeners_fails() {
+    let event = MockEvent::new();
+    let _listener = event.listen();
+    event.assert_listeners(2);
+}
+
+#[global_allocator]
+static ALLOCATOR: CountingAllocator = CountingAllocator::new();
+
+#[test]
+fn stack_listener_does_not_allocate() {
+    let event = Event::new();
+
+    // Allocate the state of the event, which notifying alone no longer does.
+    drop(event.listen());
+    let waker = waker_fn(|| ());
+
+    assert_no_alloc(|| {
+        listener!(event => listener);
+        let mut cx = Context::from_waker(&waker);
+        assert!(Pin::new(&mut listener).poll(&mut cx).is_pending());
+        assert_eq!(event.notify(1), 1);
+        assert!(Pin::new(&mut listener).poll(&mut cx).is_ready());
+    });
+}
+
+#[test]
+fn unused_event_does_not_allocate() {
+    assert_eq!(std::mem::size_of::<Event>(), std::mem::size_of::<usize>());
+
+    assert_no_alloc(|| {
+        let events = [Event::new(), Event::new(), Event::new()];
+        for event in &events {
+            assert_eq!(event.notify(1), 0);
+            assert_eq!(event.notify(1.additional()), 0);
+            assert_eq!(event.total_listeners(), 0);
+        }
+        drop(events);
+    });
+}
+
+#[test]
+#[should_panic(expected = "expected no allocations")]
+fn assert_no_alloc_catches_allocations() {
+    let event = Event::new();
+    assert_no_alloc(|| drop(event.listen()));
+}
+
+#[test]
+fn interleaver_finds_no_lost_wakeups() {
+    use event_listener::test_util::{Interleaver, Ledger};
+    use std::cell::RefCell;
+    use std::collections::VecDeque;
+    use std::rc::Rc;
+
+    for seed in 0..200 {
+        let event = Rc::new(Event::new());
+        let queue = Rc::new(RefCell::new(VecDeque::new()));
+        let ledger = Rc::new(Ledger::new());
+        let mut interleaver = Interleaver::new(seed);
+        interleaver.spurious_polls(true);
+
+        for _ in 0..3 {
+            let (event, queue, ledger) = (event.clone(), queue.clone(), ledger.clone());
+            interleaver.spawn(async move {
+                let id = ledger.start();
+                queue.borrow_mut().push_back(id);
+                event.notify(1.additional());
+            });
+        }
+
+        for _ in 0..3 {
+            let (event, queue, ledger) = (event.clone(), queue.clone(), ledger.clone());
+            interleaver.spawn(async move {
+                loop {
+                    if let Some(id) = queue.borrow_mut().pop_front() {
+                        ledger.complete(id);
+                        return;
+                    }
+
+                    let listener = event.listen();
+                    if let Some(id) = queue.borrow_mut().pop_front() {
+                        ledger.complete(id);
+                        return;
+                    }
+                    listener.await;
+                }
+            });
+        }
+
+        interleaver.run();
+        ledger.assert_all_completed();
+    }
+}
+
+#[test]
+#[should_panic = "lost wakeup: 1 tasks are waiting, but none of them was woken up (seed 7)"]
+fn interleaver_catches_lost_wakeups() {
+    use event_listener::test_util::Interleaver;
+
+    let event = Event::new();
+    let mut interleaver = Interleaver::new(7);
+    interleaver.spawn(event.listen());
+    interleaver.run();
+}
+
+#[test]
+#[should_panic = "operation 0 completed twice"]
+fn ledger_catches_double_completion() {
+    use event_listener::test_util::Ledger;
+
+    let ledger = Ledger::new();
+    let id = ledger.start();
+    ledger.complete(id);
+    ledger.complete(id);
+}
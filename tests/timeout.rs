@@ -0,0 +1,231 @@
+#![cfg(all(feature = "std", not(target_family = "wasm")))]
+
+use std::future::Future;
+use std::pin::Pin;
+use std::task::{Context, Poll};
+use std::thread;
+use std::time::Duration;
+
+use event_listener::{Event, EventListener, IntoNotification};
+use futures_lite::future::block_on;
+use waker_fn::waker_fn;
+
+fn is_notified(listener: &mut EventListener) -> bool {
+    let waker = waker_fn(|| ());
+    Pin::new(listener)
+        .poll(&mut Context::from_waker(&waker))
+        .is_ready()
+}
+
+/// Checks that the timer thread still expires listeners when they are only polled.
+fn assert_timer_running(event: &Event) {
+    let mut listener = event.listen_timeout(Duration::from_millis(10));
+    let waker = waker_fn(|| ());
+    let mut cx = Context::from_waker(&waker);
+
+    for _ in 0..200 {
+        if let Poll::Ready(tag) = Pin::new(&mut listener).poll(&mut cx) {
+            assert_eq!(tag, None);
+            return;
+        }
+        thread::sleep(Duration::from_millis(10));
+    }
+    panic!("the timer thread stopped");
+}
+
+#[test]
+fn timeout_expires() {
+    let event = Event::new();
+    let listener = event.listen_timeout(Duration::from_millis(10));
+    assert_eq!(block_on(listener), None);
+}
+
+#[test]
+fn timeout_notified() {
+    let event = Event::new();
+    let listener = event.listen_timeout(Duration::from_secs(60));
+    assert_eq!(event.notify(1), 1);
+    assert_eq!(block_on(listener), Some(()));
+}
+
+#[test]
+fn timeout_removes_listener() {
+    let event = Event::new();
+
+    let _expired = event.listen_timeout(Duration::from_millis(10));
+    let mut waiting = event.listen();
+
+    // Once the deadline passes, the expired listener no longer takes up notifications.
+    thread::sleep(Duration::from_millis(100));
+    assert_eq!(event.total_listeners(), 1);
+    assert_eq!(event.notify(1.additional()), 1);
+    assert!(is_notified(&mut waiting));
+}
+
+#[test]
+fn timeout_dropped_before_deadline() {
+    let event = Event::new();
+
+    let first = event.listen_timeout(Duration::from_millis(20));
+    let second = event.listen();
+    drop(first);
+
+    thread::sleep(Duration::from_millis(50));
+    assert_eq!(event.notify(1), 1);
+    block_on(second);
+}
+
+#[test]
+fn timeout_checked() {
+    use event_listener::WaitError;
+    use std::panic::{self, AssertUnwindSafe};
+
+    let event = Event::new();
+
+    let expired = event.listen_timeout(Duration::from_millis(10)).checked();
+    assert_eq!(block_on(expired), Err(WaitError::TimedOut));
+    let expired = event.listen_timeout(Duration::from_millis(10)).checked();
+    assert_eq!(expired.wait(), Err(WaitError::TimedOut));
+
+    let notified = event.listen_timeout(Duration::from_secs(60)).checked();
+    assert_eq!(event.notify(1), 1);
+    assert_eq!(notified.wait(), Ok(()));
+
+    // A poisoned event reports an error instead of panicking.
+    let polled = event.listen_timeout(Duration::from_secs(60)).checked();
+    let waited = event.listen_timeout(Duration::from_secs(60)).checked();
+    let result = panic::catch_unwind(AssertUnwindSafe(|| {
+        let _guard = event.poison_guard();
+        panic!("notifier panicked");
+    }));
+    assert!(result.is_err());
+    assert_eq!(block_on(polled), Err(WaitError::Poisoned));
+    assert_eq!(waited.wait(), Err(WaitError::Poisoned));
+}
+
+#[test]
+fn timeout_remaining() {
+    let event = Event::new();
+    let mut listener = event.listen_timeout(Duration::from_secs(60));
+
+    assert_eq!(event.notify(1), 1);
+    assert_eq!(block_on(&mut listener), Some(()));
+
+    // The unused budget is still available after the notification.
+    let remaining = listener.remaining().unwrap();
+    assert!(remaining > Duration::from_secs(30));
+    assert!(remaining <= Duration::from_secs(60));
+
+    let listener = event.listen_timeout(Duration::from_millis(1)).checked();
+    thread::sleep(Duration::from_millis(5));
+    assert_eq!(listener.remaining(), Some(Duration::ZERO));
+}
+
+#[test]
+fn notify_after() {
+    let event = Event::new();
+    let listener = event.listen();
+
+    let scheduled = event.notify_after(Duration::from_millis(10), 1);
+    assert!(scheduled.deadline().is_some());
+    block_on(listener);
+
+    // The notification has been delivered, so it can no longer be cancelled.
+    assert!(!scheduled.cancel());
+}
+
+#[test]
+fn notify_at_cancelled() {
+    use std::time::Instant;
+
+    let event = Event::new();
+    let mut listener = event.listen();
+
+    let scheduled = event.notify_at(Instant::now() + Duration::from_millis(10), 1);
+    assert!(scheduled.cancel());
+
+    thread::sleep(Duration::from_millis(50));
+    assert!(!is_notified(&mut listener));
+}
+
+#[test]
+fn notify_after_outlives_event() {
+    let event = Event::new();
+    let listener = event.listen();
+
+    // The scheduled notification keeps the event alive, and dropping the handle does not
+    // cancel it.
+    let _ = event.notify_after(Duration::from_millis(10), 1);
+    drop(event);
+    block_on(listener);
+}
+
+#[test]
+fn tick_every() {
+    let event = Event::new();
+    let ticker = event.tick_every(Duration::from_millis(5), usize::MAX);
+    assert_eq!(ticker.period(), Duration::from_millis(5));
+
+    for _ in 0..3 {
+        let listeners = [event.listen(), event.listen()];
+        for listener in listeners {
+            block_on(listener);
+        }
+    }
+
+    // No more ticks once the ticker is dropped, and a tick that was in flight has completed.
+    drop(ticker);
+    thread::sleep(Duration::from_millis(20));
+    let mut listener = event.listen();
+    thread::sleep(Duration::from_millis(50));
+    assert!(!is_notified(&mut listener));
+}
+
+#[test]
+#[should_panic = "tick period must be non-zero"]
+fn tick_every_zero() {
+    let event = Event::new();
+    let _ticker = event.tick_every(Duration::ZERO, 1);
+}
+
+#[test]
+fn notify_after_panics() {
+    let event = Event::new();
+    let _listener = event.listen();
+
+    // A panicking tag does not stop the timer thread.
+    let _ = event.notify_after(
+        Duration::from_millis(1),
+        1.tag_with(|| -> () { panic!("tag panicked") }),
+    );
+    thread::sleep(Duration::from_millis(20));
+
+    assert_timer_running(&event);
+}
+
+#[test]
+fn tick_every_panics() {
+    use std::sync::atomic::{AtomicUsize, Ordering};
+    use std::sync::Arc;
+
+    /// A tag that panics the first time it is cloned.
+    struct Bomb(Arc<AtomicUsize>);
+
+    impl Clone for Bomb {
+        fn clone(&self) -> Self {
+            if self.0.fetch_add(1, Ordering::SeqCst) == 0 {
+                panic!("tick panicked");
+            }
+            Bomb(self.0.clone())
+        }
+    }
+
+    let event = Event::<Bomb>::with_tag();
+    let clones = Arc::new(AtomicUsize::new(0));
+    let _ticker = event.tick_every(Duration::from_millis(5), 1.tag(Bomb(clones.clone())));
+
+    // The timer keeps running, and so does the ticker.
+    thread::sleep(Duration::from_millis(50));
+    assert_timer_running(&Event::new());
+    assert!(clones.load(Ordering::SeqCst) > 1);
+}
@@ -0,0 +1,41 @@
+#![cfg(feature = "tokio")]
+
+use event_listener::Event;
+use futures_lite::future;
+
+fn latched() -> Event {
+    let event = Event::builder().latched(true).build();
+    event.notify(1);
+    event
+}
+
+#[test]
+fn listeners_obey_the_budget() {
+    let runtime = tokio::runtime::Builder::new_current_thread()
+        .build()
+        .unwrap();
+    let event = latched();
+
+    runtime.block_on(async {
+        // Every listener of a latched event is ready, so only the budget can stop the loop.
+        for _ in 0..10_000 {
+            let mut listener = event.listen();
+            if future::poll_once(&mut listener).await.is_none() {
+                return;
+            }
+        }
+        panic!("the task never had to yield");
+    });
+}
+
+#[test]
+fn no_budget_outside_tokio() {
+    let event = latched();
+
+    future::block_on(async {
+        for _ in 0..10_000 {
+            let mut listener = event.listen();
+            assert_eq!(future::poll_once(&mut listener).await, Some(()));
+        }
+    });
+}
@@ -0,0 +1,92 @@
+#![cfg(feature = "std")]
+
+use std::future::Future;
+use std::pin::Pin;
+use std::task::{Context, Poll};
+
+use event_listener::wait_group::WaitGroup;
+use waker_fn::waker_fn;
+
+#[cfg(target_family = "wasm")]
+use wasm_bindgen_test::wasm_bindgen_test as test;
+
+#[test]
+fn wait_completes_when_counter_hits_zero() {
+    let group = WaitGroup::new();
+    let waker = waker_fn(|| ());
+    let mut cx = Context::from_waker(&waker);
+
+    group.add(2);
+    let mut first = group.wait();
+    let mut second = group.wait();
+    assert_eq!(Pin::new(&mut first).poll(&mut cx), Poll::Pending);
+    assert_eq!(Pin::new(&mut second).poll(&mut cx), Poll::Pending);
+
+    group.done();
+    assert_eq!(Pin::new(&mut first).poll(&mut cx), Poll::Pending);
+
+    group.done();
+    assert_eq!(Pin::new(&mut first).poll(&mut cx), Poll::Ready(()));
+    assert_eq!(Pin::new(&mut second).poll(&mut cx), Poll::Ready(()));
+}
+
+#[test]
+fn wait_on_empty_group_is_ready() {
+    let group = WaitGroup::new();
+    let waker = waker_fn(|| ());
+    let mut cx = Context::from_waker(&waker);
+
+    assert_eq!(Pin::new(&mut group.wait()).poll(&mut cx), Poll::Ready(()));
+}
+
+#[test]
+fn reuse_across_rounds() {
+    let group = WaitGroup::new();
+    let waker = waker_fn(|| ());
+    let mut cx = Context::from_waker(&waker);
+
+    group.add(1);
+    let mut round_one = group.wait();
+    assert_eq!(Pin::new(&mut round_one).poll(&mut cx), Poll::Pending);
+    group.done();
+
+    // The next round starts before the first waiter is polled again.
+    group.add(1);
+    let mut round_two = group.wait();
+    assert_eq!(Pin::new(&mut round_one).poll(&mut cx), Poll::Ready(()));
+    assert_eq!(Pin::new(&mut round_two).poll(&mut cx), Poll::Pending);
+
+    group.done();
+    assert_eq!(Pin::new(&mut round_two).poll(&mut cx), Poll::Ready(()));
+    assert_eq!(group.count(), 0);
+}
+
+#[test]
+#[should_panic = "called more often"]
+fn done_without_add_panics() {
+    WaitGroup::new().done();
+}
+
+#[cfg(not(target_family = "wasm"))]
+#[test]
+fn wait_blocking_across_threads() {
+    use std::sync::atomic::{AtomicUsize, Ordering};
+    use std::sync::Arc;
+    use std::thread;
+
+    let group = Arc::new(WaitGroup::new());
+    let finished = Arc::new(AtomicUsize::new(0));
+
+    for _ in 0..8 {
+        group.add(1);
+        let group = group.clone();
+        let finished = finished.clone();
+        thread::spawn(move || {
+            finished.fetch_add(1, Ordering::SeqCst);
+            group.done();
+        });
+    }
+
+    group.wait_blocking();
+    assert_eq!(finished.load(Ordering::SeqCst), 8);
+}